@@ -0,0 +1,51 @@
+// ===========================================================================
+// wt describe - Set or update a worktree's description
+// ===========================================================================
+
+use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::cli::{Error, Result};
+use crate::complete;
+use crate::config::Config;
+use crate::git;
+use crate::meta::{self, WorktreeMeta};
+
+#[derive(Args)]
+pub struct DescribeArgs {
+    /// Branch name (use '.' for current worktree)
+    #[arg(add = ArgValueCompleter::new(complete::complete_worktrees))]
+    branch: String,
+
+    /// Description text (empty string clears it)
+    text: String,
+}
+
+pub fn run(args: DescribeArgs, config: &Config) -> Result<()> {
+    let wt_dir = config.wt_dir()?;
+
+    // Resolve '.' to current branch
+    let branch = if args.branch == "." {
+        git::current_branch()?
+    } else {
+        args.branch
+    };
+
+    config.locate_worktree(&branch)?;
+
+    let meta_path = meta::meta_path_with_fallback(&wt_dir, &branch);
+    let mut loaded = WorktreeMeta::load(&meta_path)
+        .map_err(|e| Error::Other(format!("Cannot describe '{branch}': {e}")))?;
+
+    let text = args.text.trim();
+    loaded.description = (!text.is_empty()).then(|| text.to_string());
+    loaded
+        .save(&meta_path)
+        .map_err(|e| Error::Other(e.to_string()))?;
+
+    match loaded.description {
+        Some(_) => progress!("Updated description for {branch}"),
+        None => progress!("Cleared description for {branch}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// ===========================================================================
+// wt df - Disk usage of the workspaces directory
+// ===========================================================================
+
+use crate::cli::Result;
+use crate::config::Config;
+use crate::util::{dir_size, format_bytes, free_space, GIB};
+
+pub fn run(config: &Config) -> Result<()> {
+    let ws = &config.workspaces_dir;
+
+    println!("Workspaces:   {}", ws.display());
+    println!("Total:        {}", format_bytes(dir_size(ws)));
+
+    // Current project's share, when run inside a repo
+    if let Ok(project_dir) = config.wt_dir() {
+        if project_dir.exists() {
+            println!("This project: {}", format_bytes(dir_size(&project_dir)));
+        }
+    }
+
+    match free_space(ws) {
+        Some(free) => println!("Free:         {}", format_bytes(free)),
+        None => println!("Free:         unknown"),
+    }
+
+    if let Some(warning) = low_space_warning(config) {
+        eprintln!("{warning}");
+    }
+
+    Ok(())
+}
+
+/// Warning text when free space under the workspaces dir is below
+/// `min_free_gb`. `None` when unset, undeterminable, or above threshold.
+pub fn low_space_warning(config: &Config) -> Option<String> {
+    let min_gb = config.min_free_gb?;
+    let free = free_space(&config.workspaces_dir)?;
+    free_space_warning(free, min_gb)
+}
+
+fn free_space_warning(free: u64, min_gb: u64) -> Option<String> {
+    if free >= min_gb.saturating_mul(GIB) {
+        return None;
+    }
+    Some(format!(
+        "Warning: only {} free on the workspaces filesystem (min_free_gb = {min_gb}). \
+         Run 'wt clean' to remove finished worktrees.",
+        format_bytes(free)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_space_warning_below_threshold() {
+        let msg = free_space_warning(GIB, 5).unwrap();
+        assert!(msg.contains("1.0 GiB"));
+        assert!(msg.contains("min_free_gb = 5"));
+    }
+
+    #[test]
+    fn free_space_warning_at_or_above_threshold() {
+        assert!(free_space_warning(5 * GIB, 5).is_none());
+        assert!(free_space_warning(10 * GIB, 5).is_none());
+    }
+}
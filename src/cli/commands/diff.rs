@@ -0,0 +1,92 @@
+// ===========================================================================
+// wt diff - Show a worktree's changes against its merge target
+// ===========================================================================
+
+use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::cli::{Error, Result};
+use crate::complete;
+use crate::config::Config;
+use crate::git;
+use crate::meta;
+use crate::process;
+
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Worktree to diff (default: current; use '.' for current)
+    #[arg(add = ArgValueCompleter::new(complete::complete_worktrees))]
+    branch: Option<String>,
+
+    /// Print insertion/deletion counts instead of the full diff
+    #[arg(long)]
+    stat: bool,
+}
+
+pub fn run(args: DiffArgs, config: &Config) -> Result<()> {
+    let main_repo = git::repo_root()?;
+    let wt_dir = config.wt_dir()?;
+
+    let branch = match args.branch {
+        Some(b) if b != "." => b,
+        _ => git::current_branch()?,
+    };
+
+    let wt_path = config.locate_worktree(&branch)?;
+
+    let target = meta::resolve_effective_target(
+        &wt_dir,
+        &branch,
+        None,
+        |b| git::branch_exists(b).unwrap_or(false),
+        &config.resolve_trunk_for(&branch),
+    );
+
+    if args.stat {
+        let zero = || git::DiffStat {
+            insertions: 0,
+            deletions: 0,
+        };
+        let committed = git::diff_shortstat(&target, &branch).unwrap_or_else(|_| zero());
+        let uncommitted = git::diff_shortstat_in(&wt_path).unwrap_or_else(|_| zero());
+        println!(
+            "Committed:   +{} -{}  ({target}...{branch})",
+            committed.insertions, committed.deletions
+        );
+        println!(
+            "Uncommitted: +{} -{}",
+            uncommitted.insertions, uncommitted.deletions
+        );
+        return Ok(());
+    }
+
+    let env = process::HookEnv {
+        main_repo: &main_repo,
+        worktree: &wt_path,
+        branch: &branch,
+        base_branch: &target,
+    };
+    for command in diff_commands() {
+        let status = process::run_interactive(command, &wt_path, &env)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        if !status.success() {
+            return Err(Error::Other(format!("'{command}' exited with {status}")));
+        }
+    }
+    Ok(())
+}
+
+/// Committed diff (`target...branch`), then uncommitted (`HEAD`).
+///
+/// Refs come in through the WT_* env vars rather than being spliced into
+/// the shell string, so branch names never need quoting.
+fn diff_commands() -> [&'static str; 2] {
+    if cfg!(windows) {
+        ["git diff %WT_BASE_BRANCH%...%WT_BRANCH%", "git diff HEAD"]
+    } else {
+        [
+            r#"git diff "$WT_BASE_BRANCH...$WT_BRANCH""#,
+            "git diff HEAD",
+        ]
+    }
+}
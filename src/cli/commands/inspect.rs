@@ -0,0 +1,84 @@
+// ===========================================================================
+// wt inspect - Run a command in a throwaway detached worktree
+// ===========================================================================
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::cli::{Error, Result};
+use crate::complete;
+use crate::config::Config;
+use crate::git;
+use crate::process;
+use crate::util;
+
+#[derive(Args)]
+pub struct InspectArgs {
+    /// Branch (or any commit-ish) to check out
+    #[arg(add = ArgValueCompleter::new(complete::complete_branches))]
+    branch: String,
+
+    /// Command and its arguments, run as-is inside the temporary worktree
+    /// (after `--`; use `sh -c '...'` for shell syntax)
+    #[arg(last = true, required = true, value_name = "CMD")]
+    cmd: Vec<String>,
+}
+
+pub fn run(args: InspectArgs, config: &Config) -> Result<()> {
+    let main_repo = git::repo_root()?;
+    let workspace_id = git::workspace_id()?;
+
+    // Outside workspaces/ so `wt ls` / `wt clean` never see it.
+    let inspect_dir = config.base_dir.join("inspect");
+    std::fs::create_dir_all(&inspect_dir).map_err(|e| Error::Other(e.to_string()))?;
+    let path = inspect_dir.join(format!("{workspace_id}-{}", util::generate_branch_name()));
+
+    git::create_detached_worktree(&path, &args.branch)?;
+    let guard = TempWorktree { path };
+
+    let env = process::HookEnv {
+        main_repo: &main_repo,
+        worktree: &guard.path,
+        branch: &args.branch,
+        base_branch: &args.branch,
+    };
+    progress!("Inspecting {} at {}", args.branch, guard.path.display());
+    // Ctrl-C goes to the whole foreground process group: let it stop the
+    // command but not wt, so the guard still removes the worktree. A
+    // handler (unlike SIG_IGN) is reset on exec, so the child still dies.
+    let _ = ctrlc::set_handler(|| {});
+    // `required = true` guarantees a program name.
+    let (program, program_args) = args.cmd.split_first().expect("cmd is required");
+    let status = process::run_program(program, program_args, &guard.path, &env);
+
+    // Remove before reporting, so a failing command still cleans up.
+    drop(guard);
+
+    let status = status.map_err(|e| Error::Other(e.to_string()))?;
+    if !status.success() {
+        return Err(Error::Other(format!("Command exited with {status}")));
+    }
+    Ok(())
+}
+
+/// Removes the temporary worktree on drop — including on early returns and
+/// panics — so an inspect never leaves a checkout behind.
+struct TempWorktree {
+    path: PathBuf,
+}
+
+impl Drop for TempWorktree {
+    fn drop(&mut self) {
+        if git::remove_worktree(&self.path, true).is_err() {
+            // Fallback: delete the dir; git forgets it on the next prune.
+            remove_leftover(&self.path);
+        }
+    }
+}
+
+fn remove_leftover(path: &Path) {
+    std::fs::remove_dir_all(path).ok();
+    let _ = git::git_command(&["worktree", "prune"]).output();
+}
@@ -3,30 +3,39 @@
 // ===========================================================================
 
 use std::collections::HashSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use clap::Args;
 
-use crate::cli::{write_path_file, Result};
-use crate::config::Config;
+use crate::cli::{write_path_file, Error, Result};
+use crate::config::{self, Config};
 use crate::git;
 use crate::meta;
+use crate::prompt;
 
 #[derive(Args)]
 pub struct CleanArgs {
     /// Preview which worktrees would be cleaned without removing them
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Also remove worktrees whose branch is merged into its target even
+    /// if they have uncommitted changes (discarded; needs --force)
+    #[arg(long, requires = "force")]
+    pub merged: bool,
+
+    /// Confirm discarding uncommitted changes with --merged
+    #[arg(short, long, requires = "merged")]
+    pub force: bool,
 }
 
 pub fn run(args: CleanArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
     // Get main repo path before any operations
     let main_path = git::repo_root()?;
-    let workspace_id = git::workspace_id()?;
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
+    let wt_dir = config.wt_dir()?;
 
     if !wt_dir.exists() {
-        eprintln!("No worktrees to clean.");
+        progress!("No worktrees to clean.");
         return Ok(());
     }
 
@@ -37,16 +46,29 @@ pub fn run(args: CleanArgs, config: &Config, path_file: Option<&Path>) -> Result
         .collect();
 
     let worktrees = git::list_worktrees()?;
+    let main_canon = main_path
+        .canonicalize()
+        .unwrap_or_else(|_| main_path.clone());
     let mut cleaned = 0;
     let mut checked = 0;
     let mut skipped_dirty = 0;
+    let mut skipped_in_use = 0;
+    let mut failed = 0;
     let mut cleaned_current = false;
+    // (worktree, branch, reason, dirty): decided first, removed after one
+    // confirmation
+    let mut doomed: Vec<(&git::WorktreeInfo, &str, String, bool)> = Vec::new();
 
-    for wt in worktrees {
+    for wt in &worktrees {
         if !wt.path.starts_with(&wt_dir) {
             continue;
         }
 
+        // Never touch the main repo, even if the workspaces dir overlaps it.
+        if canonical(&wt.path) == main_canon {
+            continue;
+        }
+
         let Some(branch) = wt.branch.as_ref() else {
             continue;
         };
@@ -63,51 +85,87 @@ pub fn run(args: CleanArgs, config: &Config, path_file: Option<&Path>) -> Result
             branch,
             None,
             |b| known_branches.contains(b),
-            &trunk,
+            config::match_trunk_rule(&config.trunk_rules, branch).unwrap_or(&trunk),
         );
 
-        // Skip worktrees that still differ from target — committed diff is
-        // the cheap check, run it before the per-worktree dirty status call.
-        if git::has_diff_from(branch, &target).unwrap_or(true) {
+        // A summary that can't be computed (target gone, broken worktree)
+        // keeps the worktree; so do commits not yet in the target.
+        let Ok(summary) = git::work_summary(&target, &wt.path) else {
+            continue;
+        };
+        if summary.commits_ahead > 0 {
             continue;
         }
 
-        // Dirty worktrees aren't clean even with no committed diff: git
+        // Dirty worktrees aren't clean even with no commits ahead: git
         // refuses non-force removal anyway, and silently discarding
-        // in-flight work would be a footgun.
-        let dirty = git::uncommitted_count_in(&wt.path).unwrap_or(0);
-        if dirty > 0 {
+        // in-flight work would be a footgun — unless --merged --force says
+        // the leftovers of a merged branch are scratch.
+        let dirty = summary.uncommitted;
+        let reason = if dirty == 0 {
+            format!("no diff from {target}")
+        } else if args.merged && git::is_ancestor(branch, &target).unwrap_or(false) {
+            format!("merged into {target}, discarding {dirty} uncommitted change(s)")
+        } else {
             eprintln!("Skipping {branch}: {dirty} uncommitted change(s)");
             skipped_dirty += 1;
             continue;
-        }
+        };
 
-        if args.dry_run {
-            eprintln!("Would clean (no diff from {target}): {branch}");
-            cleaned += 1;
+        // A branch checked out in another worktree (main repo included)
+        // can't be deleted — git refuses. Skip up front instead of removing
+        // the worktree and leaving its branch behind.
+        if let Some(other) = checked_out_elsewhere(&worktrees, &wt.path, branch) {
+            eprintln!(
+                "Skipping {branch}: also checked out at {}",
+                other.path.display()
+            );
+            skipped_in_use += 1;
             continue;
         }
 
-        let inside = git::is_cwd_inside(&wt.path);
-
-        eprintln!("Cleaning worktree (no diff from {target}): {branch}");
+        doomed.push((wt, branch, reason, dirty > 0));
+    }
 
-        if let Err(e) = git::remove_worktree(&wt.path, false) {
-            eprintln!("Warning: failed to remove worktree {branch}: {e}");
-            continue;
+    if args.dry_run {
+        for (_, branch, reason, _) in &doomed {
+            eprintln!("Would clean ({reason}): {branch}");
+        }
+        cleaned = doomed.len();
+    } else {
+        if !doomed.is_empty() && !confirm_removal(&doomed)? {
+            progress!("Aborted; nothing removed.");
+            return Ok(());
         }
 
-        // Switch to main repo before deleting branch — git refuses to
-        // delete the branch a worktree is on.
-        std::env::set_current_dir(&main_path).ok();
-        git::delete_branch(branch, false).ok();
-
-        crate::meta::remove_meta(&wt_dir, branch);
-
-        cleaned += 1;
-
-        if inside {
-            cleaned_current = true;
+        for (wt, branch, reason, dirty) in &doomed {
+            let inside = git::is_cwd_inside(&wt.path);
+
+            progress!("Cleaning worktree ({reason}): {branch}");
+
+            if let Err(e) = git::remove_worktree(&wt.path, *dirty) {
+                eprintln!("Warning: failed to remove worktree {branch}: {e}");
+                failed += 1;
+                continue;
+            }
+
+            // Switch to main repo before deleting branch — git refuses to
+            // delete the branch a worktree is on.
+            std::env::set_current_dir(&main_path).ok();
+            crate::meta::remove_meta(&wt_dir, branch);
+
+            // Only count as cleaned once the branch is actually gone; a
+            // refused deletion leaves a live branch behind.
+            if let Err(e) = git::delete_branch(branch, false) {
+                eprintln!("Warning: removed worktree {branch} but kept its branch: {e}");
+                failed += 1;
+            } else {
+                cleaned += 1;
+            }
+
+            if inside {
+                cleaned_current = true;
+            }
         }
     }
 
@@ -118,15 +176,23 @@ pub fn run(args: CleanArgs, config: &Config, path_file: Option<&Path>) -> Result
     };
 
     if checked == 0 {
-        eprintln!("No worktrees to clean.");
+        progress!("No worktrees to clean.");
     } else if cleaned == 0 {
-        eprintln!("No worktrees to clean (all have changes).");
+        progress!("No worktrees to clean (all have changes).");
     } else {
-        eprintln!("{cleaned} worktree(s) {verb}.");
+        progress!("{cleaned} worktree(s) {verb}.");
     }
     if skipped_dirty > 0 {
         eprintln!("{skipped_dirty} worktree(s) skipped due to uncommitted changes.");
     }
+    if skipped_in_use > 0 {
+        eprintln!(
+            "{skipped_in_use} worktree(s) skipped because their branch is checked out elsewhere."
+        );
+    }
+    if failed > 0 {
+        eprintln!("{failed} worktree(s) could not be fully cleaned.");
+    }
 
     // Write main repo path for shell to cd if we were inside a cleaned worktree
     if !args.dry_run && path_file.is_some() && cleaned_current {
@@ -135,3 +201,33 @@ pub fn run(args: CleanArgs, config: &Config, path_file: Option<&Path>) -> Result
 
     Ok(())
 }
+
+/// One prompt for the whole batch (`--yes` skips it; without a TTY it is
+/// required): count, then each branch and why it goes.
+fn confirm_removal(doomed: &[(&git::WorktreeInfo, &str, String, bool)]) -> Result<bool> {
+    eprintln!(
+        "About to remove {} worktree(s) and their branches:",
+        doomed.len()
+    );
+    for (_, branch, reason, _) in doomed {
+        eprintln!("  {branch} ({reason})");
+    }
+    prompt::confirm(&format!("Remove these {} worktree(s)?", doomed.len()))
+        .map_err(|e| Error::Other(e.to_string()))
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Find another worktree (main repo included) that has `branch` checked out.
+fn checked_out_elsewhere<'a>(
+    worktrees: &'a [git::WorktreeInfo],
+    path: &Path,
+    branch: &str,
+) -> Option<&'a git::WorktreeInfo> {
+    let own = canonical(path);
+    worktrees
+        .iter()
+        .find(|wt| wt.branch.as_deref() == Some(branch) && canonical(&wt.path) != own)
+}
@@ -2,42 +2,142 @@
 // wt new - Create a new worktree
 // ===========================================================================
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use clap::Args;
 use clap_complete::engine::ArgValueCompleter;
+use serde::Serialize;
 
 use crate::cli::{write_path_file, write_path_file_lines, Error, Result};
 use crate::complete;
-use crate::config::Config;
+use crate::config::{self, Config};
 use crate::git;
-use crate::meta::{self, WorktreeMeta};
+use crate::meta::{SnapState, WorktreeMeta};
 use crate::process;
 use crate::util;
+use crate::workspace::{CreateLock, Workspace};
 
 #[derive(Args)]
 pub struct NewArgs {
     /// Branch name (random name like 'swift-fox' if not provided)
     branch: Option<String>,
 
-    /// Base branch to create from and merge back to (default: current branch)
+    /// Base branch to create from and merge back to (default: current
+    /// branch); a commit (`HEAD~1`, `HEAD@{2}`) is only the starting point
     #[arg(long, value_name = "BRANCH", add = ArgValueCompleter::new(complete::complete_branches))]
     base: Option<String>,
 
     /// Run command in snap mode: create -> run -> merge -> cleanup
-    #[arg(short, long, value_name = "CMD")]
+    /// (`@name` uses the `[agent]` preset of that name; without CMD,
+    /// general.default_snap_command)
+    #[arg(
+        short,
+        long,
+        value_name = "CMD",
+        num_args = 0..=1,
+        default_missing_value = ""
+    )]
     snap: Option<String>,
+
+    /// Short note on what this worktree is for (shown in `wt ls` / `wt status`)
+    #[arg(long, value_name = "TEXT")]
+    desc: Option<String>,
+
+    /// Print the result as JSON to stdout (path file is still written)
+    #[arg(long)]
+    json: bool,
+
+    /// Create even if the project's max_worktrees limit is reached
+    #[arg(short, long)]
+    force: bool,
+
+    /// Create N randomly named worktrees at once (cd into the last one)
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 1,
+          value_parser = clap::value_parser!(u32).range(1..))]
+    count: u32,
+
+    /// Skip the configured copy_files for this worktree
+    #[arg(long, conflicts_with = "copy")]
+    no_copy: bool,
+
+    /// Also copy files matching this pattern (repeatable; adds to copy_files)
+    #[arg(long, value_name = "GLOB")]
+    copy: Vec<String>,
+
+    /// Fetch general.remote (default: origin) and start from its copy of
+    /// the base branch instead of the local one
+    #[arg(long)]
+    pull: bool,
+
+    /// Run the post_create hooks concurrently, output prefixed per hook
+    /// (also hooks.post_create_parallel)
+    #[arg(long)]
+    parallel: bool,
 }
 
-pub fn run(args: NewArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
+impl NewArgs {
+    /// Plain `wt new [branch] --base <base>`, for commands that chain into
+    /// creating a worktree (e.g. `wt merge --then-new`).
+    pub fn chained(branch: Option<String>, base: String) -> Self {
+        Self {
+            branch,
+            base: Some(base),
+            snap: None,
+            desc: None,
+            json: false,
+            force: false,
+            count: 1,
+            no_copy: false,
+            copy: Vec::new(),
+            pull: false,
+            parallel: false,
+        }
+    }
+}
+
+/// One created worktree; also the line `wt new --json` prints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NewOutcome {
+    pub branch: String,
+    pub path: PathBuf,
+    pub base: String,
+    pub trunk: String,
+    /// Agent command the snap loop will run (`-s`), after preset expansion
+    pub snap_command: Option<String>,
+}
+
+/// Returns one outcome per created worktree (several with `--count`).
+pub fn run(
+    mut args: NewArgs,
+    config: &Config,
+    path_file: Option<&Path>,
+) -> Result<Vec<NewOutcome>> {
+    args.snap = match args.snap {
+        Some(cmd) => Some(resolve_snap_command(config, snap_or_default(config, cmd)?)),
+        None => None,
+    };
+
     // Ensure we're in a git repo
-    let repo_root = git::repo_root()?;
-    let workspace_id = git::workspace_id()?;
-    let workspace_dir = config.workspaces_dir.join(&workspace_id);
+    let ws = Workspace::open(config.clone())?;
+    let workspace_dir = ws.wt_dir();
+
+    if args.count > 1 {
+        check_count_args(&args)?;
+    }
+    args.branch = args.branch.map(|b| config.prefixed_branch(&b));
+
+    // Checked before anything is created: otherwise the worktree is left
+    // behind with a snap session no loop will ever finish. With --json the
+    // caller owns the agent loop, so no wrapper is required.
+    if args.snap.is_some() && path_file.is_none() && !args.json {
+        return Err(Error::Other(
+            "Snap mode requires shell integration. Run 'wt setup' first.".into(),
+        ));
+    }
 
     // Nested snap stacks two loops in the parent shell and breaks cwd tracking
     // when the inner one finishes.
-    if args.snap.is_some() && git::is_cwd_inside(&workspace_dir) {
+    if args.snap.is_some() && git::is_cwd_inside(workspace_dir) {
         return Err(Error::Other(
             "Refusing to start snap mode inside an existing worktree.\n\
              Run 'wt cd' to return to the main repo, then retry."
@@ -48,55 +148,281 @@ pub fn run(args: NewArgs, config: &Config, path_file: Option<&Path>) -> Result<(
     // Determine trunk branch
     let trunk = config.resolve_trunk();
 
-    // Resolve base branch: --base flag > current branch > trunk.
+    // Resolve base branch: --base flag > matching trunk_rules > current branch > trunk.
     // Determines both the checkout starting point and the default merge/sync target.
-    let base_branch = if let Some(ref b) = args.base {
+    let rule_trunk = args
+        .branch
+        .as_deref()
+        .and_then(|b| config::match_trunk_rule(&config.trunk_rules, b));
+    // A commit --base is resolved once, up front (`HEAD@{1}` moves as soon
+    // as anything touches HEAD); the merge target then falls back as if no
+    // --base was given.
+    let (base_arg, base_commit) = match args.base.as_deref() {
+        Some(b) if is_commit_expr(b) || !git::branch_exists(b)? => {
+            let Some(commit) = git::resolve_commit(b)? else {
+                return Err(Error::Other(format!(
+                    "Branch '{b}' does not exist (nor does a commit by that name)"
+                )));
+            };
+            (None, Some((b, commit)))
+        }
+        base => (base, None),
+    };
+    let base_branch = if let Some(b) = base_arg.or(rule_trunk) {
         if !git::branch_exists(b)? {
             return Err(Error::Other(format!("Branch '{b}' does not exist")));
         }
-        b.clone()
+        b.to_string()
     } else {
         // Detached HEAD falls back to trunk.
-        git::current_branch()
-            .ok()
-            .filter(|b| b != "HEAD")
-            .unwrap_or_else(|| trunk.clone())
+        git::current_branch().ok().unwrap_or_else(|| trunk.clone())
+    };
+
+    // Checkout starting point; the meta keeps the local base as merge target.
+    let start = match base_commit {
+        Some((expr, _)) if args.pull => {
+            return Err(Error::Other(format!(
+                "--pull needs a branch as --base, not the commit '{expr}'"
+            )));
+        }
+        Some((expr, commit)) => {
+            progress!("Starting from {expr} ({})", short_id(&commit));
+            commit
+        }
+        None if args.pull => pull_start(&config.remote, &base_branch)?,
+        None => base_branch.clone(),
+    };
+
+    // Counted under the create lock, so concurrent `wt new` runs can't both
+    // slip under the limit. `pending`: further worktrees this run will add.
+    let enforce_limit = |pending: usize| -> Result<()> {
+        let Some(max) = config.max_worktrees else {
+            return Ok(());
+        };
+        let existing = git::list_worktrees()?
+            .iter()
+            .filter(|wt| wt.path.starts_with(workspace_dir))
+            .filter(|wt| wt.branch.as_deref() != Some(trunk.as_str()))
+            .count();
+        check_worktree_limit(existing + pending, max, args.force)
+    };
+
+    // Warn only — a full disk will fail the checkout loudly enough on its own.
+    if let Some(warning) = super::super::df::low_space_warning(config) {
+        eprintln!("{warning}");
+    }
+
+    // Remote-tracking refs are local state (as of the last fetch), so the
+    // remote check works offline; a failing lookup counts as "not taken".
+    let remotes = git::remotes().unwrap_or_default();
+    let on_remote = |name: &str| {
+        remotes
+            .iter()
+            .find(|r| git::remote_branch_exists(r, name).unwrap_or(false))
+            .cloned()
+    };
+
+    // User-provided names (e.g. issue keys like PROJ-123) are used verbatim:
+    // validated, never suffixed. Only generated names get the `-{i}` suffix.
+    let generate = || {
+        let name = util::generate_unique_branch_name(|n| {
+            let n = config.prefixed_branch(n);
+            git::branch_exists(&n).unwrap_or(false) || on_remote(&n).is_some()
+        });
+        config.prefixed_branch(&name)
     };
 
-    // Generate or use provided branch name
-    let branch = args.branch.unwrap_or_else(|| {
-        util::generate_unique_branch_name(|n| git::branch_exists(n).unwrap_or(false))
-    });
+    let copy = copy_patterns(&config.copy_files, args.no_copy, &args.copy);
+    let parallel = args.parallel || config.hooks.post_create_parallel.unwrap_or(false);
+
+    if args.count > 1 {
+        // Each name is generated after the previous branch exists, so the
+        // batch never collides with itself.
+        let mut outcomes = Vec::new();
+        for i in 0..args.count as usize {
+            let lock = ws.lock_create()?;
+            // The rest of the batch counts in full: refuse up front rather
+            // than stop halfway.
+            enforce_limit(args.count as usize - 1 - i)?;
+            let branch = generate();
+            check_base_differs(&branch, &base_branch)?;
+            let meta = new_meta(&base_branch, args.desc.clone(), None);
+            let path = create_one(&ws, lock, &branch, &start, meta, &copy, parallel)?;
+            let outcome = NewOutcome {
+                branch,
+                path,
+                base: base_branch.clone(),
+                trunk: trunk.clone(),
+                snap_command: None,
+            };
+            // Reported as each one lands, so a later failure still leaves
+            // the earlier worktrees visible.
+            if args.json {
+                print_json(&outcome)?;
+            } else {
+                print_created(&outcome);
+            }
+            outcomes.push(outcome);
+        }
+        if let Some(last) = outcomes.last() {
+            write_path_file(path_file, &last.path)?;
+        }
+        return Ok(outcomes);
+    }
 
-    // Worktree path
-    let wt_dir = &workspace_dir;
-    let wt_path = wt_dir.join(&branch);
+    // Parallel `wt new` runs would otherwise both see a name as free.
+    let lock = ws.lock_create()?;
+    enforce_limit(0)?;
+    let branch = match args.branch {
+        Some(b) => {
+            git::validate_branch_name(&b)?;
+            if !git::branch_exists(&b)? {
+                if let Some(remote) = on_remote(&b) {
+                    eprintln!(
+                        "Warning: '{b}' already exists on {remote}. The new local branch \
+                         starts from {start} and does not track {remote}/{b}."
+                    );
+                }
+            }
+            b
+        }
+        None => generate(),
+    };
+    check_base_differs(&branch, &base_branch)?;
+
+    let meta = new_meta(&base_branch, args.desc, args.snap.clone());
+    let path = create_one(&ws, lock, &branch, &start, meta, &copy, parallel)?;
 
-    // Create workspace directory if needed
-    std::fs::create_dir_all(wt_dir).map_err(|e| Error::Other(e.to_string()))?;
+    let outcome = NewOutcome {
+        branch,
+        path,
+        base: base_branch,
+        trunk,
+        snap_command: args.snap,
+    };
+    present(&outcome, args.json, path_file)?;
+    Ok(vec![outcome])
+}
+
+/// Output for a single `wt new`: the JSON line, then either the snap
+/// handoff (path + command) or the plain path for the shell wrapper.
+fn present(outcome: &NewOutcome, json: bool, path_file: Option<&Path>) -> Result<()> {
+    if json {
+        print_json(outcome)?;
+    }
+
+    // No wrapper means --json (checked in `run`): the caller runs the agent.
+    if let Some(cmd) = &outcome.snap_command {
+        if path_file.is_some() {
+            write_path_file_lines(path_file, &[&outcome.path.display().to_string(), cmd])?;
+        }
+        return Ok(());
+    }
+
+    if path_file.is_some() {
+        write_path_file(path_file, &outcome.path)?;
+    } else if !json {
+        print_created(outcome);
+    }
+    Ok(())
+}
+
+fn print_created(outcome: &NewOutcome) {
+    progress!(
+        "Created worktree: {} (from {})",
+        outcome.branch,
+        outcome.base
+    );
+    progress!("Path: {}", outcome.path.display());
+}
+
+/// Can only be a commit, never a branch name: git's ref rules forbid
+/// `~ ^ :` and `@{`, and a lone `@` is HEAD.
+fn is_commit_expr(base: &str) -> bool {
+    base == "@" || base.contains(['~', '^', ':']) || base.contains("@{")
+}
+
+fn short_id(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+/// `--pull`: fetch `remote`, then `<remote>/<base>` as the starting point.
+fn pull_start(remote: &str, base: &str) -> Result<String> {
+    progress!("Fetching {remote}...");
+    git::fetch_remote(remote)?;
+    if !git::remote_branch_exists(remote, base)? {
+        return Err(Error::Other(format!(
+            "--pull: {remote} has no branch '{base}'"
+        )));
+    }
+    Ok(format!("{remote}/{base}"))
+}
 
-    git::create_worktree(&wt_path, &branch, &base_branch)?;
+/// Meta for a fresh worktree; a snap command also opens its session.
+fn new_meta(base_branch: &str, desc: Option<String>, snap: Option<String>) -> WorktreeMeta {
+    let mut meta = WorktreeMeta::new(base_branch.to_string());
+    meta.description = desc.filter(|d| !d.trim().is_empty());
+    meta.snap_command = snap.clone();
+    meta.snap_state = snap.map(SnapState::new);
+    meta
+}
 
-    let meta = WorktreeMeta::new(base_branch);
-    let meta_path = meta::meta_path(wt_dir, &branch);
-    meta.save(&meta_path)
-        .map_err(|e| Error::Other(e.to_string()))?;
+/// Config `copy_files` plus `--copy` patterns, or none with `--no-copy`.
+fn copy_patterns(configured: &[String], no_copy: bool, extra: &[String]) -> Vec<String> {
+    if no_copy {
+        return Vec::new();
+    }
+    configured.iter().chain(extra).cloned().collect()
+}
 
-    // Copy files from main repo
-    copy_files(&repo_root, &wt_path, config)?;
+/// Worktree + meta + copy files (`Workspace::create_from`), then warnings
+/// and post_create hooks for one branch. `lock` is released before the hooks.
+fn create_one(
+    ws: &Workspace,
+    lock: CreateLock,
+    branch: &str,
+    start: &str,
+    meta: WorktreeMeta,
+    copy: &[String],
+    parallel: bool,
+) -> Result<PathBuf> {
+    let config = ws.config();
+    let repo_root = ws.repo_root();
+    let base_branch = meta.base_branch.clone();
+    let created = ws.create_from(branch, start, meta, copy)?;
+    // The branch exists now; hooks (often slow installs) run unlocked.
+    drop(lock);
+    let wt_path = created.path;
+
+    for failure in &created.copy_failures {
+        eprintln!("Warning: failed to copy {failure}");
+    }
+    if !created.copy_failures.is_empty() {
+        eprintln!(
+            "Warning: {} file(s) from copy_files were not copied.",
+            created.copy_failures.len()
+        );
+    }
 
     // Run post_create hooks. On failure, leave the worktree in place — the
     // user usually wants to fix the hook (e.g. install missing tool) and
     // resume manually rather than have us silently rm a half-created tree.
     if !config.hooks.post_create.is_empty() {
-        eprintln!("Running post-create hooks...");
+        progress!("Running post-create hooks...");
         let env = process::HookEnv {
-            main_repo: &repo_root,
+            main_repo: repo_root,
             worktree: &wt_path,
-            branch: &branch,
-            base_branch: &meta.base_branch,
+            branch,
+            base_branch: &base_branch,
+        };
+        let log = config.hooks_log(branch);
+        let hooks = &config.hooks.post_create;
+        let result = if parallel && hooks.len() > 1 {
+            process::run_hooks_parallel(hooks, &wt_path, &env, log.as_deref())
+        } else {
+            process::run_hooks(hooks, &wt_path, &env, log.as_deref())
         };
-        if let Err(e) = process::run_hooks(&config.hooks.post_create, &wt_path, &env) {
+        if let Err(e) = result {
             eprintln!();
             eprintln!("post_create hook failed: {e}");
             eprintln!("Worktree '{branch}' was created at: {}", wt_path.display());
@@ -105,143 +431,206 @@ pub fn run(args: NewArgs, config: &Config, path_file: Option<&Path>) -> Result<(
         }
     }
 
-    // Handle snap mode - write path + command for shell wrapper to execute
-    if let Some(cmd) = args.snap {
-        if path_file.is_some() {
-            write_path_file_lines(path_file, &[&wt_path.display().to_string(), &cmd])?;
-        } else {
-            return Err(Error::Other(
-                "Snap mode requires shell integration. Run 'wt setup' first.".into(),
-            ));
-        }
-        return Ok(());
-    }
-
-    // Write path for shell integration
-    if path_file.is_some() {
-        write_path_file(path_file, &wt_path)?;
-    } else {
-        eprintln!("Created worktree: {branch} (from {})", meta.base_branch);
-        eprintln!("Path: {}", wt_path.display());
-    }
+    Ok(wt_path)
+}
 
+/// One `NewOutcome` line on stdout (one per worktree with `--count`).
+fn print_json(outcome: &NewOutcome) -> Result<()> {
+    let json = serde_json::to_string(outcome).map_err(|e| Error::Other(e.to_string()))?;
+    println!("{json}");
     Ok(())
 }
 
-/// Reject patterns that could escape the repo root.
-///
-/// Without this guard, a malicious `.agent-worktree.toml` could exfiltrate
-/// host files into the worktree via `/abs/path` or `..` traversal — the
-/// downstream `strip_prefix` would silently skip mismatches.
-fn validate_copy_pattern(pattern: &str) -> Result<()> {
-    if pattern.starts_with('/') {
+/// `--count` only makes random names, and one snap loop can't drive several
+/// worktrees.
+fn check_count_args(args: &NewArgs) -> Result<()> {
+    if let Some(branch) = &args.branch {
         return Err(Error::Other(format!(
-            "copy_files pattern '{pattern}' cannot start with '/' (absolute path)"
+            "--count creates randomly named worktrees; drop the branch name '{branch}'."
         )));
     }
-    if pattern.split(['/', '\\']).any(|seg| seg == "..") {
+    if args.snap.is_some() {
+        return Err(Error::Other(
+            "--count cannot be combined with -s/--snap (one agent per snap session).".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Expand `@name` to its `[agent]` preset; anything else runs as typed.
+pub fn resolve_snap_command(config: &Config, cmd: String) -> String {
+    match config::resolve_agent_preset(&config.agent_presets, &cmd) {
+        Some(preset) => preset.to_string(),
+        None => {
+            if cmd.starts_with('@') {
+                eprintln!("Warning: no [agent] preset '{cmd}'; running it as a command");
+            }
+            cmd
+        }
+    }
+}
+
+/// Bare `-s` (empty `cmd`) falls back to `general.default_snap_command`.
+fn snap_or_default(config: &Config, cmd: String) -> Result<String> {
+    if !cmd.is_empty() {
+        return Ok(cmd);
+    }
+    config.default_snap_command.clone().ok_or_else(|| {
+        Error::Other(
+            "-s/--snap needs a command: pass one (wt new -s <cmd>) \
+             or set general.default_snap_command."
+                .into(),
+        )
+    })
+}
+
+/// `wt new foo --base foo` (or `wt new main` while on main) is almost always
+/// a typo; catch it before git reports something confusing.
+fn check_base_differs(branch: &str, base: &str) -> Result<()> {
+    if branch == base {
         return Err(Error::Other(format!(
-            "copy_files pattern '{pattern}' cannot contain '..'"
+            "Cannot create '{branch}' from itself. Pass a different --base, \
+             or pick another branch name."
         )));
     }
     Ok(())
 }
 
-fn copy_files(from: &Path, to: &Path, config: &Config) -> Result<()> {
-    use ignore::overrides::OverrideBuilder;
-    use ignore::WalkBuilder;
-
-    if config.copy_files.is_empty() {
+/// Guardrail against runaway agent spawning: refuse once the project already
+/// has `max` managed worktrees, unless `--force` is given.
+fn check_worktree_limit(existing: usize, max: usize, force: bool) -> Result<()> {
+    if force || existing < max {
         return Ok(());
     }
+    Err(Error::Other(format!(
+        "Worktree limit reached ({existing}/{max}, max_worktrees in .agent-worktree.toml).\n\
+         Run 'wt clean' to remove finished worktrees, or pass --force to override."
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(snap: Option<&str>) -> NewOutcome {
+        NewOutcome {
+            branch: "feat".into(),
+            path: PathBuf::from("/tmp/ws/feat"),
+            base: "main".into(),
+            trunk: "main".into(),
+            snap_command: snap.map(str::to_string),
+        }
+    }
 
-    for pattern in &config.copy_files {
-        validate_copy_pattern(pattern)?;
+    #[test]
+    fn new_outcome_json_keeps_field_names() {
+        let json: serde_json::Value = serde_json::to_value(outcome(Some("claude"))).unwrap();
+        assert_eq!(json["branch"], "feat");
+        assert_eq!(json["path"], "/tmp/ws/feat");
+        assert_eq!(json["base"], "main");
+        assert_eq!(json["trunk"], "main");
+        assert_eq!(json["snap_command"], "claude");
     }
 
-    // Build gitignore-style matcher
-    // Patterns work like .gitignore: "*.md" matches all .md files, "/*.md" matches only root
-    let mut builder = OverrideBuilder::new(from);
-    for pattern in &config.copy_files {
-        builder
-            .add(pattern)
-            .map_err(|e| Error::Other(format!("invalid pattern '{}': {}", pattern, e)))?;
+    #[test]
+    fn present_writes_snap_handoff_to_path_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("path");
+        present(&outcome(Some("claude")), false, Some(&file)).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file)
+                .unwrap()
+                .lines()
+                .collect::<Vec<_>>(),
+            ["/tmp/ws/feat", "claude"]
+        );
+
+        present(&outcome(None), false, Some(&file)).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap().trim(),
+            "/tmp/ws/feat"
+        );
     }
-    let overrides = builder.build().map_err(|e| Error::Other(e.to_string()))?;
 
-    // follow_links=false: a symlink in the repo could otherwise pull files
-    // from outside the repo into the worktree.
-    let walker = WalkBuilder::new(from)
-        .overrides(overrides)
-        .standard_filters(false)
-        .follow_links(false)
-        .build();
+    #[test]
+    fn is_commit_expr_only_for_non_branch_syntax() {
+        assert!(is_commit_expr("HEAD~1"));
+        assert!(is_commit_expr("HEAD@{2}"));
+        assert!(is_commit_expr("main^"));
+        assert!(is_commit_expr("@"));
+        assert!(is_commit_expr("stash@{0}"));
+        assert!(!is_commit_expr("main"));
+        assert!(!is_commit_expr("user@feature"));
+        assert!(!is_commit_expr("agent/fix-1"));
+    }
 
-    for entry in walker.flatten() {
-        let path = entry.path();
-        if path.is_file() {
-            let rel = match path.strip_prefix(from) {
-                Ok(r) => r,
-                Err(e) => {
-                    eprintln!(
-                        "Warning: failed to strip prefix for {}: {e}",
-                        path.display()
-                    );
-                    continue;
-                }
-            };
-            let dest = to.join(rel);
+    #[test]
+    fn check_base_differs_rejects_same() {
+        let err = check_base_differs("foo", "foo").unwrap_err();
+        assert!(err.to_string().contains("from itself"));
+    }
 
-            if let Some(parent) = dest.parent() {
-                if let Err(e) = std::fs::create_dir_all(parent) {
-                    eprintln!(
-                        "Warning: failed to create directory {}: {e}",
-                        parent.display()
-                    );
-                    continue;
-                }
-            }
+    #[test]
+    fn check_base_differs_accepts_other() {
+        assert!(check_base_differs("foo", "main").is_ok());
+    }
 
-            if let Err(e) = std::fs::copy(path, &dest) {
-                eprintln!("Warning: failed to copy {}: {e}", rel.display());
-            }
-        }
+    fn count_args(branch: Option<&str>, snap: Option<&str>) -> NewArgs {
+        let mut args = NewArgs::chained(branch.map(str::to_string), "main".into());
+        args.snap = snap.map(str::to_string);
+        args.count = 3;
+        args
     }
 
-    Ok(())
-}
+    #[test]
+    fn check_count_args_accepts_plain_batch() {
+        assert!(check_count_args(&count_args(None, None)).is_ok());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn check_count_args_rejects_branch_name() {
+        let err = check_count_args(&count_args(Some("feat"), None)).unwrap_err();
+        assert!(err.to_string().contains("'feat'"));
+    }
 
     #[test]
-    fn validate_copy_pattern_accepts_relative_glob() {
-        assert!(validate_copy_pattern(".env").is_ok());
-        assert!(validate_copy_pattern(".env.*").is_ok());
-        assert!(validate_copy_pattern("config/*.toml").is_ok());
-        assert!(validate_copy_pattern("**/.secret").is_ok());
+    fn check_count_args_rejects_snap() {
+        let err = check_count_args(&count_args(None, Some("claude"))).unwrap_err();
+        assert!(err.to_string().contains("--snap"));
     }
 
     #[test]
-    fn validate_copy_pattern_rejects_absolute_path() {
-        let err = validate_copy_pattern("/etc/passwd").unwrap_err();
-        assert!(err.to_string().contains("absolute"));
+    fn check_worktree_limit_below_threshold() {
+        assert!(check_worktree_limit(2, 3, false).is_ok());
     }
 
     #[test]
-    fn validate_copy_pattern_rejects_parent_traversal() {
-        let err = validate_copy_pattern("../secrets").unwrap_err();
-        assert!(err.to_string().contains(".."));
+    fn check_worktree_limit_at_threshold() {
+        let err = check_worktree_limit(3, 3, false).unwrap_err();
+        assert!(err.to_string().contains("limit reached (3/3"));
+        assert!(err.to_string().contains("--force"));
+    }
 
-        let err = validate_copy_pattern("config/../../etc/passwd").unwrap_err();
-        assert!(err.to_string().contains(".."));
+    #[test]
+    fn check_worktree_limit_above_threshold() {
+        assert!(check_worktree_limit(5, 3, false).is_err());
+    }
+
+    #[test]
+    fn check_worktree_limit_force_bypasses() {
+        assert!(check_worktree_limit(3, 3, true).is_ok());
+        assert!(check_worktree_limit(5, 3, true).is_ok());
     }
 
     #[test]
-    fn validate_copy_pattern_rejects_backslash_traversal() {
-        // Windows-style path separator should still be rejected.
-        let err = validate_copy_pattern("..\\secrets").unwrap_err();
-        assert!(err.to_string().contains(".."));
+    fn copy_patterns_merges_or_skips() {
+        let configured = vec![".env".to_string()];
+        let extra = vec!["*.local".to_string()];
+        assert_eq!(
+            copy_patterns(&configured, false, &extra),
+            [".env", "*.local"]
+        );
+        assert_eq!(copy_patterns(&configured, false, &[]), [".env"]);
+        assert!(copy_patterns(&configured, true, &[]).is_empty());
     }
 }
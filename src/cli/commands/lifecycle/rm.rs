@@ -7,16 +7,19 @@ use std::path::Path;
 use clap::Args;
 use clap_complete::engine::ArgValueCompleter;
 
-use crate::cli::{write_path_file, Error, Result};
+use crate::cli::{locate_worktree, write_path_file, Error, Result};
 use crate::complete;
 use crate::config::Config;
 use crate::git;
+use crate::prompt;
+use crate::workspace::Workspace;
 
 #[derive(Args)]
 pub struct RmArgs {
-    /// Branch name to remove (use '.' for current worktree)
+    /// Branch name to remove (use '.' for current worktree; omit to remove
+    /// the worktree containing the cwd, after confirmation)
     #[arg(add = ArgValueCompleter::new(complete::complete_worktrees))]
-    branch: String,
+    branch: Option<String>,
 
     /// Force removal even with uncommitted changes
     #[arg(short, long)]
@@ -25,22 +28,16 @@ pub struct RmArgs {
 
 pub fn run(args: RmArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
     // Get main repo path BEFORE any destructive operations
-    let main_path = git::repo_root()?;
-    let workspace_id = git::workspace_id()?;
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
-
-    // Resolve '.' to current branch
-    let branch = if args.branch == "." {
-        git::current_branch()?
-    } else {
-        args.branch
-    };
+    let ws = Workspace::open(config.clone())?;
 
-    let wt_path = wt_dir.join(&branch);
+    // No argument: the worktree we're in, confirmed below
+    let (branch, inferred) = match args.branch {
+        Some(b) if b == "." => (git::current_branch()?, false),
+        Some(b) => (b, false),
+        None => (current_worktree_branch()?, true),
+    };
 
-    if !wt_path.exists() {
-        return Err(Error::Git(git::Error::WorktreeNotFound(branch.clone())));
-    }
+    let wt_path = locate_worktree(ws.wt_dir(), &branch)?;
 
     // Check if we're inside the worktree being removed
     let inside_target = git::is_cwd_inside(&wt_path);
@@ -56,24 +53,39 @@ pub fn run(args: RmArgs, config: &Config, path_file: Option<&Path>) -> Result<()
         ));
     }
 
-    // Remove worktree
-    git::remove_worktree(&wt_path, args.force)?;
-
-    // Switch to main repo before deleting branch (avoid "not in repo" error)
-    std::env::set_current_dir(&main_path).ok();
-
-    // Delete branch — best-effort, failure doesn't block worktree cleanup
-    let _ = git::delete_branch(&branch, args.force);
+    if inferred {
+        let confirmed = prompt::confirm(&format!(
+            "Remove the current worktree '{branch}' and its branch?"
+        ))
+        .map_err(|e| Error::Other(e.to_string()))?;
+        if !confirmed {
+            progress!("Aborted; worktree '{branch}' kept.");
+            return Ok(());
+        }
+    }
 
-    // Remove metadata
-    crate::meta::remove_meta(&wt_dir, &branch);
+    // Worktree, then branch (best-effort) and metadata
+    ws.remove(&branch, args.force)?;
 
-    eprintln!("Removed worktree: {branch}");
+    progress!("Removed worktree: {branch}");
 
     // If we were inside the removed worktree, write main repo path for shell to cd
     if path_file.is_some() && inside_target {
-        write_path_file(path_file, &main_path)?;
+        write_path_file(path_file, ws.repo_root())?;
     }
 
     Ok(())
 }
+
+/// Branch of the linked worktree containing the cwd.
+fn current_worktree_branch() -> Result<String> {
+    let wt = git::worktree_path_for_cwd()?.ok_or_else(|| {
+        Error::Other("Not inside a worktree. Pass the branch to remove: wt rm <branch>".into())
+    })?;
+    wt.branch.ok_or_else(|| {
+        Error::Other(format!(
+            "Worktree at {} has no branch checked out (detached HEAD)",
+            wt.path.display()
+        ))
+    })
+}
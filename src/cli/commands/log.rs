@@ -0,0 +1,86 @@
+// ===========================================================================
+// wt log - Show commits a worktree adds over its merge target
+// ===========================================================================
+
+use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::cli::{Error, Result};
+use crate::complete;
+use crate::config::Config;
+use crate::git;
+use crate::meta;
+use crate::process;
+
+#[derive(Args)]
+pub struct LogArgs {
+    /// Branch to show (default: current; use '.' for current)
+    #[arg(add = ArgValueCompleter::new(complete::complete_worktrees))]
+    branch: Option<String>,
+
+    /// Draw the commit graph with ref names (git log --graph --decorate)
+    #[arg(long)]
+    graph: bool,
+}
+
+pub fn run(args: LogArgs, config: &Config) -> Result<()> {
+    let main_repo = git::repo_root()?;
+    let wt_dir = config.wt_dir()?;
+
+    let branch = match args.branch {
+        Some(b) if b != "." => b,
+        _ => git::current_branch()?,
+    };
+    if !git::branch_exists(&branch)? {
+        return Err(Error::Other(format!("Branch '{branch}' does not exist")));
+    }
+
+    // Same target `wt merge` would use, so this is the list its squash
+    // message summarizes.
+    let target = meta::resolve_effective_target(
+        &wt_dir,
+        &branch,
+        None,
+        |b| git::branch_exists(b).unwrap_or(false),
+        &config.resolve_trunk_for(&branch),
+    );
+    if branch == target {
+        return Err(Error::Other(format!(
+            "{branch} is its own merge target; nothing to compare"
+        )));
+    }
+
+    if !args.graph {
+        let log = git::log_oneline(&target, &branch)?;
+        if log.trim().is_empty() {
+            progress!("No commits on {branch} beyond {target}");
+        } else {
+            print!("{log}");
+        }
+        return Ok(());
+    }
+
+    let env = process::HookEnv {
+        main_repo: &main_repo,
+        worktree: &main_repo,
+        branch: &branch,
+        base_branch: &target,
+    };
+    let command = graph_command();
+    let status = process::run_interactive(command, &main_repo, &env)
+        .map_err(|e| Error::Other(e.to_string()))?;
+    if !status.success() {
+        return Err(Error::Other(format!("'{command}' exited with {status}")));
+    }
+    Ok(())
+}
+
+/// Refs come in through the WT_* env vars (see `wt diff`), so branch names
+/// never need shell quoting.
+fn graph_command() -> &'static str {
+    if cfg!(windows) {
+        "git log --oneline --graph --decorate %WT_BASE_BRANCH%..%WT_BRANCH%"
+    } else {
+        r#"git log --oneline --graph --decorate "$WT_BASE_BRANCH..$WT_BRANCH""#
+    }
+}
@@ -2,45 +2,357 @@
 // wt ls - List worktrees with git status info
 // ===========================================================================
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 use crate::cli::Result;
-use crate::config::Config;
-use crate::git;
+use crate::config::{self, Config};
+use crate::git::{self, WorkSummary};
 use crate::meta;
+use crate::util;
+use crate::workspace::Workspace;
 
 #[derive(Args)]
 pub struct LsArgs {
-    /// Show full path for each worktree
+    /// Show full path and upstream branch for each worktree
     #[arg(short, long)]
     pub long: bool,
+
+    /// Show an UPSTREAM column: each branch's tracking branch, `-` if none
+    #[arg(long)]
+    pub remote: bool,
+
+    /// Redraw every few seconds until interrupted (Ctrl-C)
+    #[arg(short, long)]
+    pub watch: bool,
+
+    /// Sort rows by this key (default: age, newest first)
+    #[arg(long, value_enum, default_value_t = SortKey::Age)]
+    pub sort: SortKey,
+
+    /// Reverse the sort order
+    #[arg(short, long)]
+    pub reverse: bool,
+
+    /// Only worktrees with uncommitted changes
+    #[arg(long, conflicts_with = "clean")]
+    pub dirty: bool,
+
+    /// Only worktrees with commits ahead of their merge target
+    #[arg(long, conflicts_with = "clean")]
+    pub ahead: bool,
+
+    /// Only worktrees `wt clean` would remove (no diff from their target)
+    #[arg(long)]
+    pub clean: bool,
+
+    /// Only worktrees created and last entered (`wt cd`) longer ago than
+    /// this (30m, 6h, 7d, 2w) with no commits ahead
+    #[arg(long, value_name = "DURATION", value_parser = util::parse_duration)]
+    pub stale: Option<Duration>,
+
+    /// Plain output even on a terminal (NO_COLOR is honored too)
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Stable tab-separated output for scripts: branch, uncommitted,
+    /// commits, insertions, deletions, path (no header)
+    #[arg(long, conflicts_with = "watch")]
+    pub porcelain: bool,
+
+    /// Also list worktrees `wt` doesn't manage (the main repo and ones
+    /// added with plain `git worktree add`), with a KIND column
+    #[arg(short, long)]
+    pub all: bool,
 }
 
+impl LsArgs {
+    /// Filters are ANDed; no filter flags keeps every row.
+    fn keeps(&self, row: &Row, now: DateTime<Utc>) -> bool {
+        (!self.dirty || row.uncommitted > 0)
+            && (!self.ahead || row.commits > 0)
+            && (!self.clean || row.clean_candidate)
+            && self.stale.is_none_or(|age| is_stale_row(row, age, now))
+    }
+}
+
+/// `--stale`: no meta (unknown age) never counts as stale.
+fn is_stale_row(row: &Row, age: Duration, now: DateTime<Utc>) -> bool {
+    let old = |at: DateTime<Utc>| now - at >= age;
+    row.created_at.is_some_and(old) && row.last_accessed.is_none_or(old) && row.commits == 0
+}
+
+/// `--sort` keys. Count-like keys put the largest first, so the busiest
+/// worktree tops the list when triaging agent runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    /// Branch name, A-Z
+    Name,
+    /// Creation time, newest first (no metadata sorts last)
+    Age,
+    /// Commits ahead of the merge target, most first
+    Commits,
+    /// Insertions + deletions (committed and uncommitted), most first
+    Diff,
+}
+
+/// Where a worktree lives, relative to `wt`'s workspace dir (`--all`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Under the workspace dir: created by `wt new`
+    Managed,
+    /// The main working tree
+    Main,
+    /// Anywhere else (`git worktree add` by hand)
+    External,
+}
+
+impl Kind {
+    fn label(self) -> &'static str {
+        match self {
+            Kind::Managed => "managed",
+            Kind::Main => "main",
+            Kind::External => "external",
+        }
+    }
+}
+
+/// `--watch` 刷新间隔
+const WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Every Nth watch tick drops the cache. The fingerprint misses some
+/// changes (trunk moving, unstaged edits git hasn't stat-refreshed into
+/// the index yet); this bounds how long they stay stale.
+const FULL_REFRESH_TICKS: u32 = 15;
+
 pub fn run(args: LsArgs, config: &Config) -> Result<()> {
-    let workspace_id = git::workspace_id()?;
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
+    if !args.watch {
+        return render(&args, config, &mut StatsCache::default());
+    }
 
-    if !wt_dir.exists() {
-        eprintln!("No worktrees for this project.");
-        return Ok(());
+    let mut cache = StatsCache::default();
+    for tick in 0u32.. {
+        if tick % FULL_REFRESH_TICKS == 0 {
+            cache.clear();
+        }
+        // Clear screen + home cursor
+        print!("\x1b[2J\x1b[H");
+        render(&args, config, &mut cache)?;
+        eprintln!("\nEvery {}s, Ctrl-C to exit", WATCH_INTERVAL.as_secs());
+        std::thread::sleep(WATCH_INTERVAL);
     }
+    Ok(())
+}
+
+// ===========================================================================
+// Per-worktree stats cache (--watch)
+// ===========================================================================
 
-    let worktrees = git::list_worktrees()?;
+/// What has to change before a worktree's git stats are recomputed
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Fingerprint {
+    head: Option<String>,
+    index_mtime: Option<SystemTime>,
+}
 
-    let managed: Vec<_> = worktrees
-        .iter()
-        .filter(|wt| wt.path.starts_with(&wt_dir))
-        .collect();
+/// Recompute unless both HEAD and index mtime are known and unchanged.
+/// An unreadable HEAD / index is never trusted as "unchanged".
+fn is_stale(cached: Option<&Fingerprint>, current: &Fingerprint) -> bool {
+    if current.head.is_none() || current.index_mtime.is_none() {
+        return true;
+    }
+    cached != Some(current)
+}
+
+struct CachedStats {
+    fingerprint: Fingerprint,
+    target: String,
+    /// `None`: `git::work_summary` failed (e.g. the target is gone)
+    stats: Option<WorkSummary>,
+}
+
+#[derive(Default)]
+struct StatsCache {
+    entries: HashMap<PathBuf, CachedStats>,
+    // Resolved once per worktree: `git rev-parse --git-path index` is stable.
+    index_paths: HashMap<PathBuf, PathBuf>,
+}
+
+impl StatsCache {
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn fingerprint(&mut self, path: &Path) -> Fingerprint {
+        let index = match self.index_paths.get(path) {
+            Some(p) => Some(p.clone()),
+            None => git::index_path_in(path).ok().inspect(|p| {
+                self.index_paths.insert(path.to_path_buf(), p.clone());
+            }),
+        };
+        Fingerprint {
+            head: git::current_commit_in(path).ok(),
+            index_mtime: index.and_then(|p| p.metadata().and_then(|m| m.modified()).ok()),
+        }
+    }
+
+    /// Cached stats where still fresh; the stale ones are recomputed
+    /// together (`parallel_map`). Results in `jobs` order.
+    fn stats_all(&mut self, jobs: &[StatsJob]) -> Vec<Option<WorkSummary>> {
+        let fingerprints: Vec<Fingerprint> =
+            jobs.iter().map(|job| self.fingerprint(job.path)).collect();
+        let hits: Vec<Option<Option<WorkSummary>>> = jobs
+            .iter()
+            .zip(&fingerprints)
+            .map(|(job, fingerprint)| {
+                self.entries
+                    .get(job.path)
+                    .filter(|hit| {
+                        hit.target == job.target && !is_stale(Some(&hit.fingerprint), fingerprint)
+                    })
+                    .map(|hit| hit.stats)
+            })
+            .collect();
+
+        let misses: Vec<&StatsJob> = jobs
+            .iter()
+            .zip(&hits)
+            .filter(|(_, hit)| hit.is_none())
+            .map(|(job, _)| job)
+            .collect();
+        let mut fresh = parallel_map(&misses, |job| job.compute()).into_iter();
+
+        let mut out = Vec::with_capacity(jobs.len());
+        for ((job, fingerprint), hit) in jobs.iter().zip(fingerprints).zip(hits) {
+            let stats = match hit {
+                Some(stats) => stats,
+                None => {
+                    let stats = fresh.next().expect("one result per miss");
+                    self.entries.insert(
+                        job.path.to_path_buf(),
+                        CachedStats {
+                            fingerprint,
+                            target: job.target.to_string(),
+                            stats,
+                        },
+                    );
+                    stats
+                }
+            };
+            out.push(stats);
+        }
+        out
+    }
+}
 
-    if managed.is_empty() {
-        eprintln!("No worktrees for this project.");
+/// One row's inputs to `git::work_summary`
+struct StatsJob<'a> {
+    path: &'a Path,
+    target: &'a str,
+}
+
+impl StatsJob<'_> {
+    fn compute(&self) -> Option<WorkSummary> {
+        git::work_summary(self.target, self.path).ok()
+    }
+}
+
+/// Upper bound on `parallel_map` threads: each one is mostly waiting on git.
+const MAX_WORKERS: usize = 8;
+
+/// `f` over `items` on a few scoped threads; results keep `items` order.
+fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_WORKERS)
+        .min(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else { break };
+                        done.push((i, f(item)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        for handle in handles {
+            for (i, result) in handle.join().expect("ls worker panicked") {
+                results[i] = Some(result);
+            }
+        }
+    });
+    results
+        .into_iter()
+        .map(|r| r.expect("every item mapped"))
+        .collect()
+}
+
+// ===========================================================================
+// Table
+// ===========================================================================
+
+fn render(args: &LsArgs, config: &Config, cache: &mut StatsCache) -> Result<()> {
+    let ws = Workspace::open(config.clone())?;
+    let wt_dir = ws.wt_dir();
+
+    if !args.all && !wt_dir.exists() {
+        progress!("No worktrees for this project.");
+        return Ok(());
+    }
+
+    // (worktree, kind); without --all only the managed ones
+    let mut worktrees: Vec<(git::WorktreeInfo, Kind)> = if args.all {
+        git::list_worktrees()?
+            .into_iter()
+            .enumerate()
+            .filter(|(_, wt)| !wt.is_bare)
+            .map(|(i, wt)| {
+                let kind = if wt.path.starts_with(wt_dir) {
+                    Kind::Managed
+                } else if i == 0 {
+                    Kind::Main
+                } else {
+                    Kind::External
+                };
+                (wt, kind)
+            })
+            .collect()
+    } else {
+        ws.list()?
+            .into_iter()
+            .map(|wt| (wt, Kind::Managed))
+            .collect()
+    };
+
+    if worktrees.is_empty() {
+        progress!("No worktrees for this project.");
         return Ok(());
     }
 
+    let show_upstream = args.long || args.remote;
+    if show_upstream {
+        git::fill_upstreams(worktrees.iter_mut().map(|(wt, _)| wt))?;
+    }
+
     let trunk = config.resolve_trunk();
     // Fetch all local branches once instead of N subprocess calls.
     let known_branches: HashSet<String> = git::local_branches()
@@ -51,36 +363,35 @@ pub fn run(args: LsArgs, config: &Config) -> Result<()> {
     let current = git::current_branch().ok();
     let home = dirs::home_dir();
 
+    // Rows first (meta files only), then the git stats for all of them at
+    // once: those are several subprocesses per row.
     let mut rows: Vec<Row> = Vec::new();
-    for wt in &managed {
+    let mut targets: Vec<String> = Vec::new();
+    for (wt, kind) in &worktrees {
+        let kind = *kind;
         let branch = wt.branch.as_deref().unwrap_or("(detached)");
         let is_current = current.as_deref() == Some(branch);
 
-        let meta_path = meta::meta_path_with_fallback(&wt_dir, branch);
-        let loaded_meta = meta::WorktreeMeta::load(&meta_path).ok();
+        // Only `wt new` writes meta, next to the managed worktrees.
+        let loaded_meta = if kind == Kind::Managed {
+            meta::WorktreeMeta::load(&meta::meta_path_with_fallback(wt_dir, branch)).ok()
+        } else {
+            None
+        };
 
         let base_branch = loaded_meta.as_ref().map(|m| m.base_branch.clone());
         let created_at = loaded_meta.as_ref().map(|m| m.created_at);
+        let last_accessed = loaded_meta.as_ref().and_then(|m| m.last_accessed);
+        let description = loaded_meta.as_ref().and_then(|m| m.description.clone());
 
+        // Same target as `wt clean` / `wt merge`, trunk_rules included.
         let effective_target = meta::resolve_target_branch(
             None,
             base_branch.as_deref(),
             |b| known_branches.contains(b),
-            &trunk,
+            config::match_trunk_rule(&config.trunk_rules, branch).unwrap_or(&trunk),
         );
 
-        let uncommitted = git::uncommitted_count_in(&wt.path).unwrap_or(0);
-        let commits = git::commit_count(&effective_target, branch).unwrap_or(0);
-
-        let c = git::diff_shortstat(&effective_target, branch).unwrap_or(git::DiffStat {
-            insertions: 0,
-            deletions: 0,
-        });
-        let u = git::diff_shortstat_in(&wt.path).unwrap_or(git::DiffStat {
-            insertions: 0,
-            deletions: 0,
-        });
-
         let path = if args.long {
             Some(shorten_path(&wt.path, &home))
         } else {
@@ -89,27 +400,85 @@ pub fn run(args: LsArgs, config: &Config) -> Result<()> {
 
         rows.push(Row {
             branch: branch.to_string(),
+            wt_path: wt.path.clone(),
             base_branch,
+            upstream: wt.upstream.clone(),
             is_current,
-            uncommitted,
-            commits,
-            insertions: c.insertions + u.insertions,
-            deletions: c.deletions + u.deletions,
+            uncommitted: 0,
+            commits: 0,
+            insertions: 0,
+            deletions: 0,
             path,
             created_at,
+            last_accessed,
+            description,
+            // Only --clean may remove it, and only managed branches qualify.
+            clean_candidate: args.clean
+                && kind == Kind::Managed
+                && branch != trunk
+                && wt.branch.is_some(),
+            kind: args.all.then_some(kind),
         });
+        targets.push(effective_target);
+    }
+
+    let jobs: Vec<StatsJob> = rows
+        .iter()
+        .zip(&targets)
+        .map(|(row, target)| StatsJob {
+            path: &row.wt_path,
+            target,
+        })
+        .collect();
+    // Plain `wt ls` runs once: skip the fingerprint subprocesses.
+    let stats = if args.watch {
+        cache.stats_all(&jobs)
+    } else {
+        parallel_map(&jobs, StatsJob::compute)
+    };
+    drop(jobs);
+
+    for (row, stats) in rows.iter_mut().zip(stats) {
+        // Same rule as `wt clean`: a failed summary keeps the worktree.
+        row.clean_candidate &= stats.is_some_and(|s| !s.has_work());
+        let stats = stats.unwrap_or_default();
+        row.uncommitted = stats.uncommitted;
+        row.commits = stats.commits_ahead;
+        row.insertions = stats.insertions;
+        row.deletions = stats.deletions;
+    }
+
+    let now = Utc::now();
+    rows.retain(|r| args.keeps(r, now));
+    if args.porcelain {
+        sort_rows(&mut rows, args.sort, args.reverse);
+        for row in &rows {
+            println!("{}", porcelain_line(row));
+        }
+        return Ok(());
+    }
+    if rows.is_empty() {
+        progress!("No worktrees match the filter.");
+        return Ok(());
     }
 
-    // Sort newest-first; rows without meta sink to the bottom (None < Some).
-    rows.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    sort_rows(&mut rows, args.sort, args.reverse);
 
-    print_table(&rows);
+    let color = use_color(
+        args.no_color,
+        std::env::var_os("NO_COLOR").as_deref(),
+        std::io::stdout().is_terminal(),
+    );
+    print_table(&rows, now, show_upstream, color);
     Ok(())
 }
 
 struct Row {
     branch: String,
+    wt_path: PathBuf,
     base_branch: Option<String>,
+    /// Set only under `--long` / `--remote`
+    upstream: Option<String>,
     is_current: bool,
     uncommitted: usize,
     commits: usize,
@@ -117,9 +486,100 @@ struct Row {
     deletions: usize,
     path: Option<String>,
     created_at: Option<DateTime<Utc>>,
+    last_accessed: Option<DateTime<Utc>>,
+    description: Option<String>,
+    /// Set only under `--clean`: what `wt clean` would remove
+    clean_candidate: bool,
+    /// Set only under `--all`
+    kind: Option<Kind>,
 }
 
-fn print_table(rows: &[Row]) {
+/// `--porcelain` line. The field set and order are a stable interface:
+/// only ever append new fields at the end. `--all` appends the kind.
+fn porcelain_line(row: &Row) -> String {
+    let mut line = format!(
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        row.branch,
+        row.uncommitted,
+        row.commits,
+        row.insertions,
+        row.deletions,
+        escape_path(&row.wt_path.display().to_string())
+    );
+    if let Some(kind) = row.kind {
+        line.push('\t');
+        line.push_str(kind.label());
+    }
+    line
+}
+
+/// Ties fall back to branch name so the order is stable across redraws.
+fn sort_rows(rows: &mut [Row], key: SortKey, reverse: bool) {
+    use std::cmp::Reverse;
+    match key {
+        SortKey::Name => rows.sort_by(|a, b| a.branch.cmp(&b.branch)),
+        // Rows without meta sink to the bottom (None < Some).
+        SortKey::Age => rows.sort_by(|a, b| {
+            Reverse(a.created_at)
+                .cmp(&Reverse(b.created_at))
+                .then_with(|| a.branch.cmp(&b.branch))
+        }),
+        SortKey::Commits => rows.sort_by(|a, b| {
+            b.commits
+                .cmp(&a.commits)
+                .then_with(|| a.branch.cmp(&b.branch))
+        }),
+        SortKey::Diff => rows.sort_by(|a, b| {
+            (b.insertions + b.deletions)
+                .cmp(&(a.insertions + a.deletions))
+                .then_with(|| a.branch.cmp(&b.branch))
+        }),
+    }
+    if reverse {
+        rows.reverse();
+    }
+}
+
+/// Longest description shown in the table; `wt status` shows it in full.
+const DESC_MAX: usize = 30;
+
+/// Color only on a terminal, unless `--no-color` or a non-empty NO_COLOR
+/// (https://no-color.org) says otherwise.
+fn use_color(no_color_flag: bool, no_color_env: Option<&std::ffi::OsStr>, is_tty: bool) -> bool {
+    is_tty && !no_color_flag && no_color_env.is_none_or(|v| v.is_empty())
+}
+
+const BOLD_GREEN: &str = "1;32";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const RED: &str = "31";
+
+/// Wrap already-padded text, so escape codes never count toward alignment.
+fn paint(text: &str, code: &str, on: bool) -> String {
+    if on {
+        format!("\x1b[{code}m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+/// DIFF column, right-aligned to `width`: insertions green, deletions red.
+fn diff_cell(insertions: usize, deletions: usize, width: usize, color: bool) -> String {
+    if insertions == 0 && deletions == 0 {
+        return format!("{:>width$}", "-");
+    }
+    let ins = format!("+{insertions}");
+    let del = format!("-{deletions}");
+    let pad = width.saturating_sub(ins.len() + 1 + del.len());
+    format!(
+        "{}{} {}",
+        " ".repeat(pad),
+        paint(&ins, GREEN, color),
+        paint(&del, RED, color)
+    )
+}
+
+fn print_table(rows: &[Row], now: DateTime<Utc>, show_upstream: bool, color: bool) {
     let bw = rows
         .iter()
         .map(|r| r.branch.len())
@@ -128,6 +588,8 @@ fn print_table(rows: &[Row]) {
         .max(6);
     let show_path = rows.iter().any(|r| r.path.is_some());
     let show_base = rows.iter().any(|r| r.base_branch.is_some());
+    let show_desc = rows.iter().any(|r| r.description.is_some());
+    let show_kind = rows.iter().any(|r| r.kind.is_some());
 
     let sw = if show_base {
         rows.iter()
@@ -138,15 +600,43 @@ fn print_table(rows: &[Row]) {
     } else {
         0
     };
+    let uw = if show_upstream {
+        rows.iter()
+            .filter_map(|r| r.upstream.as_ref().map(|s| s.len()))
+            .max()
+            .unwrap_or(8)
+            .max(8)
+    } else {
+        0
+    };
 
     let mut header = format!("  {:<bw$}", "BRANCH", bw = bw);
+    if show_kind {
+        header.push_str(&format!("   {:<8}", "KIND"));
+    }
     if show_base {
         header.push_str(&format!("   {:<sw$}", "BASE", sw = sw));
     }
+    if show_upstream {
+        header.push_str(&format!("   {:<uw$}", "UPSTREAM", uw = uw));
+    }
     header.push_str(&format!(
-        "   {:>8}   {:>7}   {:>10}",
-        "UNCOMMIT", "COMMITS", "DIFF"
+        "   {:>8}   {:>7}   {:>10}   {:>5}   {:>8}",
+        "UNCOMMIT", "COMMITS", "DIFF", "AGE", "ACCESSED"
     ));
+    let dw = if show_desc {
+        rows.iter()
+            .filter_map(|r| r.description.as_deref())
+            .map(|d| truncate_desc(d).chars().count())
+            .max()
+            .unwrap_or(4)
+            .max(4)
+    } else {
+        0
+    };
+    if show_desc {
+        header.push_str(&format!("   {:<dw$}", "DESC", dw = dw));
+    }
     if show_path {
         header.push_str("   PATH");
     }
@@ -160,28 +650,54 @@ fn print_table(rows: &[Row]) {
         + 7
         + 3
         + 10
+        + 3
+        + 5
+        + 3
+        + 8
+        + if show_kind { 3 + 8 } else { 0 }
         + if show_base { 3 + sw } else { 0 }
+        + if show_upstream { 3 + uw } else { 0 }
+        + if show_desc { 3 + dw } else { 0 }
         + if show_path { 40 } else { 0 };
     println!("{}", "-".repeat(sep_len));
 
     for row in rows {
         let marker = if row.is_current { "* " } else { "  " };
+        let current = color && row.is_current;
 
-        let diff = if row.insertions == 0 && row.deletions == 0 {
-            "-".to_string()
-        } else {
-            format!("+{} -{}", row.insertions, row.deletions)
-        };
-
-        let mut line = format!("{}{:<bw$}", marker, row.branch, bw = bw);
+        let mut line = paint(
+            &format!("{}{:<bw$}", marker, row.branch, bw = bw),
+            BOLD_GREEN,
+            current,
+        );
+        if show_kind {
+            let kind = row.kind.map_or("-", Kind::label);
+            line.push_str(&format!("   {kind:<8}"));
+        }
         if show_base {
             let src = row.base_branch.as_deref().unwrap_or("-");
             line.push_str(&format!("   {:<sw$}", src, sw = sw));
         }
+        if show_upstream {
+            let upstream = row.upstream.as_deref().unwrap_or("-");
+            line.push_str(&format!("   {:<uw$}", upstream, uw = uw));
+        }
+        let age = format_relative(row.created_at, now);
+        let accessed = format_relative(row.last_accessed, now);
+        let uncommitted = paint(
+            &format!("{:>8}", row.uncommitted),
+            YELLOW,
+            color && row.uncommitted > 0,
+        );
+        let diff = diff_cell(row.insertions, row.deletions, 10, color);
         line.push_str(&format!(
-            "   {:>8}   {:>7}   {:>10}",
-            row.uncommitted, row.commits, diff
+            "   {uncommitted}   {:>7}   {diff}   {:>5}   {:>8}",
+            row.commits, age, accessed
         ));
+        if show_desc {
+            let desc = row.description.as_deref().map_or("-".into(), truncate_desc);
+            line.push_str(&format!("   {:<dw$}", desc, dw = dw));
+        }
 
         if let Some(ref path) = row.path {
             println!("{line}   {path}");
@@ -191,11 +707,298 @@ fn print_table(rows: &[Row]) {
     }
 }
 
-fn shorten_path(path: &std::path::Path, home: &Option<std::path::PathBuf>) -> String {
-    match home {
+/// Single-line, at most DESC_MAX chars (ellipsis when cut).
+fn truncate_desc(desc: &str) -> String {
+    let line = desc.lines().next().unwrap_or("").trim();
+    if line.chars().count() <= DESC_MAX && !desc.trim().contains('\n') {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(DESC_MAX - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// Compact relative time ("now", "5m", "3h", "2d"); `-` when unknown.
+fn format_relative(at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    let Some(at) = at else {
+        return "-".to_string();
+    };
+    // Clock skew can put `at` slightly in the future; treat as "now".
+    let elapsed = (now - at).max(Duration::zero());
+    if elapsed < Duration::minutes(1) {
+        "now".to_string()
+    } else if elapsed < Duration::hours(1) {
+        format!("{}m", elapsed.num_minutes())
+    } else if elapsed < Duration::days(1) {
+        format!("{}h", elapsed.num_hours())
+    } else {
+        format!("{}d", elapsed.num_days())
+    }
+}
+
+fn shorten_path(path: &Path, home: &Option<PathBuf>) -> String {
+    let shown = match home {
         Some(h) if path.starts_with(h) => {
             format!("~/{}", path.strip_prefix(h).unwrap().display())
         }
         _ => path.display().to_string(),
+    };
+    escape_path(&shown)
+}
+
+/// Paths are the one free-form field in a row: a newline would split it
+/// and a tab shift the `--porcelain` fields. Control characters and `%`
+/// itself are percent-encoded (`%0A`), so scripts can decode them back.
+fn escape_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c.is_control() || c == '%' {
+            let mut buf = [0; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                out.push_str(&format!("%{b:02X}"));
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_desc_short_is_unchanged() {
+        assert_eq!(truncate_desc("fix login race"), "fix login race");
+    }
+
+    #[test]
+    fn truncate_desc_long_is_cut() {
+        let long = "a".repeat(DESC_MAX + 10);
+        let cut = truncate_desc(&long);
+        assert_eq!(cut.chars().count(), DESC_MAX);
+        assert!(cut.ends_with('…'));
+    }
+
+    #[test]
+    fn truncate_desc_keeps_first_line() {
+        assert_eq!(truncate_desc("first\nsecond"), "first…");
+    }
+
+    #[test]
+    fn format_relative_missing() {
+        assert_eq!(format_relative(None, Utc::now()), "-");
+    }
+
+    #[test]
+    fn format_relative_units() {
+        let now = Utc::now();
+        let ago = |d: Duration| format_relative(Some(now - d), now);
+        assert_eq!(ago(Duration::seconds(10)), "now");
+        assert_eq!(ago(Duration::minutes(5)), "5m");
+        assert_eq!(ago(Duration::hours(3)), "3h");
+        assert_eq!(ago(Duration::days(2)), "2d");
+    }
+
+    fn fp(head: Option<&str>, mtime_secs: Option<u64>) -> Fingerprint {
+        Fingerprint {
+            head: head.map(str::to_string),
+            index_mtime: mtime_secs
+                .map(|s| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(s)),
+        }
+    }
+
+    #[test]
+    fn is_stale_unchanged_reuses_cache() {
+        let cached = fp(Some("abc"), Some(100));
+        assert!(!is_stale(Some(&cached), &fp(Some("abc"), Some(100))));
+    }
+
+    #[test]
+    fn is_stale_head_changed() {
+        let cached = fp(Some("abc"), Some(100));
+        assert!(is_stale(Some(&cached), &fp(Some("def"), Some(100))));
+    }
+
+    #[test]
+    fn is_stale_index_mtime_changed() {
+        let cached = fp(Some("abc"), Some(100));
+        assert!(is_stale(Some(&cached), &fp(Some("abc"), Some(101))));
+    }
+
+    #[test]
+    fn is_stale_without_cache_or_unknown_state() {
+        assert!(is_stale(None, &fp(Some("abc"), Some(100))));
+        // Unreadable HEAD / index never counts as unchanged
+        let cached = fp(None, Some(100));
+        assert!(is_stale(Some(&cached), &fp(None, Some(100))));
+        let cached = fp(Some("abc"), None);
+        assert!(is_stale(Some(&cached), &fp(Some("abc"), None)));
+    }
+
+    fn row(branch: &str, age_days: Option<i64>, commits: usize, diff: usize) -> Row {
+        Row {
+            branch: branch.to_string(),
+            wt_path: PathBuf::from("/wt").join(branch),
+            base_branch: None,
+            upstream: None,
+            is_current: false,
+            uncommitted: 0,
+            commits,
+            insertions: diff,
+            deletions: 0,
+            path: None,
+            created_at: age_days.map(|d| Utc::now() - Duration::days(d)),
+            last_accessed: None,
+            description: None,
+            clean_candidate: false,
+            kind: None,
+        }
+    }
+
+    fn sorted(key: SortKey, reverse: bool) -> Vec<String> {
+        let mut rows = vec![
+            row("bravo", Some(2), 5, 10),
+            row("alpha", None, 1, 300),
+            row("charlie", Some(1), 5, 0),
+        ];
+        sort_rows(&mut rows, key, reverse);
+        rows.into_iter().map(|r| r.branch).collect()
+    }
+
+    #[test]
+    fn sort_rows_by_each_key() {
+        assert_eq!(sorted(SortKey::Name, false), ["alpha", "bravo", "charlie"]);
+        assert_eq!(sorted(SortKey::Age, false), ["charlie", "bravo", "alpha"]);
+        assert_eq!(
+            sorted(SortKey::Commits, false),
+            ["bravo", "charlie", "alpha"]
+        );
+        assert_eq!(sorted(SortKey::Diff, false), ["alpha", "bravo", "charlie"]);
+    }
+
+    fn filter_args(dirty: bool, ahead: bool, clean: bool) -> LsArgs {
+        LsArgs {
+            long: false,
+            remote: false,
+            watch: false,
+            sort: SortKey::Age,
+            reverse: false,
+            dirty,
+            ahead,
+            clean,
+            no_color: false,
+            porcelain: false,
+            all: false,
+            stale: None,
+        }
+    }
+
+    #[test]
+    fn filters_are_anded() {
+        let mut busy = row("busy", None, 2, 0);
+        busy.uncommitted = 1;
+        let ahead = row("ahead", None, 1, 0);
+        let mut idle = row("idle", None, 0, 0);
+        idle.clean_candidate = true;
+
+        let kept = |args: LsArgs| -> Vec<&str> {
+            [&busy, &ahead, &idle]
+                .into_iter()
+                .filter(|r| args.keeps(r, Utc::now()))
+                .map(|r| r.branch.as_str())
+                .collect()
+        };
+        assert_eq!(
+            kept(filter_args(false, false, false)),
+            ["busy", "ahead", "idle"]
+        );
+        assert_eq!(kept(filter_args(true, false, false)), ["busy"]);
+        assert_eq!(kept(filter_args(false, true, false)), ["busy", "ahead"]);
+        assert_eq!(kept(filter_args(true, true, false)), ["busy"]);
+        assert_eq!(kept(filter_args(false, false, true)), ["idle"]);
+    }
+
+    #[test]
+    fn stale_needs_old_creation_old_access_and_no_commits() {
+        let now = Utc::now();
+        let stale = |created: Option<i64>, accessed: Option<i64>, commits: usize| {
+            let mut r = row("x", None, commits, 0);
+            r.created_at = created.map(|d| now - Duration::days(d));
+            r.last_accessed = accessed.map(|d| now - Duration::days(d));
+            is_stale_row(&r, Duration::days(7), now)
+        };
+        assert!(stale(Some(10), None, 0));
+        assert!(stale(Some(10), Some(8), 0));
+        assert!(!stale(Some(10), Some(1), 0), "recently entered");
+        assert!(!stale(Some(3), None, 0), "too new");
+        assert!(!stale(Some(10), None, 2), "has commits ahead");
+        assert!(!stale(None, None, 0), "unknown age");
+    }
+
+    #[test]
+    fn sort_rows_reverse() {
+        assert_eq!(sorted(SortKey::Name, true), ["charlie", "bravo", "alpha"]);
+        assert_eq!(sorted(SortKey::Diff, true), ["charlie", "bravo", "alpha"]);
+    }
+
+    #[test]
+    fn porcelain_line_fields() {
+        let mut r = row("feat/x", Some(1), 2, 7);
+        r.uncommitted = 1;
+        r.deletions = 3;
+        assert_eq!(porcelain_line(&r), "feat/x\t1\t2\t7\t3\t/wt/feat/x");
+        r.kind = Some(Kind::External);
+        assert_eq!(
+            porcelain_line(&r),
+            "feat/x\t1\t2\t7\t3\t/wt/feat/x\texternal"
+        );
+    }
+
+    #[test]
+    fn porcelain_line_escapes_path_control_chars() {
+        let mut r = row("x", None, 0, 0);
+        r.wt_path = PathBuf::from("/wt/a\tb\nc%d");
+        assert_eq!(porcelain_line(&r), "x\t0\t0\t0\t0\t/wt/a%09b%0Ac%25d");
+        assert_eq!(escape_path("/wt/plain-ü"), "/wt/plain-ü");
+    }
+
+    #[test]
+    fn use_color_needs_tty_and_no_opt_out() {
+        use std::ffi::OsStr;
+        assert!(use_color(false, None, true));
+        assert!(!use_color(false, None, false));
+        assert!(!use_color(true, None, true));
+        assert!(!use_color(false, Some(OsStr::new("1")), true));
+        // Empty NO_COLOR doesn't count as set
+        assert!(use_color(false, Some(OsStr::new("")), true));
+    }
+
+    #[test]
+    fn diff_cell_alignment_ignores_escape_codes() {
+        assert_eq!(diff_cell(0, 0, 10, true), "         -");
+        assert_eq!(diff_cell(3, 12, 10, false), "    +3 -12");
+        assert_eq!(
+            diff_cell(3, 12, 10, true),
+            "    \x1b[32m+3\x1b[0m \x1b[31m-12\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn parallel_map_keeps_input_order() {
+        let items: Vec<u64> = (0..50).collect();
+        let out = parallel_map(&items, |&n| {
+            // Uneven work, so workers finish out of order
+            std::thread::sleep(std::time::Duration::from_micros((50 - n) * 20));
+            n * 2
+        });
+        assert_eq!(out, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+        assert!(parallel_map(&[] as &[u64], |&n| n).is_empty());
+    }
+
+    #[test]
+    fn format_relative_future_is_now() {
+        let now = Utc::now();
+        assert_eq!(format_relative(Some(now + Duration::hours(1)), now), "now");
     }
 }
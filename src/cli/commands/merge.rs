@@ -2,17 +2,21 @@
 // wt merge - Merge current worktree to trunk
 // ===========================================================================
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use clap::Args;
 use clap_complete::engine::ArgValueCompleter;
 
+use super::NewArgs;
 use crate::cli::{write_path_file, Error, Result};
 use crate::complete;
 use crate::config::{Config, MergeStrategy};
 use crate::git;
 use crate::meta;
 use crate::process;
+use crate::workspace::{
+    build_merge_message, check_fast_forward, CommitOverrides, MergeResult, Workspace,
+};
 
 #[derive(Args)]
 pub struct MergeArgs {
@@ -28,72 +32,169 @@ pub struct MergeArgs {
     #[arg(short = 'd', long)]
     delete: bool,
 
+    /// With -d, also delete the branch on the remote (general.remote,
+    /// default origin)
+    #[arg(long, requires = "delete")]
+    delete_remote: bool,
+
     /// Skip pre-merge hooks
     #[arg(short = 'H', long)]
     skip_hooks: bool,
+
+    /// After merging, create a new worktree from the target and switch to it
+    #[arg(
+        long,
+        value_name = "BRANCH",
+        num_args = 0..=1,
+        default_missing_value = ""
+    )]
+    then_new: Option<String>,
+
+    /// Show the merge plan (target, strategy, commits, message) without merging
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Stash uncommitted changes before merging and restore them afterward
+    #[arg(long)]
+    autostash: bool,
+
+    /// Author for the squash commit ("Name <email>")
+    #[arg(long, value_name = "AUTHOR")]
+    author: Option<String>,
+
+    /// Author and committer date for the squash commit (any format git accepts)
+    #[arg(long, value_name = "DATE")]
+    date: Option<String>,
+
+    /// Edit the generated squash commit message in git's editor first
+    #[arg(short = 'e', long)]
+    edit: bool,
+
+    /// Skip the repo's git hooks (pre-commit, commit-msg) on the merge commit.
+    /// agent-worktree's own pre_merge/post_merge hooks still run; see -H.
+    #[arg(long)]
+    no_verify: bool,
+
+    /// Recover from an interrupted merge: reset the main repo and check
+    /// out the branch it was on before `wt merge` started
+    #[arg(long, conflicts_with_all = ["dry_run", "delete", "then_new", "autostash", "edit"])]
+    abort: bool,
 }
 
-pub fn run(args: MergeArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
-    let main_repo = git::repo_root()?;
-    run_merge(args, config, path_file, &main_repo)
+/// What `wt merge` did (a dry run merges nothing)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeOutcome {
+    /// New commits landed on the target
+    pub merged: bool,
+    /// Stopped on conflicts: nothing merged, HEAD back where it was
+    pub conflicted: bool,
 }
 
-fn run_merge(
-    args: MergeArgs,
-    config: &Config,
-    path_file: Option<&Path>,
-    main_repo: &Path,
-) -> Result<()> {
+impl MergeOutcome {
+    const CONFLICTED: Self = Self {
+        merged: false,
+        conflicted: true,
+    };
+
+    /// CLI view: conflicts become `Error::MergeConflict` (exit 4).
+    pub fn into_result(self) -> Result<()> {
+        if self.conflicted {
+            return Err(Error::MergeConflict);
+        }
+        Ok(())
+    }
+}
+
+pub fn run(args: MergeArgs, config: &Config, path_file: Option<&Path>) -> Result<MergeOutcome> {
+    let ws = Workspace::open(config.clone())?;
+    if args.abort {
+        let prev = ws.abort_merge(&git::current_branch()?)?;
+        progress!("Merge aborted; main repo is back on {prev}.");
+        return Ok(MergeOutcome::default());
+    }
+    run_merge(args, &ws, path_file)
+}
+
+fn run_merge(args: MergeArgs, ws: &Workspace, path_file: Option<&Path>) -> Result<MergeOutcome> {
+    let config = ws.config();
+    let main_repo = ws.repo_root();
     let current = git::current_branch()?;
-    let workspace_id = git::workspace_id()?;
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
 
-    // --into target must exist AND not be checked out elsewhere.
-    // git refuses to checkout a branch that another worktree owns; without
-    // the second check, merge would fail mid-flight with a confusing
-    // low-level git error instead of a clear upfront message.
     if let Some(ref branch) = args.into {
         if !git::branch_exists(branch)? {
             return Err(Error::Other(format!("Branch '{branch}' does not exist")));
         }
-        let main_canon = main_repo
-            .canonicalize()
-            .unwrap_or_else(|_| main_repo.to_path_buf());
-        let conflict = git::list_worktrees()?.into_iter().find(|wt| {
-            wt.branch.as_deref() == Some(branch.as_str())
-                && wt.path.canonicalize().unwrap_or_else(|_| wt.path.clone()) != main_canon
-        });
-        if let Some(wt) = conflict {
-            return Err(Error::Other(format!(
-                "Branch '{branch}' is checked out in another worktree at {}.\n\
-                 Switch that worktree off the branch, or merge from there directly.",
-                wt.path.display()
-            )));
-        }
     }
 
-    let target = meta::resolve_effective_target(
-        &wt_dir,
-        &current,
-        args.into.as_deref(),
-        |b| git::branch_exists(b).unwrap_or(false),
-        &config.resolve_trunk(),
-    );
+    let target = ws.merge_target(&current, args.into.as_deref());
 
     if current == target {
-        return Err(Error::Other(format!("Cannot merge {current} into itself")));
+        return Err(Error::MergeIntoTrunk(current));
     }
 
-    if git::has_uncommitted_changes()? {
-        return Err(Error::Other(format!(
-            "Worktree '{current}' has uncommitted changes. Commit or stash first."
-        )));
+    let site = ws.merge_site(&target)?;
+
+    let strategy = args.strategy.unwrap_or(config.merge_strategy);
+
+    if !strategy.is_squash() && args.edit {
+        return Err(Error::Other(
+            "--edit only applies to squash merges (use -s squash).".into(),
+        ));
+    }
+    // --no-ff merge commits come from `git merge`, which has no --author.
+    if !strategy.is_squash() && (args.author.is_some() || args.date.is_some()) {
+        return Err(Error::Other(
+            "--author/--date only apply to squash merges (use -s squash).".into(),
+        ));
+    }
+
+    // Read-only: nothing below this point (checkout, hooks, merge) runs.
+    if args.dry_run {
+        print_merge_plan(
+            &current,
+            &target,
+            strategy,
+            config,
+            args.skip_hooks,
+            site.worktree.as_deref(),
+        );
+        return Ok(MergeOutcome::default());
+    }
+
+    // Before hooks/autostash: a diverged target fails regardless.
+    if strategy == MergeStrategy::FfOnly {
+        check_fast_forward(&current, &target)?;
     }
 
-    let wt_path = wt_dir.join(&current);
+    // Not a worktree at all (e.g. merging from the main repo): keep the
+    // managed path so hooks and messages still name where it would be.
+    let wt_path = config
+        .locate_worktree(&current)
+        .or_else(|_| config.worktree_path(&current))?;
     let inside_worktree = git::is_cwd_inside(&wt_path);
 
-    let strategy = args.strategy.unwrap_or(config.merge_strategy);
+    // Popped on every exit path below (Drop), including early errors.
+    let mut autostash = Autostash::default();
+
+    if git::has_uncommitted_changes()? {
+        if !args.autostash {
+            return Err(Error::MergeBlockedByChanges(format!(
+                "Worktree '{current}'"
+            )));
+        }
+        // -d would delete the worktree the stash has to be restored into.
+        if args.delete {
+            return Err(Error::Other(
+                "--autostash cannot restore changes into a worktree removed by --delete.\n\
+                 Commit or discard the uncommitted changes first."
+                    .into(),
+            ));
+        }
+        // The check above looked at the cwd, which need not be `wt_path`
+        // (e.g. merging from the main repo).
+        let cwd = std::env::current_dir().map_err(|e| Error::Other(e.to_string()))?;
+        autostash.push(&cwd, &current)?;
+    }
 
     // Shared across pre_merge/post_merge: same worktree, branch, and target.
     let hook_env = process::HookEnv {
@@ -104,158 +205,321 @@ fn run_merge(
     };
 
     if !args.skip_hooks && !config.hooks.pre_merge.is_empty() {
-        eprintln!("Running pre-merge hooks...");
+        progress!("Running pre-merge hooks...");
         // CWD = worktree so pre_merge and post_merge see the same context.
-        process::run_hooks(&config.hooks.pre_merge, &wt_path, &hook_env)
+        let log = config.hooks_log(&current);
+        process::run_hooks(&config.hooks.pre_merge, &wt_path, &hook_env, log.as_deref())
             .map_err(|e| Error::Other(e.to_string()))?;
     }
 
     let commit_count = git::commit_count(&target, &current).unwrap_or(0);
-    eprintln!("Merging {current} into {target} ({commit_count} commits, {strategy:?})");
+    progress!("Merging {current} into {target} ({commit_count} commits, {strategy:?})");
 
-    std::env::set_current_dir(main_repo).map_err(|e| Error::Other(e.to_string()))?;
+    std::env::set_current_dir(&site.dir).map_err(|e| Error::Other(e.to_string()))?;
 
+    // `merge_into` checks these too; here so nothing gets stashed into a
+    // merge or rebase already under way.
+    site.check_idle()?;
     if git::has_uncommitted_changes()? {
-        return Err(Error::Other(
-            "Main repo has uncommitted changes. Commit or stash before merging.".into(),
-        ));
+        if !args.autostash {
+            return Err(Error::MergeBlockedByChanges(site.location()));
+        }
+        autostash.push(&site.dir, &current)?;
+    }
+
+    let msg_file = meta::merge_msg_path(ws.wt_dir(), &current);
+    let edit = |msg: &str| edit_message(&msg_file, msg);
+    let overrides = CommitOverrides {
+        author: args.author.as_deref(),
+        date: args.date.as_deref(),
+        no_verify: args.no_verify,
+        edit: args.edit.then_some(&edit as &dyn Fn(&str) -> String),
+    };
+
+    let merged = match ws.merge_into(&current, &target, strategy, &overrides)? {
+        MergeResult::Merged => true,
+        // Still on to --then-new: `target` already has the branch's work.
+        MergeResult::UpToDate => {
+            progress!("Nothing to merge: {current} is already up to date with {target}");
+            false
+        }
+        MergeResult::Conflicts(conflicts) => {
+            print_conflict_hint(&conflicts);
+            return Ok(MergeOutcome::CONFLICTED);
+        }
+    };
+
+    if merged {
+        // Match pre_merge: CWD = worktree (still on disk, since cleanup
+        // happens after this block).
+        run_post_merge_hooks(config, &wt_path, &hook_env)?;
     }
-    if git::is_merge_in_progress() {
-        return Err(Error::Other("Main repo has a merge in progress.".into()));
+
+    if site.worktree.is_some() {
+        std::env::set_current_dir(main_repo).map_err(|e| Error::Other(e.to_string()))?;
     }
-    if git::is_rebase_in_progress() {
-        return Err(Error::Other("Main repo has a rebase in progress.".into()));
+
+    if merged {
+        if args.delete {
+            cleanup_worktree(&current, config)?;
+            if inside_worktree {
+                write_path_file(path_file, main_repo)?;
+            }
+            if args.delete_remote {
+                delete_remote_branch(&config.remote, &current);
+            }
+        }
+
+        progress!("Merge complete: {current} into {target}.");
     }
 
-    // Capture main repo's current branch *before* we move HEAD, so we can
-    // restore it if any subsequent step fails.
-    let original_main_branch = git::current_branch().ok();
+    // CWD is the main repo and `target` has the branch's work (merged just
+    // now or already), so `wt new` starts from it. Its path-file write
+    // supersedes any main-repo path written by -d above.
+    if let Some(next) = args.then_new {
+        let branch = Some(next).filter(|b| !b.is_empty());
+        let new_args = NewArgs::chained(branch, target);
+        super::lifecycle::new::run(new_args, config, path_file)?;
+    }
 
-    git::checkout(&target)?;
+    Ok(MergeOutcome {
+        merged,
+        conflicted: false,
+    })
+}
 
-    if !git::dry_run_merge(&current, strategy.is_squash())? {
-        if let Some(orig) = &original_main_branch {
-            let _ = git::checkout(orig);
+/// Stashes taken by `--autostash`, restored (in reverse order) when dropped.
+///
+/// Each entry remembers what `dir` had checked out when stashed: a
+/// successful merge leaves the main repo on the target, and popping there
+/// would move the user's changes onto it.
+///
+/// A pop that conflicts leaves the stash entry intact — git keeps it — and
+/// we only warn, since the merge outcome itself is already decided.
+#[derive(Default)]
+struct Autostash {
+    entries: Vec<StashEntry>,
+}
+
+struct StashEntry {
+    dir: PathBuf,
+    id: String,
+    head: String,
+}
+
+impl Autostash {
+    fn push(&mut self, dir: &Path, branch: &str) -> Result<()> {
+        let head = git::head_state_in(dir)?.checkout_ref().to_string();
+        let message = format!("wt merge --autostash ({branch})");
+        if let Some(id) = git::stash_push(dir, &message)? {
+            progress!("Stashed uncommitted changes in {}", dir.display());
+            self.entries.push(StashEntry {
+                dir: dir.to_path_buf(),
+                id,
+                head,
+            });
         }
-        print_conflict_hint();
-        return Err(Error::Other("Merge aborted due to conflicts".into()));
-    }
-
-    match execute_merge(&current, &target, strategy) {
-        Ok(false) => {
-            eprintln!("Nothing to merge: {current} is already up to date with {target}");
-            // Restore main repo to its prior branch — moving HEAD is a side
-            // effect of the dry-run + checkout sequence; the user didn't
-            // ask for it.
-            if let Some(orig) = &original_main_branch {
-                let _ = git::checkout(orig);
+        Ok(())
+    }
+}
+
+impl Drop for Autostash {
+    fn drop(&mut self) {
+        while let Some(StashEntry { dir, id, head }) = self.entries.pop() {
+            let restored = git::head_state_in(&dir).is_ok_and(|now| now.checkout_ref() == head)
+                || git::checkout_in(&dir, &head).is_ok();
+            if !restored {
+                eprintln!(
+                    "Warning: could not check {head} back out in {}; \
+                     not re-applying autostash there.",
+                    dir.display()
+                );
+                eprintln!("Your changes are kept in stash {id}.");
+                continue;
             }
-            return Ok(());
-        }
-        Err(e) => {
-            // Roll back any squash staging, then return HEAD to where it was.
-            let _ = git::reset_merge();
-            if let Some(orig) = &original_main_branch {
-                let _ = git::checkout(orig);
+            match git::stash_pop(&dir, &id) {
+                Ok(()) => progress!("Restored stashed changes in {}", dir.display()),
+                Err(e) => {
+                    eprintln!(
+                        "Warning: could not re-apply autostash in {}: {e}",
+                        dir.display()
+                    );
+                    eprintln!("Your changes are kept in stash {id}.");
+                    eprintln!("Resolve any conflicts there, then run 'git stash drop' if needed.");
+                }
             }
-            return Err(e);
         }
-        Ok(true) => {}
     }
+}
 
-    if !config.hooks.post_merge.is_empty() {
-        eprintln!("Running post-merge hooks...");
-        // Match pre_merge: CWD = worktree (still on disk, since cleanup
-        // happens after this block).
-        process::run_hooks(&config.hooks.post_merge, &wt_path, &hook_env)
-            .map_err(|e| Error::Other(e.to_string()))?;
+/// Run post_merge hooks after the merge has landed.
+///
+/// Failures only warn unless `hooks.post_merge_strict` is set — the merge
+/// can't be undone at this point, so failing the command would misreport a
+/// successful merge.
+pub fn run_post_merge_hooks(
+    config: &Config,
+    wt_path: &Path,
+    hook_env: &process::HookEnv,
+) -> Result<()> {
+    if config.hooks.post_merge.is_empty() {
+        return Ok(());
     }
-
-    if args.delete {
-        cleanup_worktree(&current, config)?;
-        if inside_worktree {
-            write_path_file(path_file, main_repo)?;
+    progress!("Running post-merge hooks...");
+    let log = config.hooks_log(hook_env.branch);
+    if let Err(e) = process::run_hooks(&config.hooks.post_merge, wt_path, hook_env, log.as_deref())
+    {
+        if config.hooks.is_post_merge_strict() {
+            return Err(Error::Other(e.to_string()));
         }
+        eprintln!("Warning: post_merge {e} (merge already completed)");
     }
+    Ok(())
+}
 
-    eprintln!("Merge complete: {current} into {target}.");
+/// `wt merge --dry-run`: what `Workspace::merge_into` would do, from the same
+/// inputs (commit log, `build_merge_message`), with no side effects.
+fn print_merge_plan(
+    branch: &str,
+    target: &str,
+    strategy: MergeStrategy,
+    config: &Config,
+    skip_hooks: bool,
+    target_wt: Option<&Path>,
+) {
+    let commits = git::commit_count(target, branch).unwrap_or(0);
+    let log = git::log_oneline(target, branch).unwrap_or_default();
+
+    println!("Merge plan (dry run)");
+    println!("Branch:       {branch}");
+    println!("Target:       {target}");
+    if let Some(path) = target_wt {
+        println!(
+            "Merge in:     {} (worktree holding {target})",
+            path.display()
+        );
+    }
+    println!("Strategy:     {strategy:?}");
+    println!("Commits:      {commits}");
 
-    Ok(())
+    let pre = if skip_hooks {
+        "skipped".to_string()
+    } else {
+        config.hooks.pre_merge.len().to_string()
+    };
+    println!(
+        "Hooks:        pre_merge {pre}, post_merge {}",
+        config.hooks.post_merge.len()
+    );
+
+    if git::has_uncommitted_changes().unwrap_or(false) {
+        println!("Note:         worktree has uncommitted changes; merge would be refused");
+    }
+    if strategy == MergeStrategy::FfOnly && check_fast_forward(branch, target).is_err() {
+        println!("Note:         {target} has diverged; ff-only merge would be refused");
+    }
+
+    if commits == 0 {
+        println!("Nothing to merge: {branch} is already up to date with {target}");
+        return;
+    }
+
+    println!("Message:");
+    for line in build_merge_message(branch, &log).lines() {
+        println!("  {line}");
+    }
 }
 
-pub fn print_conflict_hint() {
+/// `conflicts` comes from the dry run; empty when git failed without
+/// leaving unmerged paths (the heading is skipped then).
+pub fn print_conflict_hint(conflicts: &[String]) {
+    print_conflicted_files(conflicts);
     eprintln!("Merge would conflict. Sync first to resolve:");
     eprintln!("  wt sync");
     eprintln!("  wt merge");
 }
 
-/// Build commit message for squash merge
-///
-/// - Single commit: use that commit's message directly
-/// - Multiple commits: "Merge branch 'x'" + list all commits
-/// - No commits: "Merge branch 'x'"
-pub fn build_merge_message(branch: &str, log: &str) -> String {
-    let lines: Vec<&str> = log.lines().filter(|l| !l.is_empty()).collect();
-
-    match lines.len() {
-        0 => format!("Merge branch '{branch}'"),
-        1 => {
-            // Single commit → strip hash prefix, use message directly
-            let line = lines[0];
-            line.split_once(' ')
-                .map(|(_, msg)| msg.to_string())
-                .unwrap_or_else(|| format!("Merge branch '{branch}'"))
-        }
-        _ => {
-            let mut msg = format!("Merge branch '{branch}'\n\n");
-            for line in &lines {
-                msg.push_str(&format!("* {line}\n"));
-            }
-            msg.trim_end().to_string()
-        }
+fn print_conflicted_files(conflicts: &[String]) {
+    if conflicts.is_empty() {
+        return;
+    }
+    eprintln!("Conflicts:");
+    for path in conflicts {
+        eprintln!("  {path}");
     }
 }
 
-/// Execute squash/merge. Caller must already be on trunk.
-///
-/// Returns true if changes were merged, false if already up to date.
-pub fn execute_merge(branch: &str, trunk: &str, strategy: MergeStrategy) -> Result<bool> {
-    let log = git::log_oneline(trunk, branch).unwrap_or_default();
-    let msg = build_merge_message(branch, &log);
-
-    match strategy {
-        MergeStrategy::Squash => {
-            git::merge(branch, true, false, None)?;
-            if git::has_staged_changes()? {
-                git::commit(&msg)?;
-                Ok(true)
-            } else {
-                Ok(false)
-            }
+/// `--edit`: `msg` after a round trip through git's editor. A failing
+/// editor or an emptied message falls back to `msg` where `git commit`
+/// would abort: the squash is already staged, and aborting would strand it.
+fn edit_message(file: &Path, msg: &str) -> String {
+    match try_edit_message(file, msg) {
+        Ok(Some(edited)) => edited,
+        Ok(None) => {
+            eprintln!("Warning: empty commit message; using the generated one");
+            msg.to_string()
         }
-        MergeStrategy::Merge => {
-            // Detect "already up to date" before invoking git: when there
-            // are no commits ahead, `git merge --no-ff` succeeds silently
-            // without producing a merge commit. Returning Ok(true) in that
-            // case would print "Merge complete" and (with -d) cleanup a
-            // worktree even though nothing happened — caller relies on the
-            // bool to know whether to proceed.
-            if git::commit_count(trunk, branch)? == 0 {
-                return Ok(false);
-            }
-            git::merge(branch, false, true, Some(&msg))?;
-            Ok(true)
+        Err(e) => {
+            eprintln!("Warning: {e}; using the generated commit message");
+            msg.to_string()
         }
     }
 }
 
+const EDIT_HELP: &str = "\n\n# Edit the squash commit message for wt merge.\n\
+                         # Lines starting with '#' are ignored; an empty message keeps the original.\n";
+
+/// `file` is per branch and outside the shared `.git`, so concurrent
+/// merges from other worktrees can't clobber it.
+fn try_edit_message(file: &Path, msg: &str) -> Result<Option<String>> {
+    let editor = git::editor()?;
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| Error::Other(e.to_string()))?;
+    }
+    std::fs::write(file, format!("{msg}{EDIT_HELP}"))
+        .map_err(|e| Error::Other(format!("{}: {e}", file.display())))?;
+
+    let status = process::run_editor(&editor, file);
+    let edited = std::fs::read_to_string(file);
+    let _ = std::fs::remove_file(file);
+
+    let status = status.map_err(|e| Error::Other(format!("editor '{editor}': {e}")))?;
+    if !status.success() {
+        return Err(Error::Other(format!(
+            "editor '{editor}' exited with {status}"
+        )));
+    }
+    let edited = edited.map_err(|e| Error::Other(e.to_string()))?;
+    Ok(strip_message_comments(&edited))
+}
+
+/// Drop `#` lines and surrounding blank space; None when nothing is left.
+fn strip_message_comments(text: &str) -> Option<String> {
+    let kept: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(str::trim_end)
+        .collect();
+    let msg = kept.join("\n").trim().to_string();
+    (!msg.is_empty()).then_some(msg)
+}
+
+/// `--delete-remote`: the merge already landed, so failures only warn.
+fn delete_remote_branch(remote: &str, branch: &str) {
+    match git::delete_remote_branch(remote, branch) {
+        Ok(true) => progress!("Deleted remote branch: {remote}/{branch}"),
+        Ok(false) => eprintln!("Warning: {remote} has no branch '{branch}'; nothing to delete"),
+        Err(e) => eprintln!("Warning: failed to delete {remote}/{branch}: {e}"),
+    }
+}
+
 /// Clean up worktree after successful merge
 pub fn cleanup_worktree(branch: &str, config: &Config) -> Result<()> {
-    let workspace_id = git::workspace_id()?;
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
-    let wt_path = wt_dir.join(branch);
+    let wt_path = config
+        .locate_worktree(branch)
+        .or_else(|_| config.worktree_path(branch))?;
 
-    eprintln!("Cleaning up worktree: {branch}");
+    progress!("Cleaning up worktree: {branch}");
 
     git::remove_worktree(&wt_path, false).ok();
 
@@ -263,7 +527,7 @@ pub fn cleanup_worktree(branch: &str, config: &Config) -> Result<()> {
     // the branch is "not fully merged" even though changes are in trunk
     git::delete_branch(branch, true).ok();
 
-    crate::meta::remove_meta(&wt_dir, branch);
+    crate::meta::remove_meta(&config.wt_dir()?, branch);
 
     Ok(())
 }
@@ -273,24 +537,24 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_build_merge_message_with_commits() {
-        let log = "abc1234 Add user authentication\ndef5678 Fix login edge case\n";
-        let msg = build_merge_message("feature-auth", log);
-        assert!(msg.starts_with("Merge branch 'feature-auth'\n"));
-        assert!(msg.contains("abc1234 Add user authentication"));
-        assert!(msg.contains("def5678 Fix login edge case"));
-    }
-
-    #[test]
-    fn test_build_merge_message_single_commit() {
-        let log = "abc1234 Initial implementation\n";
-        let msg = build_merge_message("fix-bug", log);
-        assert_eq!(msg, "Initial implementation");
+    fn test_merge_outcome_into_result() {
+        assert!(MergeOutcome::default().into_result().is_ok());
+        let merged = MergeOutcome {
+            merged: true,
+            conflicted: false,
+        };
+        assert!(merged.into_result().is_ok());
+        let err = MergeOutcome::CONFLICTED.into_result().unwrap_err();
+        assert_eq!(err.exit_code(), crate::cli::EXIT_CONFLICT);
     }
 
     #[test]
-    fn test_build_merge_message_empty_log() {
-        let msg = build_merge_message("my-branch", "");
-        assert_eq!(msg, "Merge branch 'my-branch'");
+    fn test_strip_message_comments() {
+        let edited = format!("Better subject\n\nBody line  \n{EDIT_HELP}");
+        assert_eq!(
+            strip_message_comments(&edited).as_deref(),
+            Some("Better subject\n\nBody line")
+        );
+        assert_eq!(strip_message_comments(EDIT_HELP), None);
     }
 }
@@ -7,6 +7,11 @@ pub mod nav;
 pub mod snap;
 pub mod sys;
 
+pub mod describe;
+pub mod df;
+pub mod diff;
+pub mod inspect;
+pub mod log;
 pub mod ls;
 pub mod merge;
 pub mod r#move;
@@ -14,10 +19,15 @@ pub mod status;
 pub mod sync;
 
 // Re-export argument types
+pub use describe::DescribeArgs;
+pub use diff::DiffArgs;
+pub use inspect::InspectArgs;
 pub use lifecycle::{CleanArgs, NewArgs, RmArgs};
+pub use log::LogArgs;
 pub use ls::LsArgs;
 pub use merge::MergeArgs;
-pub use nav::CdArgs;
-pub use r#move::MoveArgs;
+pub use nav::{CdArgs, OpenArgs};
+pub use r#move::{MoveArgs, RenameArgs};
+pub use snap::{SnapAbortArgs, SnapArgs};
 pub use sync::SyncArgs;
-pub use sys::{InitArgs, SetupArgs};
+pub use sys::{InitArgs, SetupArgs, UpdateArgs};
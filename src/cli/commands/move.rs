@@ -1,5 +1,5 @@
 // ===========================================================================
-// wt move - Rename worktree branch
+// wt move / wt rename - Rename worktree branch
 // ===========================================================================
 
 use std::path::Path;
@@ -7,10 +7,11 @@ use std::path::Path;
 use clap::Args;
 use clap_complete::engine::ArgValueCompleter;
 
-use crate::cli::{write_path_file, Error, Result};
+use crate::cli::{locate_worktree, worktree_path, write_path_file, Error, Result};
 use crate::complete;
 use crate::config::Config;
 use crate::git;
+use crate::meta::{self, WorktreeMeta};
 
 #[derive(Args)]
 pub struct MoveArgs {
@@ -20,11 +21,24 @@ pub struct MoveArgs {
 
     /// New branch name
     new_branch: String,
+
+    /// Rename even with a rebase, merge or conflict in progress
+    #[arg(short, long)]
+    force: bool,
+}
+
+#[derive(Args)]
+pub struct RenameArgs {
+    /// New branch name for the current worktree
+    new_branch: String,
+
+    /// Rename even with a rebase, merge or conflict in progress
+    #[arg(short, long)]
+    force: bool,
 }
 
 pub fn run(args: MoveArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
-    let workspace_id = git::workspace_id()?;
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
+    let wt_dir = config.wt_dir()?;
 
     // Resolve '.' to current branch
     let old_branch = if args.old_branch == "." {
@@ -33,41 +47,109 @@ pub fn run(args: MoveArgs, config: &Config, path_file: Option<&Path>) -> Result<
         args.old_branch
     };
 
-    let old_path = wt_dir.join(&old_branch);
-    let new_path = wt_dir.join(&args.new_branch);
+    rename(
+        &wt_dir,
+        &old_branch,
+        &args.new_branch,
+        args.force,
+        path_file,
+    )
+}
+
+/// `wt rename <new>`: always renames the managed worktree containing the cwd.
+pub fn run_current(args: RenameArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
+    let wt_dir = config.wt_dir()?;
 
-    if !old_path.exists() {
-        return Err(Error::Git(git::Error::WorktreeNotFound(old_branch.clone())));
+    let not_managed = || {
+        Error::Other("Not inside a managed worktree. Use: wt mv <old-branch> <new-branch>".into())
+    };
+    let wt = git::worktree_path_for_cwd()?.ok_or_else(not_managed)?;
+    if !wt.path.starts_with(&wt_dir) {
+        return Err(not_managed());
     }
+    let old_branch = wt.branch.ok_or_else(|| {
+        Error::Other(format!(
+            "Worktree at {} has no branch checked out (detached HEAD)",
+            wt.path.display()
+        ))
+    })?;
+
+    rename(
+        &wt_dir,
+        &old_branch,
+        &args.new_branch,
+        args.force,
+        path_file,
+    )
+}
+
+/// Move the worktree dir, rename the branch and carry its metadata over.
+fn rename(
+    wt_dir: &Path,
+    old_branch: &str,
+    new_branch: &str,
+    force: bool,
+    path_file: Option<&Path>,
+) -> Result<()> {
+    let old_path = locate_worktree(wt_dir, old_branch)?;
+    let new_path = worktree_path(wt_dir, new_branch)?;
 
     if new_path.exists() {
         return Err(Error::Git(git::Error::WorktreeExists(
-            args.new_branch.clone(),
+            new_branch.to_string(),
+        )));
+    }
+
+    // git's in-progress state (rebase todo, MERGE_HEAD) still names the
+    // old branch; renaming underneath it corrupts the operation.
+    if !force && !git::is_worktree_clean(&old_path)? {
+        return Err(Error::Other(format!(
+            "Worktree '{old_branch}' has a rebase, merge or conflict in progress.\n\
+             Finish or abort it first (or pass --force)."
         )));
     }
 
+    // Read metadata before touching git: an unreadable file aborts here
+    // instead of leaving a renamed worktree with orphaned metadata.
+    let old_meta = meta::meta_path_with_fallback(wt_dir, old_branch);
+    let loaded = if old_meta.exists() {
+        Some(
+            WorktreeMeta::load(&old_meta)
+                .map_err(|e| Error::Other(format!("Cannot rename '{old_branch}': {e}")))?,
+        )
+    } else {
+        None
+    };
+
     // Check if we're inside the worktree being renamed
     let inside_target = git::is_cwd_inside(&old_path);
 
-    // Move worktree to new path (updates git's internal tracking)
-    git::move_worktree(&old_path, &new_path)?;
-
-    // Rename branch
-    git::rename_branch(&old_branch, &args.new_branch)?;
+    // Branch first: it fails on the common errors (name taken) before
+    // anything on disk changes. A failed move then renames it back, so
+    // either both happen or neither does.
+    git::rename_branch(old_branch, new_branch)?;
+    if let Err(e) = git::move_worktree(&old_path, &new_path) {
+        let e = move_error(e, &old_path);
+        if let Err(undo) = git::rename_branch(new_branch, old_branch) {
+            return Err(Error::Other(format!(
+                "{e}\nAlso failed to rename branch {new_branch} back to {old_branch}: {undo}"
+            )));
+        }
+        return Err(e);
+    }
 
-    // Rename metadata file (find old with fallback, write new format)
-    let old_meta = crate::meta::meta_path_with_fallback(&wt_dir, &old_branch);
-    let new_meta = crate::meta::meta_path(&wt_dir, &args.new_branch);
-    if old_meta.exists() {
-        std::fs::rename(&old_meta, &new_meta).map_err(|e| {
+    if let Some(loaded) = loaded {
+        let new_meta = meta::meta_path(wt_dir, new_branch);
+        carry_meta(&loaded, &old_meta, &new_meta).map_err(|e| {
             Error::Other(format!(
-                "Failed to rename metadata {}: {e}",
-                old_meta.display()
+                "Failed to move metadata {} -> {}: {e}",
+                old_meta.display(),
+                new_meta.display()
             ))
         })?;
     }
 
-    eprintln!("Renamed {} -> {}", old_branch, args.new_branch);
+    progress!("Renamed {} -> {}", old_branch, new_branch);
 
     // If we were inside the renamed worktree, write new path for shell to cd
     if path_file.is_some() && inside_target {
@@ -76,3 +158,55 @@ pub fn run(args: MoveArgs, config: &Config, path_file: Option<&Path>) -> Result<
 
     Ok(())
 }
+
+/// `git worktree move` failure, with what to do about it when git's own
+/// message doesn't say.
+fn move_error(e: git::Error, path: &Path) -> Error {
+    let hint = match &e {
+        git::Error::Command(msg) => move_hint(msg, path),
+        _ => None,
+    };
+    match hint {
+        Some(hint) => Error::Other(format!("{e}\n{hint}")),
+        None => Error::Git(e),
+    }
+}
+
+fn move_hint(msg: &str, path: &Path) -> Option<String> {
+    if msg.contains("submodules") {
+        Some(
+            "git cannot move worktrees that contain submodules. Nothing was renamed; \
+             to rename only the branch use: git branch -m <old> <new>"
+                .into(),
+        )
+    } else if msg.contains("cross-device") {
+        Some(
+            "The workspaces dir spans filesystems, so git cannot rename the directory. \
+             Nothing was renamed."
+                .into(),
+        )
+    } else if msg.contains("locked working tree") || msg.contains("is locked") {
+        Some(format!(
+            "Unlock it first: git worktree unlock {}",
+            path.display()
+        ))
+    } else {
+        None
+    }
+}
+
+/// Re-save under the new name (legacy `.status.toml` lands as `.toml`),
+/// keeping every field — snap session, description, timestamps.
+fn carry_meta(loaded: &WorktreeMeta, old: &Path, new: &Path) -> meta::Result<()> {
+    if let Some(parent) = new.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // A newer `wt` may have written fields this binary would drop on save.
+    if loaded.is_newer_schema() {
+        std::fs::rename(old, new)?;
+        return Ok(());
+    }
+    loaded.save(new)?;
+    std::fs::remove_file(old)?;
+    Ok(())
+}
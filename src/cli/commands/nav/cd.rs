@@ -2,23 +2,29 @@
 // wt cd - Change to worktree directory
 // ===========================================================================
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use clap::Args;
 use clap_complete::engine::ArgValueCompleter;
 
-use crate::cli::{write_path_file, Error, Result};
+use crate::cli::{clear_path_file, write_path_file, Error, Result};
 use crate::complete;
 use crate::config::Config;
 use crate::git;
+use crate::meta;
 
 #[derive(Args)]
 pub struct CdArgs {
-    /// Branch name to switch to (omit to return to main repo)
+    /// Branch name to switch to (omit to return to main repo; '-' for the
+    /// previous location)
     #[arg(add = ArgValueCompleter::new(complete::complete_worktrees))]
     branch: Option<String>,
 }
 
+/// `wt cd -`: like the shell's `cd -`, go back to where the last `wt cd`
+/// started.
+const PREVIOUS: &str = "-";
+
 pub fn run(args: CdArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
     // `wt cd` only makes sense behind the shell wrapper — a child process
     // can't change its parent shell's CWD. Without a path_file the wrapper
@@ -30,20 +36,104 @@ pub fn run(args: CdArgs, config: &Config, path_file: Option<&Path>) -> Result<()
         ));
     }
 
-    let Some(branch) = args.branch else {
-        let repo_root = git::repo_root()?;
-        write_path_file(path_file, &repo_root)?;
-        return Ok(());
-    };
-
     let workspace_id = git::workspace_id()?;
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
-    let wt_path = wt_dir.join(&branch);
+    let wt_dir = config.wt_dir()?;
+    let history = last_cd_file(&config.base_dir, &workspace_id);
 
-    if !wt_path.exists() {
-        return Err(Error::Git(git::Error::WorktreeNotFound(branch)));
+    let target = match args.branch.as_deref() {
+        None => {
+            let main = git::main_worktree()?.ok_or_else(|| {
+                Error::Other(
+                    "This repository is bare; there is no main working tree to return to.".into(),
+                )
+            })?;
+            // A redundant cd would still reset the shell's own `cd -`.
+            let here = std::env::current_dir().and_then(|d| d.canonicalize());
+            if here.is_ok_and(|d| d == main) {
+                progress!("Already in main repo");
+                clear_path_file(path_file);
+                return Ok(());
+            }
+            main
+        }
+        Some(PREVIOUS) => read_last_cd(&history)?,
+        Some(branch) => config.locate_worktree(branch)?,
+    };
+
+    // History is a convenience: failing to record it must not block the cd.
+    if let Ok(cwd) = std::env::current_dir() {
+        record_last_cd(&history, &cwd).ok();
     }
 
-    write_path_file(path_file, &wt_path)?;
+    write_path_file(path_file, &target)?;
+    if let Some(branch) = args.branch.filter(|b| b != PREVIOUS) {
+        meta::touch_last_accessed(&wt_dir, &branch).ok();
+    }
     Ok(())
 }
+
+/// One file per workspace, so `wt cd -` never jumps into another repo.
+fn last_cd_file(base_dir: &Path, workspace_id: &str) -> PathBuf {
+    base_dir.join("last_cd").join(workspace_id)
+}
+
+fn read_last_cd(file: &Path) -> Result<PathBuf> {
+    let last = std::fs::read_to_string(file).unwrap_or_default();
+    let last = last.trim();
+    if last.is_empty() {
+        return Err(Error::Other("no previous location".into()));
+    }
+    let path = PathBuf::from(last);
+    if !path.is_dir() {
+        return Err(Error::Other(format!(
+            "previous location {} no longer exists",
+            path.display()
+        )));
+    }
+    Ok(path)
+}
+
+fn record_last_cd(file: &Path, location: &Path) -> std::io::Result<()> {
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(file, location.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_last_cd_without_history_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = read_last_cd(&last_cd_file(dir.path(), "repo-abc123")).unwrap_err();
+        assert_eq!(err.to_string(), "no previous location");
+    }
+
+    #[test]
+    fn record_then_read_last_cd_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = last_cd_file(dir.path(), "repo-abc123");
+        record_last_cd(&file, dir.path()).unwrap();
+        assert_eq!(read_last_cd(&file).unwrap(), dir.path());
+    }
+
+    #[test]
+    fn read_last_cd_rejects_removed_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = last_cd_file(dir.path(), "repo-abc123");
+        record_last_cd(&file, &dir.path().join("gone")).unwrap();
+        let err = read_last_cd(&file).unwrap_err();
+        assert!(err.to_string().contains("no longer exists"));
+    }
+
+    #[test]
+    fn last_cd_file_is_per_workspace() {
+        let base = Path::new("/base");
+        assert_ne!(
+            last_cd_file(base, "a-000001"),
+            last_cd_file(base, "b-000002")
+        );
+    }
+}
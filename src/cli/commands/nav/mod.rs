@@ -1,3 +1,5 @@
 pub mod cd;
+pub mod open;
 
 pub use cd::CdArgs;
+pub use open::OpenArgs;
@@ -0,0 +1,110 @@
+// ===========================================================================
+// wt open - Launch an editor in a worktree
+// ===========================================================================
+
+use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::cli::{Error, Result};
+use crate::complete;
+use crate::config::Config;
+use crate::git;
+use crate::meta::{self, WorktreeMeta};
+use crate::process;
+
+#[derive(Args)]
+pub struct OpenArgs {
+    /// Branch name to open (use '.' for current worktree)
+    #[arg(add = ArgValueCompleter::new(complete::complete_worktrees))]
+    branch: String,
+}
+
+pub fn run(args: OpenArgs, config: &Config) -> Result<()> {
+    let editor = resolve_editor(
+        config.editor.as_deref(),
+        std::env::var("VISUAL").ok().as_deref(),
+        std::env::var("EDITOR").ok().as_deref(),
+    )
+    .ok_or_else(|| {
+        Error::Other(
+            "No editor configured. Set one of:\n  \
+             editor = \"code\" under [general] in ~/.agent-worktree/config.toml\n  \
+             $VISUAL\n  \
+             $EDITOR"
+                .into(),
+        )
+    })?;
+
+    let main_repo = git::repo_root()?;
+    let wt_dir = config.wt_dir()?;
+
+    // Resolve '.' to current branch
+    let branch = if args.branch == "." {
+        git::current_branch()?
+    } else {
+        args.branch
+    };
+
+    let wt_path = config.locate_worktree(&branch)?;
+
+    let base_branch = WorktreeMeta::load(&meta::meta_path_with_fallback(&wt_dir, &branch))
+        .map(|m| m.base_branch)
+        .unwrap_or_else(|_| config.resolve_trunk());
+    let env = process::HookEnv {
+        main_repo: &main_repo,
+        worktree: &wt_path,
+        branch: &branch,
+        base_branch: &base_branch,
+    };
+
+    let status = process::run_interactive(&format!("{editor} ."), &wt_path, &env)
+        .map_err(|e| Error::Other(e.to_string()))?;
+    if !status.success() {
+        return Err(Error::Other(format!(
+            "Editor '{editor}' exited with {status}"
+        )));
+    }
+    Ok(())
+}
+
+/// Priority: config `editor` > $VISUAL > $EDITOR. Blank values are skipped.
+fn resolve_editor(
+    configured: Option<&str>,
+    visual: Option<&str>,
+    editor: Option<&str>,
+) -> Option<String> {
+    [configured, visual, editor]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_editor_config_wins() {
+        let got = resolve_editor(Some("code"), Some("vim"), Some("nano"));
+        assert_eq!(got.as_deref(), Some("code"));
+    }
+
+    #[test]
+    fn resolve_editor_visual_before_editor() {
+        let got = resolve_editor(None, Some("vim"), Some("nano"));
+        assert_eq!(got.as_deref(), Some("vim"));
+    }
+
+    #[test]
+    fn resolve_editor_skips_blank() {
+        let got = resolve_editor(Some("  "), Some(""), Some("nano"));
+        assert_eq!(got.as_deref(), Some("nano"));
+    }
+
+    #[test]
+    fn resolve_editor_none() {
+        assert!(resolve_editor(None, None, None).is_none());
+    }
+}
@@ -0,0 +1,130 @@
+// ===========================================================================
+// wt snap-abort - Finish an orphaned snap session from outside its loop
+// ===========================================================================
+
+use std::path::Path;
+
+use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::cli::{write_path_file, Error, Result};
+use crate::complete;
+use crate::config::Config;
+use crate::git;
+use crate::meta;
+use crate::prompt;
+
+#[derive(Args)]
+pub struct SnapAbortArgs {
+    /// Worktree whose snap session to abort (default: current worktree)
+    #[arg(add = ArgValueCompleter::new(complete::complete_worktrees))]
+    branch: Option<String>,
+
+    /// Also remove the worktree and its branch (asks for confirmation)
+    #[arg(long)]
+    rm: bool,
+}
+
+/// What happens to the worktree once its session state is cleared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbortOutcome {
+    Preserve,
+    Remove,
+}
+
+/// Removal needs both `--rm` and a yes; declining keeps the worktree (the
+/// session is already cleared either way).
+pub fn decide_outcome(
+    rm: bool,
+    confirm: impl FnOnce() -> prompt::Result<bool>,
+) -> Result<AbortOutcome> {
+    if !rm {
+        return Ok(AbortOutcome::Preserve);
+    }
+    match confirm() {
+        Ok(true) => Ok(AbortOutcome::Remove),
+        Ok(false) => Ok(AbortOutcome::Preserve),
+        Err(e) => Err(Error::Other(e.to_string())),
+    }
+}
+
+pub fn run(args: SnapAbortArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
+    let main_repo = git::repo_root()?;
+    let wt_dir = config.wt_dir()?;
+
+    let branch = match args.branch {
+        Some(b) if b != "." => b,
+        _ => git::current_branch()?,
+    };
+
+    let wt_path = config.locate_worktree(&branch)?;
+
+    let cleared = meta::clear_snap_state(&wt_dir, &branch)
+        .map_err(|e| Error::Other(format!("Cannot abort snap session of '{branch}': {e}")))?;
+    if !cleared {
+        return Err(Error::Other(format!(
+            "Worktree '{branch}' has no active snap session."
+        )));
+    }
+    progress!("Cleared snap session for {branch}");
+
+    let outcome = decide_outcome(args.rm, || {
+        prompt::confirm(&format!("Remove worktree '{branch}' and its branch?"))
+    })?;
+
+    if outcome == AbortOutcome::Preserve {
+        eprintln!("Worktree preserved: {}", wt_path.display());
+        eprintln!("Continue there, then 'wt merge' (or 'wt rm {branch}' to discard).");
+        return Ok(());
+    }
+
+    let inside_target = git::is_cwd_inside(&wt_path);
+    if inside_target && path_file.is_none() {
+        return Err(Error::Other(
+            "Refusing to remove the current worktree without shell integration.\n\
+             Run 'wt setup' first, or 'cd' to the main repo and retry."
+                .into(),
+        ));
+    }
+
+    // Non-force, like snap cleanup: leftover untracked files fail loudly.
+    git::remove_worktree(&wt_path, false)?;
+    git::delete_branch_in(&main_repo, &branch, true).ok();
+    meta::remove_meta(&wt_dir, &branch);
+    progress!("Removed worktree: {branch}");
+
+    if inside_target {
+        write_path_file(path_file, &main_repo)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide_outcome_without_rm_preserves() {
+        let outcome = decide_outcome(false, || panic!("must not prompt")).unwrap();
+        assert_eq!(outcome, AbortOutcome::Preserve);
+    }
+
+    #[test]
+    fn test_decide_outcome_rm_confirmed_removes() {
+        let outcome = decide_outcome(true, || Ok(true)).unwrap();
+        assert_eq!(outcome, AbortOutcome::Remove);
+    }
+
+    #[test]
+    fn test_decide_outcome_rm_declined_preserves() {
+        let outcome = decide_outcome(true, || Ok(false)).unwrap();
+        assert_eq!(outcome, AbortOutcome::Preserve);
+    }
+
+    #[test]
+    fn test_decide_outcome_rm_not_interactive_errors() {
+        let result = decide_outcome(true, || Err(prompt::Error::NotInteractive("x".into())));
+        assert!(result.unwrap_err().to_string().contains("--yes"));
+    }
+}
@@ -1 +1,6 @@
+pub mod abort;
 pub mod resume;
+pub mod start;
+
+pub use abort::SnapAbortArgs;
+pub use start::SnapArgs;
@@ -6,6 +6,7 @@ use std::path::{Path, PathBuf};
 
 // Exit codes consumed by the shell wrapper's snap loop.
 // Keep in sync with the `case $continue_status` blocks in src/shell/mod.rs.
+// Other commands: 1 = failure, 4 = `wt merge` conflict (cli::EXIT_CONFLICT).
 pub const EXIT_DONE: i32 = 0;
 pub const EXIT_REOPEN: i32 = 2;
 pub const EXIT_PRESERVE: i32 = 3;
@@ -16,6 +17,7 @@ use crate::git;
 use crate::meta::{self, WorktreeMeta};
 use crate::process;
 use crate::prompt::{self, SnapExitChoice, SnapMergeChoice};
+use crate::workspace::{MergeResult, Workspace};
 
 // ===========================================================================
 // Public Types
@@ -64,11 +66,10 @@ pub fn run(config: &Config, path_file: Option<&Path>) -> Result<()> {
 pub fn gather_context(config: &Config) -> Result<SnapContext> {
     let cwd = std::env::current_dir().map_err(|e| Error::Other(e.to_string()))?;
     let branch = git::current_branch()?;
-    let workspace_id = git::workspace_id()?;
     let repo_root = git::repo_root()?;
 
     // Load metadata to get base_branch (fallback to legacy .status.toml).
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
+    let wt_dir = config.wt_dir()?;
     let meta_path = meta::meta_path_with_fallback(&wt_dir, &branch);
     let loaded_meta = WorktreeMeta::load(&meta_path).ok();
 
@@ -87,11 +88,14 @@ pub fn gather_context(config: &Config) -> Result<SnapContext> {
                  Resolve manually with: wt merge --into <branch>"
             )));
         }
-        None => config.resolve_trunk(),
+        None => config.resolve_trunk_for(&branch),
     };
 
-    let has_uncommitted = git::has_uncommitted_changes().unwrap_or(false);
-    let has_commits_ahead = git::commit_count(&merge_target, "HEAD").unwrap_or(0) > 0;
+    // Propagate failures: an empty summary would read as "nothing to keep"
+    // and send the worktree to cleanup.
+    let summary = git::work_summary(&merge_target, &cwd)?;
+    let has_uncommitted = summary.uncommitted > 0;
+    let has_commits_ahead = summary.commits_ahead > 0;
 
     Ok(SnapContext {
         cwd,
@@ -158,13 +162,22 @@ pub fn determine_action_with_choice(
 /// Uses non-force removal so that any untracked files left in the worktree
 /// (build artifacts, .env, agent-generated scratch) cause the cleanup to
 /// fail loudly instead of silently deleting work.
-pub fn cleanup_worktree(wt_path: &Path, branch: &str, config: &Config) -> Result<()> {
+///
+/// The cwd is usually `wt_path` itself, so everything after the removal
+/// runs against `repo_root` — cwd-relative git calls would fail once the
+/// directory is gone.
+pub fn cleanup_worktree(
+    wt_path: &Path,
+    branch: &str,
+    repo_root: &Path,
+    config: &Config,
+) -> Result<()> {
+    let wt_dir = config.wt_dir().ok();
+
     git::remove_worktree(wt_path, false)?;
-    git::delete_branch(branch, true).ok();
+    git::delete_branch_in(repo_root, branch, true).ok();
 
-    // Remove metadata
-    if let Ok(workspace_id) = git::workspace_id() {
-        let wt_dir = config.workspaces_dir.join(&workspace_id);
+    if let Some(wt_dir) = wt_dir {
         meta::remove_meta(&wt_dir, branch);
     }
 
@@ -175,6 +188,14 @@ pub fn cleanup_worktree(wt_path: &Path, branch: &str, config: &Config) -> Result
 // Side Effects (Hard to Test)
 // ===========================================================================
 
+/// The shell loop stops on EXIT_PRESERVE; drop the session marker so the
+/// preserved worktree isn't mistaken for an orphaned snap session.
+fn end_session(ctx: &SnapContext, config: &Config) {
+    if let Ok(wt_dir) = config.wt_dir() {
+        meta::clear_snap_state(&wt_dir, &ctx.branch).ok();
+    }
+}
+
 /// Execute action with side effects
 fn execute_action(
     ctx: &SnapContext,
@@ -184,8 +205,8 @@ fn execute_action(
 ) -> Result<()> {
     match action {
         SnapAction::CleanupNoChanges => {
-            eprintln!("No changes detected. Cleaning up...");
-            cleanup_worktree(&ctx.cwd, &ctx.branch, config)?;
+            progress!("No changes detected. Cleaning up...");
+            cleanup_worktree(&ctx.cwd, &ctx.branch, &ctx.repo_root, config)?;
             write_path_file(path_file, &ctx.repo_root)?;
             std::process::exit(EXIT_DONE);
         }
@@ -200,62 +221,62 @@ fn execute_action(
 
             // Run pre-merge hooks
             if !config.hooks.pre_merge.is_empty() {
-                eprintln!("Running pre-merge hooks...");
-                process::run_hooks(&config.hooks.pre_merge, &ctx.cwd, &hook_env)
+                progress!("Running pre-merge hooks...");
+                let log = config.hooks_log(&ctx.branch);
+                process::run_hooks(&config.hooks.pre_merge, &ctx.cwd, &hook_env, log.as_deref())
                     .map_err(|e| Error::Other(e.to_string()))?;
             }
 
-            eprintln!("Merging {} into {}...", ctx.branch, ctx.merge_target);
-
-            std::env::set_current_dir(&ctx.repo_root).map_err(|e| Error::Other(e.to_string()))?;
-            git::checkout(&ctx.merge_target)?;
-
-            if !git::dry_run_merge(&ctx.branch, config.merge_strategy.is_squash())? {
-                git::checkout(&ctx.merge_target).ok();
-                let _ = std::env::set_current_dir(&ctx.cwd);
-                super::super::merge::print_conflict_hint();
-                eprintln!();
-                eprintln!(
-                    "Conflicts in worktree '{}'. Resolve there, then 'wt merge'.",
-                    ctx.branch
-                );
-                std::process::exit(EXIT_PRESERVE);
-            }
+            progress!("Merging {} into {}...", ctx.branch, ctx.merge_target);
 
-            if let Err(e) = super::super::merge::execute_merge(
+            // All main-repo git work goes through `-C repo_root`; the process
+            // cwd stays in the worktree throughout.
+            let ws = Workspace::open(config.clone())?;
+            match ws.merge_into(
                 &ctx.branch,
                 &ctx.merge_target,
                 config.merge_strategy,
+                &Default::default(),
             ) {
-                eprintln!("Merge failed: {e}");
-                let _ = git::reset_merge();
-                let _ = git::checkout(&ctx.merge_target);
-                let _ = std::env::set_current_dir(&ctx.cwd);
-                eprintln!(
-                    "Worktree '{}' preserved. Inspect there and retry.",
-                    ctx.branch
-                );
-                std::process::exit(EXIT_PRESERVE);
+                Ok(MergeResult::Merged | MergeResult::UpToDate) => {}
+                Ok(MergeResult::Conflicts(conflicts)) => {
+                    end_session(ctx, config);
+                    super::super::merge::print_conflict_hint(&conflicts);
+                    eprintln!();
+                    eprintln!(
+                        "Conflicts in worktree '{}'. Resolve there, then 'wt merge'.",
+                        ctx.branch
+                    );
+                    std::process::exit(EXIT_PRESERVE);
+                }
+                Err(e) => {
+                    eprintln!("Merge failed: {e}");
+                    end_session(ctx, config);
+                    eprintln!(
+                        "Worktree '{}' preserved. Inspect there and retry.",
+                        ctx.branch
+                    );
+                    std::process::exit(EXIT_PRESERVE);
+                }
             }
 
-            eprintln!("Merged {} into {}", ctx.branch, ctx.merge_target);
+            progress!("Merged {} into {}", ctx.branch, ctx.merge_target);
 
             // Match pre_merge CWD so hooks see the same context across phases.
-            if !config.hooks.post_merge.is_empty() {
-                eprintln!("Running post-merge hooks...");
-                process::run_hooks(&config.hooks.post_merge, &ctx.cwd, &hook_env)
-                    .map_err(|e| Error::Other(e.to_string()))?;
-            }
+            super::super::merge::run_post_merge_hooks(config, &ctx.cwd, &hook_env)?;
 
-            cleanup_worktree(&ctx.cwd, &ctx.branch, config)?;
+            cleanup_worktree(&ctx.cwd, &ctx.branch, &ctx.repo_root, config)?;
             write_path_file(path_file, &ctx.repo_root)?;
             std::process::exit(EXIT_DONE);
         }
         SnapAction::Reopen => {
-            eprintln!("Reopening agent...");
+            // The wrapper reruns the command it was handed, i.e. the meta's
+            // `snap_command`.
+            progress!("Reopening agent...");
             std::process::exit(EXIT_REOPEN);
         }
         SnapAction::ExitPreserve => {
+            end_session(ctx, config);
             eprintln!();
             eprintln!("Exiting snap mode. Worktree preserved.");
             eprintln!();
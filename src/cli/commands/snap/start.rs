@@ -0,0 +1,94 @@
+// ===========================================================================
+// wt snap - Enter snap mode in an existing worktree
+// ===========================================================================
+
+use std::path::Path;
+
+use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::cli::commands::lifecycle::new::resolve_snap_command;
+use crate::cli::{write_path_file_lines, Error, Result};
+use crate::complete;
+use crate::config::Config;
+use crate::git;
+use crate::meta::{self, SnapState, WorktreeMeta};
+
+#[derive(Args)]
+pub struct SnapArgs {
+    /// Worktree to run the agent in ('.' for current)
+    #[arg(add = ArgValueCompleter::new(complete::complete_worktrees))]
+    branch: String,
+
+    /// Agent command (after `--`; `@name` uses the `[agent]` preset).
+    /// Default: the last snap command run in this worktree
+    #[arg(last = true, value_name = "CMD")]
+    cmd: Vec<String>,
+}
+
+/// Same hand-off as `wt new --snap`: the path file gets the worktree path
+/// and the agent command, and the shell wrapper runs the snap-continue loop.
+pub fn run(args: SnapArgs, config: &Config, path_file: Option<&Path>) -> Result<()> {
+    let wt_dir = config.wt_dir()?;
+
+    let branch = match args.branch.as_str() {
+        "." => git::current_branch()?,
+        b => b.to_string(),
+    };
+    let wt_path = config.locate_worktree(&branch)?;
+
+    // Without the wrapper nobody runs the loop, and the session marker
+    // would be left behind as an orphan.
+    if path_file.is_none() {
+        return Err(Error::Other(
+            "Snap mode requires shell integration. Run 'wt setup' first.".into(),
+        ));
+    }
+
+    // Nested snap stacks two loops in the parent shell (see `wt new --snap`).
+    if git::is_cwd_inside(&wt_dir) {
+        if let Ok(current) = git::current_branch() {
+            if has_session(&wt_dir, &current) {
+                return Err(Error::Other(format!(
+                    "Refusing to start snap mode inside the snap session of '{current}'.\n\
+                     Exit that agent first, or run 'wt snap-abort {current}' if it is orphaned."
+                )));
+            }
+        }
+    }
+
+    let command = if args.cmd.is_empty() {
+        last_command(&wt_dir, &branch)?
+    } else {
+        resolve_snap_command(config, args.cmd.join(" "))
+    };
+    let started = meta::set_snap_state(&wt_dir, &branch, SnapState::new(command.clone()))
+        .map_err(|e| Error::Other(format!("Cannot start snap session in '{branch}': {e}")))?;
+    if !started {
+        return Err(Error::Other(format!(
+            "Worktree '{branch}' already has an active snap session.\n\
+             Run 'wt snap-abort {branch}' if it is orphaned."
+        )));
+    }
+
+    write_path_file_lines(path_file, &[&wt_path.display().to_string(), &command])
+}
+
+/// Stored already resolved, so a preset edited since is not re-expanded.
+fn last_command(wt_dir: &Path, branch: &str) -> Result<String> {
+    WorktreeMeta::load(&meta::meta_path_with_fallback(wt_dir, branch))
+        .ok()
+        .and_then(|m| m.snap_command)
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "No previous snap command for '{branch}'.\n\
+                 Pass one after '--': wt snap {branch} -- <cmd>"
+            ))
+        })
+}
+
+fn has_session(wt_dir: &Path, branch: &str) -> bool {
+    WorktreeMeta::load(&meta::meta_path_with_fallback(wt_dir, branch))
+        .map(|m| m.snap_state.is_some())
+        .unwrap_or(false)
+}
@@ -9,15 +9,14 @@ use crate::meta::{self, WorktreeMeta};
 
 pub fn run(config: &Config) -> Result<()> {
     let current = git::current_branch()?;
-    let workspace_id = git::workspace_id()?;
-    let wt_dir = config.workspaces_dir.join(&workspace_id);
-    let wt_path = wt_dir.join(&current);
-
-    if !wt_path.exists() {
-        return Err(Error::Other(format!(
-            "Not in a managed worktree (branch: {current})"
-        )));
-    }
+    let wt_dir = config.wt_dir()?;
+    let wt_path = match config.locate_worktree(&current) {
+        Ok(path) => path,
+        Err(Error::Git(git::Error::WorktreeNotFound(_))) => {
+            return Err(Error::WorktreeNotManaged(current))
+        }
+        Err(e) => return Err(e),
+    };
 
     let trunk = config.resolve_trunk();
 
@@ -58,6 +57,9 @@ pub fn run(config: &Config) -> Result<()> {
             "Created:      {}",
             m.created_at.format("%Y-%m-%d %H:%M:%S UTC")
         );
+        if let Some(ref desc) = m.description {
+            println!("Description:  {desc}");
+        }
     }
 
     println!("Commits:      {commits}");
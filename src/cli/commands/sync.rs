@@ -1,7 +1,9 @@
 // ===========================================================================
-// wt sync - Sync current worktree with trunk
+// wt sync - Sync current (or named) worktree with trunk
 // ===========================================================================
 
+use std::path::Path;
+
 use clap::Args;
 use clap_complete::engine::ArgValueCompleter;
 
@@ -13,14 +15,30 @@ use crate::meta;
 
 #[derive(Args)]
 pub struct SyncArgs {
+    /// Worktree to sync (default: current; use '.' for current)
+    #[arg(
+        add = ArgValueCompleter::new(complete::complete_worktrees),
+        conflicts_with_all = ["continue", "abort"]
+    )]
+    branch: Option<String>,
+
     /// Sync strategy (default: rebase)
     #[arg(short, long, value_enum)]
     strategy: Option<SyncStrategy>,
 
+    /// Rebase interactively (git rebase -i) to squash/fixup while syncing
+    #[arg(short, long, conflicts_with_all = ["continue", "abort"])]
+    interactive: bool,
+
     /// Source branch to sync from (default: base branch or trunk)
     #[arg(long, value_name = "BRANCH", add = ArgValueCompleter::new(complete::complete_branches))]
     from: Option<String>,
 
+    /// Fetch general.remote (default: origin) first and sync against its
+    /// copy of the target; if the fetch fails, warn and use the local one
+    #[arg(long, conflicts_with_all = ["continue", "abort"])]
+    fetch: bool,
+
     /// Continue sync after resolving conflicts
     #[arg(long)]
     r#continue: bool,
@@ -33,13 +51,13 @@ pub struct SyncArgs {
 pub fn run(args: SyncArgs, config: &Config) -> Result<()> {
     if args.abort {
         if git::is_rebase_in_progress() {
-            eprintln!("Aborting rebase...");
+            progress!("Aborting rebase...");
             git::rebase_abort()?;
-            eprintln!("Rebase aborted.");
+            progress!("Rebase aborted.");
         } else if git::is_merge_in_progress() {
-            eprintln!("Aborting merge...");
+            progress!("Aborting merge...");
             git::merge_abort()?;
-            eprintln!("Merge aborted.");
+            progress!("Merge aborted.");
         } else {
             return Err(Error::Other("No sync in progress to abort".into()));
         }
@@ -48,61 +66,115 @@ pub fn run(args: SyncArgs, config: &Config) -> Result<()> {
 
     if args.r#continue {
         if git::is_rebase_in_progress() {
-            eprintln!("Continuing rebase...");
+            progress!("Continuing rebase...");
             git::rebase_continue()?;
-            eprintln!("Rebase continued.");
+            progress!("Rebase continued.");
         } else if git::is_merge_in_progress() {
-            eprintln!("Continuing merge...");
+            progress!("Continuing merge...");
             git::merge_continue()?;
-            eprintln!("Merge continued.");
+            progress!("Merge continued.");
         } else {
             return Err(Error::Other("No sync in progress to continue".into()));
         }
         return Ok(());
     }
 
-    let current = git::current_branch()?;
+    let wt_dir = config.wt_dir()?;
+
+    // A named worktree is synced in place via `git -C`, so it works from the
+    // main repo or from any other worktree without cd-ing into it.
+    let (current, wt_path, named) = match args.branch.as_deref() {
+        None | Some(".") => {
+            let cwd = std::env::current_dir().map_err(|e| Error::Other(e.to_string()))?;
+            (git::current_branch()?, cwd, false)
+        }
+        Some(branch) => (branch.to_string(), config.locate_worktree(branch)?, true),
+    };
 
     if let Some(ref branch) = args.from {
         if !git::branch_exists(branch)? {
             return Err(Error::Other(format!("Branch '{branch}' does not exist")));
         }
-        eprintln!(
+        progress!(
             "Note: --from '{branch}' applies to this sync only. \
              The worktree's base branch is unchanged."
         );
     }
 
-    let target = {
-        let workspace_id = git::workspace_id()?;
-        let wt_dir = config.workspaces_dir.join(&workspace_id);
-        meta::resolve_effective_target(
-            &wt_dir,
-            &current,
-            args.from.as_deref(),
-            |b| git::branch_exists(b).unwrap_or(false),
-            &config.resolve_trunk(),
-        )
-    };
+    let target = meta::resolve_effective_target(
+        &wt_dir,
+        &current,
+        args.from.as_deref(),
+        |b| git::branch_exists(b).unwrap_or(false),
+        &config.resolve_trunk_for(&current),
+    );
 
     if current == target {
-        return Err(Error::Other(format!("Cannot sync {current} with itself")));
+        return Err(Error::AlreadyOnTrunk(current));
     }
 
+    let target = if args.fetch {
+        fetched_target(&config.remote, target)?
+    } else {
+        target
+    };
+
     let strategy = args.strategy.unwrap_or(config.sync_strategy);
+    if args.interactive && strategy != SyncStrategy::Rebase {
+        return Err(Error::Other(
+            "--interactive requires the rebase strategy (-s rebase)".into(),
+        ));
+    }
 
-    eprintln!("Syncing {current} with {target} ({strategy:?})...");
+    progress!("Syncing {current} with {target} ({strategy:?})...");
+
+    let result = match strategy {
+        SyncStrategy::Rebase if args.interactive => git::rebase_interactive_in(&wt_path, &target),
+        SyncStrategy::Rebase => git::rebase_in(&wt_path, &target),
+        SyncStrategy::Merge => git::merge_in(&wt_path, &target, false, false, None, false),
+    };
+    if let Err(e) = result {
+        if named {
+            return Err(conflict_error(&current, &wt_path, e));
+        }
+        return Err(e.into());
+    }
 
     match strategy {
-        SyncStrategy::Rebase => {
-            git::rebase(&target)?;
-            eprintln!("Rebased onto {target}");
+        SyncStrategy::Rebase => progress!("Rebased {current} onto {target}"),
+        SyncStrategy::Merge => progress!("Merged {target} into {current}"),
+    }
+
+    Ok(())
+}
+
+/// `--fetch`: `<remote>/<target>` once fetched; the local `target` when the
+/// fetch fails (with a warning) or the remote has no such branch.
+fn fetched_target(remote: &str, target: String) -> Result<String> {
+    progress!("Fetching {remote}...");
+    match git::fetch(remote)? {
+        git::FetchStatus::Fetched if git::remote_branch_exists(remote, &target)? => {
+            Ok(format!("{remote}/{target}"))
+        }
+        git::FetchStatus::Fetched => {
+            eprintln!("Warning: {remote} has no branch '{target}', syncing against local {target}");
+            Ok(target)
         }
-        SyncStrategy::Merge => {
-            git::merge(&target, false, false, None)?;
-            eprintln!("Merged {target} into {current}");
+        git::FetchStatus::Failed(reason) => {
+            eprintln!("Warning: fetch failed, syncing against local {target}: {reason}");
+            Ok(target)
         }
     }
+}
 
-    Ok(())
+/// Point at the worktree left mid-rebase/merge; `--continue`/`--abort` act
+/// on the cwd, so the user has to go there first.
+fn conflict_error(branch: &str, wt_path: &Path, e: git::Error) -> Error {
+    Error::Other(format!(
+        "{e}\n\n\
+         Worktree '{branch}' needs attention: {}\n\
+         Resolve conflicts there (wt cd {branch}), then: wt sync --continue\n\
+         Or abort: wt sync --abort",
+        wt_path.display()
+    ))
 }
@@ -6,10 +6,11 @@ use std::path::Path;
 
 use clap::Args;
 use clap_complete::engine::ArgValueCompleter;
+use serde::Serialize;
 
 use crate::cli::{Error, Result};
 use crate::complete;
-use crate::config::{MergeStrategy, ProjectConfig, SyncStrategy};
+use crate::config::{Config, MergeStrategy, ProjectConfig, ProjectGeneralConfig, SyncStrategy};
 use crate::git;
 
 #[derive(Args)]
@@ -29,9 +30,13 @@ pub struct InitArgs {
     /// Files to copy from main repo to new worktrees (can be repeated)
     #[arg(long, value_name = "PATTERN")]
     copy_files: Vec<String>,
+
+    /// Write only the options given, without the commented-out examples
+    #[arg(long)]
+    minimal: bool,
 }
 
-pub fn run(args: InitArgs) -> Result<()> {
+pub fn run(args: InitArgs, config: &Config) -> Result<()> {
     let config_path = Path::new(".agent-worktree.toml");
 
     if config_path.exists() {
@@ -41,7 +46,7 @@ pub fn run(args: InitArgs) -> Result<()> {
     // Detect trunk if not specified
     let trunk = args
         .trunk
-        .or_else(|| git::detect_trunk().ok())
+        .or_else(|| git::detect_trunk(&config.remote).ok())
         .unwrap_or_else(|| "main".into());
 
     let mut config = ProjectConfig::default();
@@ -52,21 +57,158 @@ pub fn run(args: InitArgs) -> Result<()> {
         config.general.copy_files = args.copy_files;
     }
 
-    let content = toml::to_string_pretty(&config).map_err(|e| Error::Other(e.to_string()))?;
+    let content = if args.minimal {
+        toml::to_string_pretty(&config).map_err(|e| Error::Other(e.to_string()))?
+    } else {
+        template(&config.general)?
+    };
 
     std::fs::write(config_path, content).map_err(|e| Error::Other(e.to_string()))?;
 
-    eprintln!("Created .agent-worktree.toml");
-    eprintln!("Trunk branch: {trunk}");
+    progress!("Created .agent-worktree.toml");
+    progress!("Trunk branch: {trunk}");
     if let Some(ref strategy) = config.general.merge_strategy {
-        eprintln!("Merge strategy: {strategy:?}");
+        progress!("Merge strategy: {strategy:?}");
     }
     if let Some(ref strategy) = config.general.sync_strategy {
-        eprintln!("Sync strategy: {strategy:?}");
+        progress!("Sync strategy: {strategy:?}");
     }
     if !config.general.copy_files.is_empty() {
-        eprintln!("Copy files: {}", config.general.copy_files.join(", "));
+        progress!("Copy files: {}", config.general.copy_files.join(", "));
     }
 
     Ok(())
 }
+
+/// Commented-out examples for every option not given on the command line.
+/// Each example is a whole `key = value` line (explanations trail it), so
+/// the file stays valid TOML with any of them uncommented.
+fn template(general: &ProjectGeneralConfig) -> Result<String> {
+    let mut out = String::from(
+        "# agent-worktree project config. Uncomment a line to enable it.\n\n[general]\n",
+    );
+    let entries = [
+        ("trunk", literal(&general.trunk)?, "\"main\""),
+        (
+            "merge_strategy",
+            literal(&general.merge_strategy)?,
+            "\"squash\"  # squash | merge | ff-only",
+        ),
+        (
+            "sync_strategy",
+            literal(&general.sync_strategy)?,
+            "\"rebase\"  # rebase | merge",
+        ),
+        (
+            "copy_files",
+            literal(&Some(&general.copy_files).filter(|c| !c.is_empty()))?,
+            "[\".env\", \".env.*\"]  # gitignore-style, copied into new worktrees",
+        ),
+        (
+            "copy_respect_gitignore",
+            None,
+            "false  # let broad patterns like \"*\" copy gitignored files",
+        ),
+        ("max_worktrees", None, "10"),
+        (
+            "min_free_gb",
+            None,
+            "5  # warn on wt new below this much free disk",
+        ),
+        ("branch_prefix", None, "\"agent/\""),
+        (
+            "default_snap_command",
+            None,
+            "\"@claude\"  # for a bare wt new -s",
+        ),
+        ("remote", None, "\"origin\""),
+    ];
+    for (key, set, example) in entries {
+        match set {
+            Some(v) => out.push_str(&format!("{key} = {v}\n")),
+            None => out.push_str(&format!("# {key} = {example}\n")),
+        }
+    }
+    out.push_str(
+        "\n[hooks]\n\
+         # post_create = [\"npm install\"]\n\
+         # pre_merge = [\"npm test\"]\n\
+         # post_merge = []\n\
+         # post_merge_strict = false  # fail wt merge when a post_merge hook fails\n\
+         # log_hooks = false  # also append hook output to <branch>.hooks.log\n\
+         # post_create_parallel = false  # run post_create hooks concurrently\n\
+         \n\
+         # [[trunk_rules]]  # branches matching pattern use this trunk\n\
+         # pattern = \"hotfix/*\"\n\
+         # trunk = \"release\"\n\
+         \n\
+         # [agent]  # presets for wt new -s @name\n\
+         # claude = \"claude\"\n",
+    );
+    Ok(out)
+}
+
+/// A set option as a TOML literal (`"main"`, `[".env"]`)
+fn literal<T: Serialize>(value: &Option<T>) -> Result<Option<String>> {
+    value
+        .as_ref()
+        .map(|v| toml::Value::try_from(v).map(|v| v.to_string()))
+        .transpose()
+        .map_err(|e| Error::Other(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every `# key = ...` / `# [table]` line, uncommented
+    fn uncomment_all(content: &str) -> String {
+        content
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(rest) if rest.starts_with('[') || rest.contains(" = ") => rest,
+                _ => line,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn template_is_valid_toml_commented_or_not() {
+        let general = ProjectGeneralConfig {
+            trunk: Some("develop".into()),
+            copy_files: vec![".env".into()],
+            ..Default::default()
+        };
+        let content = template(&general).unwrap();
+
+        let config: ProjectConfig = toml::from_str(&content).unwrap();
+        assert_eq!(config.general.trunk.as_deref(), Some("develop"));
+        assert_eq!(config.general.copy_files, [".env"]);
+        assert!(config.general.merge_strategy.is_none());
+
+        let config: ProjectConfig = toml::from_str(&uncomment_all(&content)).unwrap();
+        assert_eq!(config.general.trunk.as_deref(), Some("develop"));
+        assert_eq!(config.general.merge_strategy, Some(MergeStrategy::Squash));
+        assert_eq!(config.general.max_worktrees, Some(10));
+        assert_eq!(config.hooks.post_create, ["npm install"]);
+        assert_eq!(config.trunk_rules.len(), 1);
+        assert!(config.agent.contains_key("claude"));
+    }
+
+    #[test]
+    fn template_writes_given_options_uncommented() {
+        let general = ProjectGeneralConfig {
+            trunk: Some("main".into()),
+            sync_strategy: Some(SyncStrategy::Merge),
+            ..Default::default()
+        };
+        let content = template(&general).unwrap();
+        assert!(
+            content.contains("\nsync_strategy = \"merge\"\n"),
+            "{content}"
+        );
+        assert!(!content.contains("# sync_strategy"), "{content}");
+        assert!(content.contains("# copy_files = "), "{content}");
+    }
+}
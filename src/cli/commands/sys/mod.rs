@@ -4,3 +4,4 @@ pub mod update;
 
 pub use init::InitArgs;
 pub use setup::SetupArgs;
+pub use update::UpdateArgs;
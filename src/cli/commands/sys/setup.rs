@@ -14,6 +14,8 @@ pub enum ShellArg {
     Fish,
     #[value(alias = "pwsh")]
     Powershell,
+    #[value(alias = "nushell")]
+    Nu,
 }
 
 impl From<ShellArg> for Shell {
@@ -23,6 +25,7 @@ impl From<ShellArg> for Shell {
             ShellArg::Zsh => Shell::Zsh,
             ShellArg::Fish => Shell::Fish,
             ShellArg::Powershell => Shell::PowerShell,
+            ShellArg::Nu => Shell::Nu,
         }
     }
 }
@@ -48,13 +51,13 @@ pub fn run(args: SetupArgs) -> Result<()> {
 
     shell::install(shell).map_err(|e| Error::Other(e.to_string()))?;
 
-    eprintln!("Shell integration installed!");
-    eprintln!("Config: {}", config_path.display());
-    eprintln!();
-    eprintln!("Restart your shell or run:");
+    progress!("Shell integration installed!");
+    progress!("Config: {}", config_path.display());
+    progress!();
+    progress!("Restart your shell or run:");
     match shell {
-        Shell::PowerShell => eprintln!("  . {}", config_path.display()),
-        _ => eprintln!("  source {}", config_path.display()),
+        Shell::PowerShell => progress!("  . {}", config_path.display()),
+        _ => progress!("  source {}", config_path.display()),
     }
 
     Ok(())
@@ -88,6 +91,12 @@ mod tests {
         assert_eq!(shell, Shell::PowerShell);
     }
 
+    #[test]
+    fn test_shell_arg_to_shell_nu() {
+        let shell: Shell = ShellArg::Nu.into();
+        assert_eq!(shell, Shell::Nu);
+    }
+
     #[test]
     fn test_shell_arg_clone() {
         let arg = ShellArg::Bash;
@@ -2,11 +2,28 @@
 // cli/commands/update - Self-update Command
 // ===========================================================================
 
+use std::path::Path;
+
+use clap::Args;
+
 use crate::cli;
-use crate::update;
+use crate::config::Config;
+use crate::update::{self, UpdateChannel};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+#[derive(Args)]
+pub struct UpdateArgs {
+    /// Only report whether an update is available (exit 0 = up to date,
+    /// 2 = update available); never runs the installer
+    #[arg(long)]
+    check_only: bool,
+
+    /// Include pre-release versions (overrides `update_channel` in config)
+    #[arg(long)]
+    pre: bool,
+}
+
 /// 更新行为：纯逻辑，不涉及 IO
 #[derive(Debug)]
 pub enum UpdateAction {
@@ -25,33 +42,135 @@ pub fn determine_action(check_result: update::Result<Option<String>>) -> cli::Re
     }
 }
 
-/// 构造 npm install 命令参数
-pub fn npm_install_args() -> Vec<&'static str> {
-    vec!["install", "-g", "agent-worktree@latest"]
+/// `--check-only` 的结果：有新版本 → `Error::UpdateAvailable`（main 映射为退出码 2，1 留给错误）
+pub fn check_only_result(action: &UpdateAction) -> cli::Result<()> {
+    match action {
+        UpdateAction::AlreadyUpToDate => Ok(()),
+        UpdateAction::UpdateAvailable(latest) => Err(cli::Error::UpdateAvailable(format!(
+            "{VERSION} -> {latest}"
+        ))),
+    }
+}
+
+/// 安装来源：决定用哪个包管理器升级
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallMethod {
+    Npm,
+    Cargo,
+    Homebrew,
+}
+
+/// 升级命令：program + args
+#[derive(Debug, PartialEq, Eq)]
+pub struct UpdateCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl UpdateCommand {
+    fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    fn display(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// 根据二进制所在路径推断安装方式；无法识别时回退 npm（官方分发渠道）
+pub fn detect_install_method(exe: &Path) -> InstallMethod {
+    let path = exe.to_string_lossy().replace('\\', "/");
+    if path.contains("/.cargo/bin/") {
+        InstallMethod::Cargo
+    } else if path.contains("/Cellar/") || path.contains("/homebrew/") {
+        InstallMethod::Homebrew
+    } else {
+        InstallMethod::Npm
+    }
+}
+
+/// 解析升级命令：`WT_UPDATE_COMMAND`（非空）> 按安装方式推断
+///
+/// `pin` 固定安装版本（预发布版本不会被 `@latest` / 默认版本选中）；
+/// Homebrew 只发布正式版，忽略 pin。
+pub fn resolve_update_command(
+    override_cmd: Option<&str>,
+    exe: Option<&Path>,
+    pin: Option<&str>,
+) -> UpdateCommand {
+    if let Some(cmd) = override_cmd.filter(|c| !c.trim().is_empty()) {
+        let mut parts = cmd.split_whitespace();
+        let program = parts.next().unwrap_or_default();
+        return UpdateCommand::new(program, &parts.collect::<Vec<_>>());
+    }
+    match exe.map(detect_install_method).unwrap_or(InstallMethod::Npm) {
+        InstallMethod::Npm => {
+            let spec = format!("agent-worktree@{}", pin.unwrap_or("latest"));
+            UpdateCommand::new("npm", &["install", "-g", &spec])
+        }
+        InstallMethod::Cargo => match pin {
+            Some(v) => UpdateCommand::new("cargo", &["install", "agent-worktree", "--version", v]),
+            None => UpdateCommand::new("cargo", &["install", "agent-worktree"]),
+        },
+        InstallMethod::Homebrew => UpdateCommand::new("brew", &["upgrade", "agent-worktree"]),
+    }
 }
 
-pub fn run() -> cli::Result<()> {
-    eprintln!("Checking for updates...");
+pub fn run(args: UpdateArgs, config: &Config) -> cli::Result<()> {
+    let channel = if args.pre {
+        UpdateChannel::Prerelease
+    } else {
+        update::channel(&config.base_dir)
+    };
+    progress!("Checking for updates...");
+
+    let action = determine_action(update::check_update(VERSION, channel))?;
 
-    let action = determine_action(update::check_update(VERSION))?;
+    if args.check_only {
+        match &action {
+            UpdateAction::AlreadyUpToDate => println!("Up to date ({VERSION})"),
+            UpdateAction::UpdateAvailable(latest) => {
+                println!("Update available: {VERSION} -> {latest}")
+            }
+        }
+        return check_only_result(&action);
+    }
 
     match action {
         UpdateAction::AlreadyUpToDate => {
-            eprintln!("Already up to date ({})", VERSION);
+            progress!("Already up to date ({})", VERSION);
         }
         UpdateAction::UpdateAvailable(latest) => {
-            eprintln!("Updating agent-worktree: {} -> {}", VERSION, latest);
+            // Resolve symlinks (brew links bin/wt into the Cellar).
+            let exe = std::env::current_exe()
+                .ok()
+                .map(|p| p.canonicalize().unwrap_or(p));
+            let pin = (channel == UpdateChannel::Prerelease).then_some(latest.as_str());
+            let cmd = resolve_update_command(
+                std::env::var("WT_UPDATE_COMMAND").ok().as_deref(),
+                exe.as_deref(),
+                pin,
+            );
 
-            let status = std::process::Command::new("npm")
-                .args(npm_install_args())
+            progress!("Updating agent-worktree: {} -> {}", VERSION, latest);
+            progress!("Running: {}", cmd.display());
+
+            let status = std::process::Command::new(&cmd.program)
+                .args(&cmd.args)
                 .status()
-                .map_err(|e| cli::Error::Other(format!("failed to run npm: {e}")))?;
+                .map_err(|e| cli::Error::Other(format!("failed to run {}: {e}", cmd.program)))?;
 
             if !status.success() {
-                return Err(cli::Error::Other("npm install failed".into()));
+                return Err(cli::Error::Other(format!("'{}' failed", cmd.display())));
             }
 
-            eprintln!("Updated successfully!");
+            progress!("Updated successfully!");
         }
     }
 
@@ -88,8 +207,92 @@ mod tests {
     }
 
     #[test]
-    fn test_npm_install_args() {
-        let args = npm_install_args();
-        assert_eq!(args, vec!["install", "-g", "agent-worktree@latest"]);
+    fn test_check_only_exit_code() {
+        assert!(check_only_result(&UpdateAction::AlreadyUpToDate).is_ok());
+        let err = check_only_result(&UpdateAction::UpdateAvailable("9.9.9".into())).unwrap_err();
+        assert_eq!(err.exit_code(), cli::EXIT_UPDATE_AVAILABLE);
+        assert_ne!(
+            err.exit_code(),
+            cli::EXIT_FAILURE,
+            "1 is reserved for errors"
+        );
+    }
+
+    #[test]
+    fn test_detect_install_method() {
+        let detect = |p: &str| detect_install_method(Path::new(p));
+        assert_eq!(detect("/home/u/.cargo/bin/wt"), InstallMethod::Cargo);
+        assert_eq!(
+            detect("/opt/homebrew/Cellar/agent-worktree/0.11.1/bin/wt"),
+            InstallMethod::Homebrew
+        );
+        assert_eq!(
+            detect("/home/linuxbrew/.linuxbrew/Cellar/agent-worktree/0.11.1/bin/wt"),
+            InstallMethod::Homebrew
+        );
+        assert_eq!(
+            detect("/usr/local/lib/node_modules/agent-worktree/bin/wt"),
+            InstallMethod::Npm
+        );
+        assert_eq!(
+            detect("C:\\Users\\u\\.cargo\\bin\\wt.exe"),
+            InstallMethod::Cargo
+        );
+        assert_eq!(detect("/usr/bin/wt"), InstallMethod::Npm);
+    }
+
+    #[test]
+    fn test_resolve_update_command_by_method() {
+        let npm = resolve_update_command(None, None, None);
+        assert_eq!(npm.program, "npm");
+        assert_eq!(npm.args, vec!["install", "-g", "agent-worktree@latest"]);
+
+        let cargo = resolve_update_command(None, Some(Path::new("/home/u/.cargo/bin/wt")), None);
+        assert_eq!(cargo.display(), "cargo install agent-worktree");
+
+        let brew = resolve_update_command(
+            None,
+            Some(Path::new("/usr/local/Cellar/agent-worktree/1.0.0/bin/wt")),
+            None,
+        );
+        assert_eq!(brew.display(), "brew upgrade agent-worktree");
+    }
+
+    #[test]
+    fn test_resolve_update_command_env_override() {
+        let cmd = resolve_update_command(
+            Some("pnpm add -g agent-worktree"),
+            Some(Path::new("/home/u/.cargo/bin/wt")),
+            Some("1.0.0-rc.1"),
+        );
+        assert_eq!(cmd.program, "pnpm");
+        assert_eq!(cmd.args, vec!["add", "-g", "agent-worktree"]);
+
+        // Blank override is ignored
+        let cmd = resolve_update_command(Some("  "), None, None);
+        assert_eq!(cmd.program, "npm");
+    }
+
+    #[test]
+    fn test_resolve_update_command_pinned_prerelease() {
+        let npm = resolve_update_command(None, None, Some("1.0.0-rc.1"));
+        assert_eq!(npm.display(), "npm install -g agent-worktree@1.0.0-rc.1");
+
+        let cargo = resolve_update_command(
+            None,
+            Some(Path::new("/home/u/.cargo/bin/wt")),
+            Some("1.0.0-rc.1"),
+        );
+        assert_eq!(
+            cargo.display(),
+            "cargo install agent-worktree --version 1.0.0-rc.1"
+        );
+
+        let brew = resolve_update_command(
+            None,
+            Some(Path::new("/opt/homebrew/bin/wt")),
+            Some("1.0.0-rc.1"),
+        );
+        assert_eq!(brew.display(), "brew upgrade agent-worktree");
     }
 }
@@ -2,34 +2,102 @@
 // cli - Command Line Interface
 // ===========================================================================
 
-mod commands;
+pub(crate) mod commands;
 
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 use clap::{CommandFactory, Parser, Subcommand};
 
 use crate::config::Config;
 
-/// Write path to file for shell integration
-pub fn write_path_file(path_file: Option<&Path>, path: &Path) -> Result<()> {
+/// Tell the shell wrapper not to cd: every wrapper skips a missing path
+/// file, whereas an empty one would be a `cd ""` (fish: `cd` to $HOME).
+pub fn clear_path_file(path_file: Option<&Path>) {
     if let Some(file) = path_file {
-        std::fs::write(file, path.display().to_string())
-            .map_err(|e| Error::Other(format!("failed to write path file: {}", e)))?;
+        std::fs::remove_file(file).ok();
     }
-    Ok(())
+}
+
+/// Write path to file for shell integration
+pub fn write_path_file(path_file: Option<&Path>, path: &Path) -> Result<()> {
+    write_path_file_lines(path_file, &[&path.display().to_string()])
 }
 
 /// Write multiple lines to path file (for snap mode)
+///
+/// The path file is only the hand-off to the shell wrapper; by the time we
+/// write it the real work is done. A write failure (full or read-only
+/// TMPDIR) therefore warns with the target instead of failing a command
+/// that otherwise succeeded.
 pub fn write_path_file_lines(path_file: Option<&Path>, lines: &[&str]) -> Result<()> {
     if let Some(file) = path_file {
-        std::fs::write(file, lines.join("\n"))
-            .map_err(|e| Error::Other(format!("failed to write path file: {}", e)))?;
+        if let Err(e) = std::fs::write(file, lines.join("\n")) {
+            eprintln!("Warning: failed to write path file {}: {e}", file.display());
+            eprintln!("Target: {}", lines.first().copied().unwrap_or_default());
+            if let Some(cmd) = lines.get(1) {
+                eprintln!("Command: {cmd}");
+            }
+            eprintln!("cd there manually.");
+        }
     }
     Ok(())
 }
 
+/// `wt_dir/branch`, refusing names that land outside `wt_dir`: `..`
+/// climbing above it, absolute paths (which `join` would take as-is), or an
+/// existing path that resolves elsewhere through a symlink.
+pub fn worktree_path(wt_dir: &Path, branch: &str) -> Result<PathBuf> {
+    let outside = || Error::PathOutsideWorkspace(branch.to_string());
+
+    let mut depth = 0usize;
+    for component in Path::new(branch).components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => depth = depth.checked_sub(1).ok_or_else(outside)?,
+            Component::RootDir | Component::Prefix(_) => return Err(outside()),
+        }
+    }
+    if depth == 0 {
+        return Err(outside());
+    }
+
+    let path = wt_dir.join(branch);
+    if let (Ok(resolved), Ok(dir)) = (path.canonicalize(), wt_dir.canonicalize()) {
+        if !resolved.starts_with(&dir) || resolved == dir {
+            return Err(outside());
+        }
+    }
+    Ok(path)
+}
+
+/// Existing worktree for `branch`: the managed path under `wt_dir`, else
+/// wherever git has the branch checked out (e.g. a `git worktree add` by
+/// hand).
+pub fn locate_worktree(wt_dir: &Path, branch: &str) -> Result<PathBuf> {
+    let path = worktree_path(wt_dir, branch)?;
+    if path.exists() {
+        return Ok(path);
+    }
+    match crate::git::find_worktree_by_branch(branch)? {
+        Some(wt) if wt.path.is_dir() => Ok(wt.path),
+        _ => Err(Error::Git(crate::git::Error::WorktreeNotFound(
+            branch.to_string(),
+        ))),
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Process exit code for any failure without a more specific code
+pub const EXIT_FAILURE: i32 = 1;
+/// `wt merge` stopped on conflicts: needs manual resolution (`wt sync`),
+/// nothing was merged. Distinct from the snap-continue codes (0/2/3) and
+/// `wt update --check-only` (2).
+pub const EXIT_CONFLICT: i32 = 4;
+/// `wt update --check-only` found a newer version (0 = up to date)
+pub const EXIT_UPDATE_AVAILABLE: i32 = 2;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("config error: {0}")]
@@ -41,10 +109,58 @@ pub enum Error {
     #[error("not in a git repository")]
     NotInRepo,
 
+    #[error("Merge aborted due to conflicts")]
+    MergeConflict,
+
+    /// `wt update --check-only` found this version; not a failure, and
+    /// already reported on stdout
+    #[error("Update available: {0}")]
+    UpdateAvailable(String),
+
+    /// `wt merge` run on the branch that is its own merge target
+    #[error("Cannot merge {0} into itself")]
+    MergeIntoTrunk(String),
+
+    /// `wt sync` run on the branch that is its own sync target
+    #[error("Cannot sync {0} with itself")]
+    AlreadyOnTrunk(String),
+
+    /// Blocked by uncommitted changes; the field names where ("Main repo",
+    /// "Worktree 'x'", ...)
+    #[error("{0} has uncommitted changes. Commit or stash first.")]
+    UncommittedChanges(String),
+
+    /// `wt merge` blocked by uncommitted changes it could stash itself
+    #[error("{0} has uncommitted changes. Commit or stash first (or pass --autostash).")]
+    MergeBlockedByChanges(String),
+
+    /// Branch name whose worktree path would escape the workspace dir
+    #[error("Branch '{0}' resolves outside the workspace directory")]
+    PathOutsideWorkspace(String),
+
+    /// Current branch has no worktree under the workspaces dir
+    #[error("Not in a managed worktree (branch: {0})")]
+    WorktreeNotManaged(String),
+
     #[error("{0}")]
     Other(String),
 }
 
+impl Error {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::MergeConflict => EXIT_CONFLICT,
+            Error::UpdateAvailable(_) => EXIT_UPDATE_AVAILABLE,
+            _ => EXIT_FAILURE,
+        }
+    }
+
+    /// Only the exit code carries news; the command already printed it.
+    pub fn is_reported(&self) -> bool {
+        matches!(self, Error::UpdateAvailable(_))
+    }
+}
+
 #[derive(Parser)]
 #[command(
     name = "wt",
@@ -59,6 +175,18 @@ pub struct Cli {
     /// Write target path to file (for shell integration)
     #[arg(long, global = true, hide = true, value_name = "FILE")]
     path_file: Option<std::path::PathBuf>,
+
+    /// Answer yes to confirmation prompts (required when stdin is not a TTY)
+    #[arg(short = 'y', long, global = true)]
+    yes: bool,
+
+    /// Suppress progress messages on stderr (errors and warnings still print)
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Print each git command line to stderr before running it
+    #[arg(short, long, global = true)]
+    verbose: bool,
 }
 
 #[derive(Subcommand)]
@@ -72,6 +200,9 @@ enum Command {
     /// Switch to a worktree directory (no args = return to main repo)
     Cd(commands::CdArgs),
 
+    /// Open a worktree in your editor ($VISUAL / $EDITOR / config `editor`)
+    Open(commands::OpenArgs),
+
     /// Remove a worktree and its branch
     Rm(commands::RmArgs),
 
@@ -81,23 +212,47 @@ enum Command {
     /// Merge current worktree into trunk
     Merge(commands::MergeArgs),
 
+    /// Show a worktree's diff against its merge target
+    Diff(commands::DiffArgs),
+
+    /// Show commits a worktree adds over its merge target
+    Log(commands::LogArgs),
+
+    /// Run a command in a temporary detached worktree, then remove it
+    Inspect(commands::InspectArgs),
+
     /// Show current worktree information
     Status,
 
+    /// Show disk usage of the workspaces directory
+    Df,
+
     /// Sync current worktree from trunk
     Sync(commands::SyncArgs),
 
     /// Rename a worktree branch
     Mv(commands::MoveArgs),
 
-    /// Install shell integration (bash/zsh/fish)
+    /// Rename the current worktree's branch
+    Rename(commands::RenameArgs),
+
+    /// Set or update a worktree's description
+    Describe(commands::DescribeArgs),
+
+    /// Install shell integration (bash/zsh/fish/powershell/nu)
     Setup(commands::SetupArgs),
 
     /// Create .agent-worktree.toml config file
     Init(commands::InitArgs),
 
     /// Update to the latest version
-    Update,
+    Update(commands::UpdateArgs),
+
+    /// Run an agent in snap mode inside an existing worktree
+    Snap(commands::SnapArgs),
+
+    /// Abort an orphaned snap session (keeps the worktree unless --rm)
+    SnapAbort(commands::SnapAbortArgs),
 
     /// Continue snap mode after agent exits (internal use)
     #[command(hide = true)]
@@ -111,22 +266,38 @@ pub fn build_command() -> clap::Command {
 
 impl Cli {
     pub fn run(self) -> Result<()> {
+        // Before Config::load: finding the project config already runs git.
+        crate::util::set_quiet(self.quiet);
+        crate::util::set_verbose(self.verbose);
+        crate::git::set_run_cache(true);
         let config = Config::load()?;
         let path_file = self.path_file.as_deref();
+        crate::prompt::set_assume_yes(self.yes);
 
         match self.command {
-            Command::New(args) => commands::lifecycle::new::run(args, &config, path_file),
+            Command::New(args) => {
+                commands::lifecycle::new::run(args, &config, path_file).map(|_| ())
+            }
             Command::Ls(args) => commands::ls::run(args, &config),
             Command::Cd(args) => commands::nav::cd::run(args, &config, path_file),
+            Command::Open(args) => commands::nav::open::run(args, &config),
             Command::Rm(args) => commands::lifecycle::rm::run(args, &config, path_file),
             Command::Clean(args) => commands::lifecycle::clean::run(args, &config, path_file),
-            Command::Merge(args) => commands::merge::run(args, &config, path_file),
+            Command::Merge(args) => commands::merge::run(args, &config, path_file)?.into_result(),
+            Command::Diff(args) => commands::diff::run(args, &config),
+            Command::Log(args) => commands::log::run(args, &config),
+            Command::Inspect(args) => commands::inspect::run(args, &config),
             Command::Status => commands::status::run(&config),
+            Command::Df => commands::df::run(&config),
             Command::Sync(args) => commands::sync::run(args, &config),
             Command::Mv(args) => commands::r#move::run(args, &config, path_file),
+            Command::Rename(args) => commands::r#move::run_current(args, &config, path_file),
+            Command::Describe(args) => commands::describe::run(args, &config),
             Command::Setup(args) => commands::sys::setup::run(args),
-            Command::Init(args) => commands::sys::init::run(args),
-            Command::Update => commands::sys::update::run(),
+            Command::Init(args) => commands::sys::init::run(args, &config),
+            Command::Update(args) => commands::sys::update::run(args, &config),
+            Command::Snap(args) => commands::snap::start::run(args, &config, path_file),
+            Command::SnapAbort(args) => commands::snap::abort::run(args, &config, path_file),
             Command::SnapContinue => commands::snap::resume::run(&config, path_file),
         }
     }
@@ -145,6 +316,92 @@ mod tests {
         assert_eq!(err.to_string(), "custom error");
     }
 
+    #[test]
+    fn test_exit_code_distinguishes_conflicts() {
+        assert_eq!(Error::MergeConflict.exit_code(), EXIT_CONFLICT);
+        assert_eq!(Error::Other("x".into()).exit_code(), EXIT_FAILURE);
+        assert_eq!(Error::NotInRepo.exit_code(), EXIT_FAILURE);
+        let update = Error::UpdateAvailable("9.9.9".into());
+        assert_eq!(update.exit_code(), EXIT_UPDATE_AVAILABLE);
+        assert!(update.is_reported());
+        assert!(!Error::MergeConflict.is_reported());
+    }
+
+    #[test]
+    fn test_typed_error_messages() {
+        assert_eq!(
+            Error::MergeIntoTrunk("main".into()).to_string(),
+            "Cannot merge main into itself"
+        );
+        assert_eq!(
+            Error::AlreadyOnTrunk("main".into()).to_string(),
+            "Cannot sync main with itself"
+        );
+        assert_eq!(
+            Error::UncommittedChanges("Main repo".into()).to_string(),
+            "Main repo has uncommitted changes. Commit or stash first."
+        );
+        assert_eq!(
+            Error::MergeBlockedByChanges("Main repo".into()).to_string(),
+            "Main repo has uncommitted changes. Commit or stash first (or pass --autostash)."
+        );
+        assert_eq!(
+            Error::WorktreeNotManaged("main".into()).to_string(),
+            "Not in a managed worktree (branch: main)"
+        );
+    }
+
+    #[test]
+    fn test_worktree_path_stays_inside_wt_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let wt_dir = dir.path().join("ws");
+        std::fs::create_dir_all(&wt_dir).unwrap();
+
+        assert_eq!(worktree_path(&wt_dir, "fox").unwrap(), wt_dir.join("fox"));
+        assert_eq!(
+            worktree_path(&wt_dir, "feat/login").unwrap(),
+            wt_dir.join("feat/login")
+        );
+        assert!(worktree_path(&wt_dir, "feat/../fox").is_ok());
+
+        for bad in ["", ".", "..", "../other", "feat/../../x", "/tmp/x"] {
+            let err = worktree_path(&wt_dir, bad).unwrap_err();
+            assert!(
+                matches!(err, Error::PathOutsideWorkspace(_)),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_worktree_path_rejects_symlink_escape() {
+        let dir = tempfile::tempdir().unwrap();
+        let wt_dir = dir.path().join("ws");
+        std::fs::create_dir_all(&wt_dir).unwrap();
+        std::os::unix::fs::symlink(dir.path(), wt_dir.join("link")).unwrap();
+
+        assert!(worktree_path(&wt_dir, "link").is_err());
+        assert!(worktree_path(&wt_dir, "link/ws/fox").is_ok());
+    }
+
+    #[test]
+    fn test_write_path_file_failure_is_not_fatal() {
+        let dir = tempfile::tempdir().unwrap();
+        let unwritable = dir.path().join("missing-dir").join("path");
+        let result = write_path_file(Some(&unwritable), Path::new("/some/target"));
+        assert!(result.is_ok());
+        assert!(!unwritable.exists());
+    }
+
+    #[test]
+    fn test_write_path_file_lines_writes_all_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("path");
+        write_path_file_lines(Some(&file), &["/wt", "claude"]).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "/wt\nclaude");
+    }
+
     #[test]
     fn test_cli_parse_help() {
         // Verify CLI can parse --help without panicking
@@ -170,6 +427,16 @@ mod tests {
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_cli_parse_global_yes() {
+        let cli = Cli::try_parse_from(["wt", "-y", "ls"]).unwrap();
+        assert!(cli.yes);
+        let cli = Cli::try_parse_from(["wt", "ls", "--yes"]).unwrap();
+        assert!(cli.yes);
+        let cli = Cli::try_parse_from(["wt", "ls"]).unwrap();
+        assert!(!cli.yes);
+    }
+
     #[test]
     fn test_cli_parse_ls() {
         let cli = Cli::try_parse_from(["wt", "ls"]);
@@ -188,6 +455,12 @@ mod tests {
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_cli_parse_ls_watch() {
+        assert!(Cli::try_parse_from(["wt", "ls", "--watch"]).is_ok());
+        assert!(Cli::try_parse_from(["wt", "ls", "-lw"]).is_ok());
+    }
+
     #[test]
     fn test_cli_parse_cd() {
         let cli = Cli::try_parse_from(["wt", "cd", "branch-name"]);
@@ -224,6 +497,13 @@ mod tests {
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_cli_parse_clean_merged_requires_force() {
+        assert!(Cli::try_parse_from(["wt", "clean", "--merged", "--force"]).is_ok());
+        assert!(Cli::try_parse_from(["wt", "clean", "--merged"]).is_err());
+        assert!(Cli::try_parse_from(["wt", "clean", "-f"]).is_err());
+    }
+
     #[test]
     fn test_cli_parse_merge() {
         let cli = Cli::try_parse_from(["wt", "merge"]);
@@ -242,6 +522,18 @@ mod tests {
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_cli_parse_diff() {
+        assert!(Cli::try_parse_from(["wt", "diff"]).is_ok());
+        assert!(Cli::try_parse_from(["wt", "diff", "feature", "--stat"]).is_ok());
+    }
+
+    #[test]
+    fn test_cli_parse_log() {
+        assert!(Cli::try_parse_from(["wt", "log"]).is_ok());
+        assert!(Cli::try_parse_from(["wt", "log", ".", "--graph"]).is_ok());
+    }
+
     #[test]
     fn test_cli_parse_sync() {
         let cli = Cli::try_parse_from(["wt", "sync"]);
@@ -260,6 +552,12 @@ mod tests {
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_cli_parse_rename() {
+        assert!(Cli::try_parse_from(["wt", "rename", "new"]).is_ok());
+        assert!(Cli::try_parse_from(["wt", "rename", "old", "new"]).is_err());
+    }
+
     #[test]
     fn test_cli_parse_setup() {
         let cli = Cli::try_parse_from(["wt", "setup"]);
@@ -335,12 +633,38 @@ mod tests {
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_cli_parse_update_check_only() {
+        let cli = Cli::try_parse_from(["wt", "update", "--check-only"]);
+        assert!(cli.is_ok());
+    }
+
+    #[test]
+    fn test_cli_parse_update_pre() {
+        let cli = Cli::try_parse_from(["wt", "update", "--pre", "--check-only"]);
+        assert!(cli.is_ok());
+    }
+
     #[test]
     fn test_cli_parse_snap_continue() {
         let cli = Cli::try_parse_from(["wt", "snap-continue"]);
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_cli_parse_snap() {
+        assert!(Cli::try_parse_from(["wt", "snap", "fox", "--", "claude", "-c"]).is_ok());
+        // Command defaults to the stored one; the branch is required
+        assert!(Cli::try_parse_from(["wt", "snap", "fox"]).is_ok());
+        assert!(Cli::try_parse_from(["wt", "snap"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_snap_abort() {
+        assert!(Cli::try_parse_from(["wt", "snap-abort"]).is_ok());
+        assert!(Cli::try_parse_from(["wt", "snap-abort", "fox", "--rm"]).is_ok());
+    }
+
     #[test]
     fn test_cli_snap_continue_is_hidden() {
         // snap-continue should not appear in help
@@ -2,6 +2,7 @@
 // config - Configuration Loading & Merging
 // ===========================================================================
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use directories::BaseDirs;
@@ -22,6 +23,12 @@ pub enum Error {
 
     #[error("home directory not found")]
     NoHome,
+
+    #[error("invalid trunk_rules pattern '{pattern}': {source}")]
+    InvalidPattern {
+        pattern: String,
+        source: globset::Error,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -48,6 +55,20 @@ pub struct ProjectConfig {
 
     #[serde(default)]
     pub hooks: HooksConfig,
+
+    #[serde(default)]
+    pub trunk_rules: Vec<TrunkRule>,
+
+    /// `[agent]`：具名 agent 命令预设，`wt new -s @name` 引用
+    #[serde(default)]
+    pub agent: BTreeMap<String, String>,
+}
+
+/// `[[trunk_rules]]`：分支名匹配 `pattern`（glob）时以 `trunk` 作为 trunk
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrunkRule {
+    pub pattern: String,
+    pub trunk: String,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -60,6 +81,33 @@ pub struct GeneralConfig {
 
     #[serde(default)]
     pub copy_files: Vec<String>,
+
+    /// Glob `copy_files` patterns skip gitignored paths (default: true)
+    pub copy_respect_gitignore: Option<bool>,
+
+    pub min_free_gb: Option<u64>,
+
+    /// Command used by `wt open` (overrides $VISUAL / $EDITOR)
+    pub editor: Option<String>,
+
+    /// Daily background update check (default: true)
+    pub check_updates: Option<bool>,
+
+    /// Release channel for update checks (default: stable)
+    pub update_channel: Option<UpdateChannel>,
+
+    /// Prepended to every branch `wt new` creates (e.g. "agent/")
+    pub branch_prefix: Option<String>,
+
+    /// git executable to run instead of `git` from PATH (`WT_GIT` wins)
+    pub git_binary: Option<String>,
+
+    /// Agent command for a bare `wt new -s` (may name an `[agent]` preset)
+    pub default_snap_command: Option<String>,
+
+    /// Primary remote: trunk detection (`<remote>/HEAD`), `wt new --pull`,
+    /// `wt merge --delete-remote` (default: origin)
+    pub remote: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -72,6 +120,18 @@ pub struct ProjectGeneralConfig {
 
     #[serde(default)]
     pub copy_files: Vec<String>,
+
+    pub copy_respect_gitignore: Option<bool>,
+
+    pub max_worktrees: Option<usize>,
+
+    pub min_free_gb: Option<u64>,
+
+    pub branch_prefix: Option<String>,
+
+    pub default_snap_command: Option<String>,
+
+    pub remote: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -84,6 +144,31 @@ pub struct HooksConfig {
 
     #[serde(default)]
     pub post_merge: Vec<String>,
+
+    /// Fail the command when a post_merge hook fails (default: warn only,
+    /// since the merge has already landed by then)
+    pub post_merge_strict: Option<bool>,
+
+    /// Also append hook output to `{wt_dir}/{branch}.hooks.log`
+    pub log_hooks: Option<bool>,
+
+    /// Run the post_create hooks concurrently (pre/post_merge stay in order)
+    pub post_create_parallel: Option<bool>,
+}
+
+impl HooksConfig {
+    pub fn is_post_merge_strict(&self) -> bool {
+        self.post_merge_strict.unwrap_or(false)
+    }
+}
+
+/// `stable` 只看 npm `latest` tag；`prerelease` 也接受 beta / rc 等预发布版本
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Prerelease,
 }
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
@@ -92,6 +177,9 @@ pub enum MergeStrategy {
     #[default]
     Squash,
     Merge,
+    /// `git merge --ff-only`: no new commit; refuses once the target diverged
+    #[serde(rename = "ff-only")]
+    FfOnly,
 }
 
 impl MergeStrategy {
@@ -119,8 +207,19 @@ pub struct Config {
     pub merge_strategy: MergeStrategy,
     pub sync_strategy: SyncStrategy,
     pub copy_files: Vec<String>,
+    /// `general.copy_respect_gitignore`, defaulting to true
+    pub copy_respect_gitignore: bool,
     pub hooks: HooksConfig,
     pub trunk: Option<String>,
+    pub max_worktrees: Option<usize>,
+    pub min_free_gb: Option<u64>,
+    pub editor: Option<String>,
+    pub trunk_rules: Vec<TrunkRule>,
+    pub agent_presets: BTreeMap<String, String>,
+    pub branch_prefix: Option<String>,
+    pub default_snap_command: Option<String>,
+    /// `general.remote`, defaulting to "origin"
+    pub remote: String,
 }
 
 impl Config {
@@ -130,10 +229,16 @@ impl Config {
         // Canonicalize base_dir 解决 macOS /var -> /private/var symlink，
         // 确保与 git worktree list 返回的 canonicalized 路径一致
         let base_dir = base_dir.canonicalize().unwrap_or(base_dir);
-        let workspaces_dir = base_dir.join("workspaces");
+        let workspaces_dir = Self::resolve_workspaces_dir(
+            &base_dir,
+            std::env::var("WT_WORKSPACES_DIR").ok().as_deref(),
+        );
 
         let global = Self::load_global(&base_dir)?;
+        // Before load_project: finding the repo root already runs git.
+        crate::git::set_binary(global.general.git_binary.as_deref());
         let project = Self::load_project()?;
+        validate_trunk_rules(&project.trunk_rules)?;
 
         // Merge: project overrides global
         let merge_strategy = project
@@ -151,6 +256,15 @@ impl Config {
             post_create: merge_hooks(&global.hooks.post_create, &project.hooks.post_create),
             pre_merge: merge_hooks(&global.hooks.pre_merge, &project.hooks.pre_merge),
             post_merge: merge_hooks(&global.hooks.post_merge, &project.hooks.post_merge),
+            post_merge_strict: project
+                .hooks
+                .post_merge_strict
+                .or(global.hooks.post_merge_strict),
+            log_hooks: project.hooks.log_hooks.or(global.hooks.log_hooks),
+            post_create_parallel: project
+                .hooks
+                .post_create_parallel
+                .or(global.hooks.post_create_parallel),
         };
 
         Ok(Self {
@@ -159,20 +273,91 @@ impl Config {
             merge_strategy,
             sync_strategy,
             copy_files,
+            copy_respect_gitignore: project
+                .general
+                .copy_respect_gitignore
+                .or(global.general.copy_respect_gitignore)
+                .unwrap_or(true),
             hooks,
             trunk: project.general.trunk,
+            max_worktrees: project.general.max_worktrees,
+            min_free_gb: project.general.min_free_gb.or(global.general.min_free_gb),
+            editor: global.general.editor,
+            trunk_rules: project.trunk_rules,
+            agent_presets: project.agent,
+            branch_prefix: project
+                .general
+                .branch_prefix
+                .or(global.general.branch_prefix)
+                .filter(|p| !p.is_empty()),
+            default_snap_command: project
+                .general
+                .default_snap_command
+                .or(global.general.default_snap_command)
+                .filter(|c| !c.trim().is_empty()),
+            remote: project
+                .general
+                .remote
+                .or(global.general.remote)
+                .filter(|r| !r.is_empty())
+                .unwrap_or_else(|| "origin".to_string()),
         })
     }
 
     /// 解析 trunk 分支：配置 > 自动检测 > 默认 "main"
     pub fn resolve_trunk(&self) -> String {
-        self.trunk
-            .clone()
-            .unwrap_or_else(|| crate::git::detect_trunk().unwrap_or_else(|_| "main".into()))
+        self.trunk.clone().unwrap_or_else(|| {
+            crate::git::detect_trunk(&self.remote).unwrap_or_else(|_| "main".into())
+        })
+    }
+
+    /// 按分支解析 trunk：首个匹配的 trunk_rules > resolve_trunk()
+    pub fn resolve_trunk_for(&self, branch: &str) -> String {
+        match_trunk_rule(&self.trunk_rules, branch)
+            .map(str::to_string)
+            .unwrap_or_else(|| self.resolve_trunk())
+    }
+
+    /// `{workspaces_dir}/{workspace_id}` of the repo containing the cwd:
+    /// its managed worktrees and their meta files.
+    pub fn wt_dir(&self) -> crate::git::Result<PathBuf> {
+        Ok(self.workspaces_dir.join(crate::git::workspace_id()?))
+    }
+
+    /// Managed path for `branch`'s worktree, refusing names that escape
+    /// `wt_dir` (see `cli::worktree_path`). Use when creating one.
+    pub fn worktree_path(&self, branch: &str) -> crate::cli::Result<PathBuf> {
+        crate::cli::worktree_path(&self.wt_dir()?, branch)
+    }
+
+    /// Existing worktree for `branch`: the managed path, else wherever git
+    /// has the branch checked out (see `cli::locate_worktree`).
+    pub fn locate_worktree(&self, branch: &str) -> crate::cli::Result<PathBuf> {
+        crate::cli::locate_worktree(&self.wt_dir()?, branch)
+    }
+
+    /// Where `branch`'s hook output goes under `hooks.log_hooks`; `None`
+    /// when logging is off (or outside a repo).
+    pub fn hooks_log(&self, branch: &str) -> Option<PathBuf> {
+        if !self.hooks.log_hooks.unwrap_or(false) {
+            return None;
+        }
+        let wt_dir = self.wt_dir().ok()?;
+        Some(crate::meta::hooks_log_path(&wt_dir, branch))
     }
 
+    /// `branch_prefix` + `name`, unless `name` already carries the prefix
+    pub fn prefixed_branch(&self, name: &str) -> String {
+        apply_branch_prefix(self.branch_prefix.as_deref(), name)
+    }
+
+    /// `AGENT_WORKTREE_DIR`, then `AGENT_WORKTREE_HOME`, then `~/.agent-worktree`
     pub fn base_dir() -> Result<PathBuf> {
-        Self::resolve_base_dir(std::env::var("AGENT_WORKTREE_DIR").ok().as_deref())
+        let env_override = ["AGENT_WORKTREE_DIR", "AGENT_WORKTREE_HOME"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|s| !s.is_empty());
+        Self::resolve_base_dir(env_override.as_deref())
     }
 
     // Split out so tests can exercise both env and fallback branches
@@ -185,7 +370,19 @@ impl Config {
         Ok(base.home_dir().join(".agent-worktree"))
     }
 
-    fn load_global(base_dir: &Path) -> Result<GlobalConfig> {
+    // Same split as resolve_base_dir. The override is canonicalized like
+    // base_dir so paths compare equal to `git worktree list` output.
+    fn resolve_workspaces_dir(base_dir: &Path, env_override: Option<&str>) -> PathBuf {
+        match env_override.filter(|s| !s.is_empty()) {
+            Some(dir) => {
+                let dir = PathBuf::from(dir);
+                dir.canonicalize().unwrap_or(dir)
+            }
+            None => base_dir.join("workspaces"),
+        }
+    }
+
+    pub(crate) fn load_global(base_dir: &Path) -> Result<GlobalConfig> {
         let path = base_dir.join("config.toml");
         if !path.exists() {
             return Ok(GlobalConfig::default());
@@ -195,24 +392,82 @@ impl Config {
     }
 
     fn load_project() -> Result<ProjectConfig> {
-        // Resolve from the main repo root (via git --git-common-dir) so the
+        // Bounded by the main repo root (via git --git-common-dir) so the
         // same `.agent-worktree.toml` applies whether the user is in the main
-        // repo, a worktree root, or any subdirectory of either. Reading
-        // CWD-relative would silently miss the file inside worktrees.
+        // repo, a worktree root, or any subdirectory of either. Inside the
+        // main repo the nearest file up from CWD wins; worktrees live outside
+        // it and always get the root file.
         // Outside any git repo, fall back to default — non-git commands
         // (setup/update) must still load.
-        let path = match crate::git::repo_root() {
-            Ok(root) => root.join(".agent-worktree.toml"),
+        let root = match crate::git::repo_root() {
+            Ok(root) => root,
             Err(_) => return Ok(ProjectConfig::default()),
         };
-        if !path.exists() {
+        let cwd = std::env::current_dir()
+            .ok()
+            .and_then(|d| d.canonicalize().ok());
+        let root = root.canonicalize().unwrap_or(root);
+        let Some(path) = find_project_config(cwd.as_deref(), &root) else {
             return Ok(ProjectConfig::default());
-        }
+        };
         let content = std::fs::read_to_string(&path)?;
         Ok(toml::from_str(&content)?)
     }
 }
 
+/// Nearest `.agent-worktree.toml` walking up from `start`, never above
+/// `root`. A `start` outside `root` (or unknown) only checks `root` itself.
+fn find_project_config(start: Option<&Path>, root: &Path) -> Option<PathBuf> {
+    let start = start.filter(|s| s.starts_with(root)).unwrap_or(root);
+    start
+        .ancestors()
+        .take_while(|dir| dir.starts_with(root))
+        .map(|dir| dir.join(".agent-worktree.toml"))
+        .find(|path| path.is_file())
+}
+
+/// Prefix is taken literally ("agent/" nests, "agent-" doesn't); a name
+/// that already starts with it is left alone so `wt new agent/x` works.
+pub fn apply_branch_prefix(prefix: Option<&str>, name: &str) -> String {
+    match prefix {
+        Some(p) if !name.starts_with(p) => format!("{p}{name}"),
+        _ => name.to_string(),
+    }
+}
+
+/// First rule whose pattern matches `branch` (patterns are validated on load;
+/// an invalid one never matches).
+pub fn match_trunk_rule<'a>(rules: &'a [TrunkRule], branch: &str) -> Option<&'a str> {
+    rules
+        .iter()
+        .find(|rule| {
+            globset::Glob::new(&rule.pattern)
+                .map(|g| g.compile_matcher().is_match(branch))
+                .unwrap_or(false)
+        })
+        .map(|rule| rule.trunk.as_str())
+}
+
+/// `@name` → the `[agent]` preset of that name. None for anything else
+/// (no `@`, or no such preset); callers then use the string literally.
+pub fn resolve_agent_preset<'a>(
+    presets: &'a BTreeMap<String, String>,
+    raw: &str,
+) -> Option<&'a str> {
+    let name = raw.strip_prefix('@')?;
+    presets.get(name).map(String::as_str)
+}
+
+fn validate_trunk_rules(rules: &[TrunkRule]) -> Result<()> {
+    for rule in rules {
+        globset::Glob::new(&rule.pattern).map_err(|source| Error::InvalidPattern {
+            pattern: rule.pattern.clone(),
+            source,
+        })?;
+    }
+    Ok(())
+}
+
 fn merge_hooks(global: &[String], project: &[String]) -> Vec<String> {
     if project.is_empty() {
         global.to_vec()
@@ -277,6 +532,54 @@ post_create = ["pnpm install"]
         assert_eq!(config.hooks.post_create, vec!["pnpm install"]);
     }
 
+    #[test]
+    fn test_project_config_parse_max_worktrees() {
+        let toml = r#"
+[general]
+max_worktrees = 8
+"#;
+        let config: ProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.general.max_worktrees, Some(8));
+
+        let config = ProjectConfig::default();
+        assert!(config.general.max_worktrees.is_none());
+    }
+
+    #[test]
+    fn test_config_parse_min_free_gb() {
+        let toml = r#"
+[general]
+min_free_gb = 20
+"#;
+        let global: GlobalConfig = toml::from_str(toml).unwrap();
+        assert_eq!(global.general.min_free_gb, Some(20));
+        let project: ProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(project.general.min_free_gb, Some(20));
+    }
+
+    #[test]
+    fn test_global_config_parse_editor() {
+        let toml = r#"
+[general]
+editor = "code --wait"
+"#;
+        let global: GlobalConfig = toml::from_str(toml).unwrap();
+        assert_eq!(global.general.editor.as_deref(), Some("code --wait"));
+    }
+
+    #[test]
+    fn test_global_config_parse_git_binary() {
+        let toml = r#"
+[general]
+git_binary = "/opt/homebrew/bin/git"
+"#;
+        let global: GlobalConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            global.general.git_binary.as_deref(),
+            Some("/opt/homebrew/bin/git")
+        );
+    }
+
     #[test]
     fn test_merge_hooks_empty_project() {
         let global = vec!["global-hook".to_string()];
@@ -307,6 +610,16 @@ merge_strategy = "squash"
     // Additional tests for better coverage
     // =========================================================================
 
+    #[test]
+    fn test_merge_strategy_ff_only() {
+        let config: GlobalConfig =
+            toml::from_str("[general]\nmerge_strategy = \"ff-only\"\n").unwrap();
+        assert_eq!(config.general.merge_strategy, MergeStrategy::FfOnly);
+        assert!(!MergeStrategy::FfOnly.is_squash());
+        let out = toml::to_string(&config).unwrap();
+        assert!(out.contains("merge_strategy = \"ff-only\""), "{out}");
+    }
+
     #[test]
     fn test_merge_strategy_merge() {
         let toml = r#"[general]
@@ -354,6 +667,19 @@ post_merge = ["git push", "notify-team"]
         assert_eq!(config.hooks.post_merge, vec!["git push", "notify-team"]);
     }
 
+    #[test]
+    fn test_hooks_post_merge_strict() {
+        let hooks = HooksConfig::default();
+        assert!(!hooks.is_post_merge_strict());
+
+        let toml = r#"
+[hooks]
+post_merge_strict = true
+"#;
+        let config: ProjectConfig = toml::from_str(toml).unwrap();
+        assert!(config.hooks.is_post_merge_strict());
+    }
+
     #[test]
     fn test_project_general_config_defaults() {
         let general = ProjectGeneralConfig::default();
@@ -389,6 +715,25 @@ post_merge = ["git push", "notify-team"]
         assert!(path.to_string_lossy().contains(".agent-worktree"));
     }
 
+    #[test]
+    fn test_resolve_workspaces_dir_with_env() {
+        let path = Config::resolve_workspaces_dir(Path::new("/base"), Some("/tmp/custom-ws"));
+        assert_eq!(path, PathBuf::from("/tmp/custom-ws"));
+    }
+
+    #[test]
+    fn test_resolve_workspaces_dir_defaults_under_base() {
+        let base = Path::new("/base");
+        assert_eq!(
+            Config::resolve_workspaces_dir(base, None),
+            PathBuf::from("/base/workspaces")
+        );
+        assert_eq!(
+            Config::resolve_workspaces_dir(base, Some("")),
+            PathBuf::from("/base/workspaces")
+        );
+    }
+
     #[test]
     fn test_error_display() {
         let err = Error::NoHome;
@@ -402,11 +747,23 @@ post_merge = ["git push", "notify-team"]
                 merge_strategy: MergeStrategy::Merge,
                 sync_strategy: SyncStrategy::default(),
                 copy_files: vec![".env".to_string()],
+                copy_respect_gitignore: None,
+                min_free_gb: None,
+                editor: None,
+                check_updates: None,
+                update_channel: None,
+                branch_prefix: None,
+                git_binary: None,
+                default_snap_command: None,
+                remote: None,
             },
             hooks: HooksConfig {
                 post_create: vec!["npm install".to_string()],
                 pre_merge: vec![],
                 post_merge: vec![],
+                post_merge_strict: None,
+                log_hooks: None,
+                post_create_parallel: None,
             },
         };
         let serialized = toml::to_string(&config).unwrap();
@@ -473,14 +830,221 @@ trunk = "develop"
                 merge_strategy: None,
                 sync_strategy: None,
                 copy_files: vec![".env.local".to_string()],
+                copy_respect_gitignore: None,
+                max_worktrees: None,
+                min_free_gb: None,
+                branch_prefix: None,
+                default_snap_command: None,
+                remote: None,
             },
             hooks: HooksConfig::default(),
+            trunk_rules: vec![],
+            agent: BTreeMap::new(),
         };
         let serialized = toml::to_string(&config).unwrap();
         assert!(serialized.contains("develop"));
         assert!(serialized.contains(".env.local"));
     }
 
+    #[test]
+    fn test_project_trunk_rules_parse() {
+        let toml = r#"
+[[trunk_rules]]
+pattern = "hotfix/*"
+trunk = "release"
+
+[[trunk_rules]]
+pattern = "feature/*"
+trunk = "main"
+"#;
+        let config: ProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.trunk_rules.len(), 2);
+        assert_eq!(config.trunk_rules[0].pattern, "hotfix/*");
+        assert_eq!(config.trunk_rules[0].trunk, "release");
+    }
+
+    fn rule(pattern: &str, trunk: &str) -> TrunkRule {
+        TrunkRule {
+            pattern: pattern.into(),
+            trunk: trunk.into(),
+        }
+    }
+
+    #[test]
+    fn test_match_trunk_rule_first_match_wins() {
+        let rules = vec![
+            rule("hotfix/*", "release"),
+            rule("hotfix/urgent-*", "main"),
+            rule("feature/*", "develop"),
+        ];
+        assert_eq!(match_trunk_rule(&rules, "hotfix/urgent-1"), Some("release"));
+        assert_eq!(match_trunk_rule(&rules, "feature/login"), Some("develop"));
+    }
+
+    #[test]
+    fn test_match_trunk_rule_no_match_falls_back() {
+        let rules = vec![rule("hotfix/*", "release")];
+        assert_eq!(match_trunk_rule(&rules, "feature/login"), None);
+        assert_eq!(match_trunk_rule(&rules, "hotfix"), None);
+        assert_eq!(match_trunk_rule(&[], "hotfix/x"), None);
+    }
+
+    #[test]
+    fn test_resolve_trunk_for_uses_rules_then_trunk() {
+        let config = Config {
+            base_dir: PathBuf::from("/tmp"),
+            workspaces_dir: PathBuf::from("/tmp/workspaces"),
+            merge_strategy: MergeStrategy::default(),
+            sync_strategy: SyncStrategy::default(),
+            copy_files: vec![],
+            copy_respect_gitignore: true,
+            hooks: HooksConfig::default(),
+            trunk: Some("main".into()),
+            max_worktrees: None,
+            min_free_gb: None,
+            editor: None,
+            trunk_rules: vec![rule("hotfix/*", "release")],
+            agent_presets: BTreeMap::new(),
+            branch_prefix: None,
+            default_snap_command: None,
+            remote: "origin".into(),
+        };
+        assert_eq!(config.resolve_trunk_for("hotfix/crash"), "release");
+        assert_eq!(config.resolve_trunk_for("feature/x"), "main");
+    }
+
+    #[test]
+    fn test_validate_trunk_rules_rejects_bad_glob() {
+        assert!(validate_trunk_rules(&[rule("hotfix/*", "release")]).is_ok());
+        let err = validate_trunk_rules(&[rule("hotfix/[", "release")]).unwrap_err();
+        assert!(err.to_string().contains("hotfix/["));
+    }
+
+    #[test]
+    fn test_project_agent_presets_parse() {
+        let toml = r#"
+[agent]
+fast = "claude --model haiku"
+deep = "claude --model opus"
+"#;
+        let config: ProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.agent.len(), 2);
+        assert_eq!(config.agent["fast"], "claude --model haiku");
+
+        // Dotted keys at the top level are the same table
+        let toml = r#"agent.fast = "claude --model haiku""#;
+        let config: ProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.agent["fast"], "claude --model haiku");
+    }
+
+    #[test]
+    fn test_resolve_agent_preset_matched() {
+        let presets = BTreeMap::from([("fast".to_string(), "claude --model haiku".to_string())]);
+        assert_eq!(
+            resolve_agent_preset(&presets, "@fast"),
+            Some("claude --model haiku")
+        );
+    }
+
+    #[test]
+    fn test_resolve_agent_preset_unmatched_is_literal() {
+        let presets = BTreeMap::from([("fast".to_string(), "claude --model haiku".to_string())]);
+        assert_eq!(resolve_agent_preset(&presets, "@slow"), None);
+        assert_eq!(resolve_agent_preset(&presets, "fast"), None);
+        assert_eq!(resolve_agent_preset(&presets, "claude"), None);
+        assert_eq!(resolve_agent_preset(&BTreeMap::new(), "@fast"), None);
+    }
+
+    #[test]
+    fn test_apply_branch_prefix() {
+        assert_eq!(
+            apply_branch_prefix(Some("agent/"), "swift-fox"),
+            "agent/swift-fox"
+        );
+        assert_eq!(apply_branch_prefix(Some("agent/"), "agent/x"), "agent/x");
+        assert_eq!(apply_branch_prefix(Some("ai-"), "fix"), "ai-fix");
+        assert_eq!(apply_branch_prefix(None, "fix"), "fix");
+    }
+
+    #[test]
+    fn test_find_project_config_nearest_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let nested = root.join("a/b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join(".agent-worktree.toml"), "").unwrap();
+
+        assert_eq!(
+            find_project_config(Some(&nested), root),
+            Some(root.join(".agent-worktree.toml"))
+        );
+
+        std::fs::write(root.join("a/.agent-worktree.toml"), "").unwrap();
+        assert_eq!(
+            find_project_config(Some(&nested), root),
+            Some(root.join("a/.agent-worktree.toml"))
+        );
+    }
+
+    #[test]
+    fn test_find_project_config_stops_at_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        // Above the repo root: must be ignored
+        std::fs::write(dir.path().join(".agent-worktree.toml"), "").unwrap();
+
+        assert_eq!(find_project_config(Some(&root.join("sub")), &root), None);
+        // Outside the repo (e.g. a worktree): only the root is consulted
+        let outside = dir.path().join("elsewhere");
+        assert_eq!(find_project_config(Some(&outside), &root), None);
+        std::fs::write(root.join(".agent-worktree.toml"), "").unwrap();
+        assert_eq!(
+            find_project_config(Some(&outside), &root),
+            Some(root.join(".agent-worktree.toml"))
+        );
+    }
+
+    #[test]
+    fn test_project_branch_prefix_parse() {
+        let toml = r#"
+[general]
+branch_prefix = "agent/"
+"#;
+        let config: ProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.general.branch_prefix.as_deref(), Some("agent/"));
+    }
+
+    #[test]
+    fn test_default_snap_command_parse() {
+        let toml = r#"
+[general]
+default_snap_command = "@claude"
+"#;
+        let project: ProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            project.general.default_snap_command.as_deref(),
+            Some("@claude")
+        );
+        let global: GlobalConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            global.general.default_snap_command.as_deref(),
+            Some("@claude")
+        );
+    }
+
+    #[test]
+    fn test_remote_parse() {
+        let toml = r#"
+[general]
+remote = "upstream"
+"#;
+        let project: ProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(project.general.remote.as_deref(), Some("upstream"));
+        let global: GlobalConfig = toml::from_str(toml).unwrap();
+        assert_eq!(global.general.remote.as_deref(), Some("upstream"));
+    }
+
     #[test]
     fn test_merge_hooks_both_empty() {
         let global: Vec<String> = vec![];
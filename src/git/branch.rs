@@ -3,43 +3,20 @@
 // ===========================================================================
 
 use std::path::Path;
-use std::process::Command;
 
-use super::{run, Result};
+use super::{extract_error, git_command, run, Error, Result};
 
-/// Check if branch is merged into target
-pub fn is_merged(branch: &str, target: &str) -> Result<bool> {
-    let output = Command::new("git")
-        .args(["branch", "--merged", target])
-        .output()?;
-
-    if !output.status.success() {
-        return Ok(false);
-    }
-
-    let merged = String::from_utf8_lossy(&output.stdout);
-    Ok(merged
-        .lines()
-        .any(|l| l.trim().trim_start_matches("* ") == branch))
-}
-
-/// Check if a branch has any diff from target (commits or uncommitted changes)
+/// Whether `ancestor` is reachable from `descendant` (`git merge-base
+/// --is-ancestor`): a branch that is an ancestor of its target is merged.
 ///
-/// Returns true if branch has differences, false if identical to target.
-pub fn has_diff_from(branch: &str, target: &str) -> Result<bool> {
-    // Check committed diff: target...branch
-    let output = Command::new("git")
-        .args(["diff", "--quiet", &format!("{target}...{branch}")])
-        .output()?;
-
-    // exit 0 = no diff, exit 1 = has diff
-    if !output.status.success() {
-        return Ok(true);
+/// Unrelated histories are simply `false`; a bad ref is an error.
+pub fn is_ancestor(ancestor: &str, descendant: &str) -> Result<bool> {
+    let output = git_command(&["merge-base", "--is-ancestor", ancestor, descendant]).output()?;
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => Err(Error::Command(extract_error(&output))),
     }
-
-    // Also check if there are commits not in target
-    let count = commit_count(target, branch)?;
-    Ok(count > 0)
 }
 
 /// Delete a branch
@@ -48,24 +25,39 @@ pub fn delete_branch(name: &str, force: bool) -> Result<()> {
     run(&["branch", flag, name])
 }
 
+/// Delete a branch, running git from `repo` (safe when the cwd is a
+/// worktree that has just been removed)
+pub fn delete_branch_in(repo: &Path, name: &str, force: bool) -> Result<()> {
+    let flag = if force { "-D" } else { "-d" };
+    run(&["-C", super::path_str(repo)?, "branch", flag, name])
+}
+
+/// `git push <remote> --delete <branch>`. Returns false when the remote
+/// has no such branch (nothing to delete).
+pub fn delete_remote_branch(remote: &str, branch: &str) -> Result<bool> {
+    let output = git_command(&["push", remote, "--delete", branch]).output()?;
+    if output.status.success() {
+        return Ok(true);
+    }
+    let err = extract_error(&output);
+    if err.contains("remote ref does not exist") {
+        return Ok(false);
+    }
+    Err(Error::Command(err))
+}
+
 /// Check for uncommitted changes
 pub fn has_uncommitted_changes() -> Result<bool> {
-    let output = Command::new("git")
-        .args(["status", "--porcelain"])
-        .output()?;
-
-    Ok(!output.stdout.is_empty())
+    Ok(!status_porcelain(Path::new("."))?.is_empty())
 }
 
 /// Count uncommitted files in a specific worktree path
 ///
 /// Returns the number of lines from `git -C <path> status --porcelain`.
 pub fn uncommitted_count_in(path: &Path) -> Result<usize> {
-    let output = Command::new("git")
-        .args(["-C", super::path_str(path)?, "status", "--porcelain"])
-        .output()?;
+    let stdout = status_porcelain(path)?;
 
-    let count = String::from_utf8_lossy(&output.stdout)
+    let count = String::from_utf8_lossy(&stdout)
         .lines()
         .filter(|l| !l.is_empty())
         .count();
@@ -73,6 +65,29 @@ pub fn uncommitted_count_in(path: &Path) -> Result<usize> {
     Ok(count)
 }
 
+/// `git status --porcelain` output for the worktree at `path`.
+///
+/// Read-only on purpose: plain `status` refreshes the index and so takes
+/// `index.lock`, which can make a concurrent agent's `git commit` fail
+/// while `wt ls` polls. The untracked mode is pinned so a
+/// `status.showUntrackedFiles` setting can't change what counts as dirty.
+fn status_porcelain(path: &Path) -> Result<Vec<u8>> {
+    let output = git_command(&[
+        "--no-optional-locks",
+        "-C",
+        super::path_str(path)?,
+        "status",
+        "--porcelain=v1",
+        "--untracked-files=normal",
+    ])
+    .output()?;
+    // A failed status must not read as "nothing uncommitted".
+    if !output.status.success() {
+        return Err(Error::Command(extract_error(&output)));
+    }
+    Ok(output.stdout)
+}
+
 /// Diff stats: (insertions, deletions)
 pub struct DiffStat {
     pub insertions: usize,
@@ -84,18 +99,22 @@ pub struct DiffStat {
 /// Output format: " 3 files changed, 120 insertions(+), 30 deletions(-)"
 pub fn diff_shortstat(from: &str, to: &str) -> Result<DiffStat> {
     let range = format!("{from}...{to}");
-    let output = Command::new("git")
-        .args(["diff", "--shortstat", &range])
-        .output()?;
+    let output = git_command(&["diff", "--shortstat", &range]).output()?;
 
     Ok(parse_shortstat(&String::from_utf8_lossy(&output.stdout)))
 }
 
 /// Get diff --shortstat for uncommitted changes in a worktree
 pub fn diff_shortstat_in(path: &Path) -> Result<DiffStat> {
-    let output = Command::new("git")
-        .args(["-C", super::path_str(path)?, "diff", "--shortstat", "HEAD"])
-        .output()?;
+    let output = git_command(&[
+        "--no-optional-locks",
+        "-C",
+        super::path_str(path)?,
+        "diff",
+        "--shortstat",
+        "HEAD",
+    ])
+    .output()?;
 
     Ok(parse_shortstat(&String::from_utf8_lossy(&output.stdout)))
 }
@@ -142,26 +161,84 @@ pub(super) fn parse_shortstat(output: &str) -> DiffStat {
 /// - There are uncommitted changes in working directory, OR
 /// - Current branch has commits ahead of trunk
 pub fn has_changes_from_trunk(trunk: &str) -> Result<bool> {
-    // Check uncommitted changes first
-    if has_uncommitted_changes()? {
-        return Ok(true);
+    Ok(work_summary(trunk, Path::new("."))?.has_work())
+}
+
+/// What a worktree holds beyond its merge target
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkSummary {
+    /// `git status --porcelain` entries
+    pub uncommitted: usize,
+    /// Commits in `target..HEAD`
+    pub commits_ahead: usize,
+    /// Committed (`target...HEAD`) plus uncommitted lines
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl WorkSummary {
+    /// Anything that would be lost by removing the worktree
+    pub fn has_work(&self) -> bool {
+        self.uncommitted > 0 || self.commits_ahead > 0
     }
+}
 
-    // Check if there are commits ahead of trunk
-    let count = commit_count(trunk, "HEAD")?;
-    Ok(count > 0)
+/// Uncommitted entries, commits ahead of `target` and diff size for the
+/// worktree at `path`.
+///
+/// An unresolvable `target` is an error rather than "0 commits ahead":
+/// callers deciding whether a worktree is disposable must not read a
+/// failed lookup as "nothing to lose".
+pub fn work_summary(target: &str, path: &Path) -> Result<WorkSummary> {
+    let dir = super::path_str(path)?;
+    let uncommitted = uncommitted_count_in(path)?;
+
+    let output =
+        git_command(&["-C", dir, "rev-list", "--count", &format!("{target}..HEAD")]).output()?;
+    if !output.status.success() {
+        return Err(Error::Command(extract_error(&output)));
+    }
+    let commits_ahead = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .unwrap_or(0);
+
+    let output = git_command(&[
+        "--no-optional-locks",
+        "-C",
+        dir,
+        "diff",
+        "--shortstat",
+        &format!("{target}...HEAD"),
+    ])
+    .output()?;
+    let committed = parse_shortstat(&String::from_utf8_lossy(&output.stdout));
+    let unstaged = diff_shortstat_in(path)?;
+
+    Ok(WorkSummary {
+        uncommitted,
+        commits_ahead,
+        insertions: committed.insertions + unstaged.insertions,
+        deletions: committed.deletions + unstaged.deletions,
+    })
 }
 
 /// Check if there are staged changes ready to commit
 pub fn has_staged_changes() -> Result<bool> {
-    let output = Command::new("git")
-        .args(["diff", "--cached", "--quiet"])
-        .output()?;
+    let output = git_command(&["diff", "--cached", "--quiet"]).output()?;
 
     // exit code 0 = no diff, exit code 1 = has diff
     Ok(!output.status.success())
 }
 
+/// Check for staged changes in the worktree at `path`
+pub fn has_staged_changes_in(path: &Path) -> Result<bool> {
+    let output =
+        git_command(&["-C", super::path_str(path)?, "diff", "--cached", "--quiet"]).output()?;
+
+    Ok(!output.status.success())
+}
+
 /// Rename branch
 pub fn rename_branch(old: &str, new: &str) -> Result<()> {
     run(&["branch", "-m", old, new])
@@ -170,9 +247,7 @@ pub fn rename_branch(old: &str, new: &str) -> Result<()> {
 /// Get short log of commits between two refs
 pub fn log_oneline(from: &str, to: &str) -> Result<String> {
     let range = format!("{from}..{to}");
-    let output = Command::new("git")
-        .args(["log", "--oneline", &range])
-        .output()?;
+    let output = git_command(&["log", "--oneline", &range]).output()?;
 
     if !output.status.success() {
         return Ok(String::new());
@@ -184,9 +259,7 @@ pub fn log_oneline(from: &str, to: &str) -> Result<String> {
 /// Get commit count between two refs
 pub fn commit_count(from: &str, to: &str) -> Result<usize> {
     let range = format!("{from}..{to}");
-    let output = Command::new("git")
-        .args(["rev-list", "--count", &range])
-        .output()?;
+    let output = git_command(&["rev-list", "--count", &range]).output()?;
 
     if !output.status.success() {
         return Ok(0);
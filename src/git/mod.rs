@@ -14,6 +14,7 @@ pub use worktree::*;
 
 use std::path::Path;
 use std::process::Command;
+use std::sync::OnceLock;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -40,6 +41,12 @@ pub enum Error {
     #[error("branch '{0}' not found")]
     BranchNotFound(String),
 
+    #[error("'{0}' is not a valid branch name")]
+    InvalidBranchName(String),
+
+    #[error("HEAD is detached at {0}; check out a branch first")]
+    DetachedHead(String),
+
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -83,9 +90,64 @@ fn clean_git_error(stderr: &str) -> String {
     msg.to_string()
 }
 
+/// `git_binary` from the global config; set once at startup.
+static GIT_BINARY: OnceLock<String> = OnceLock::new();
+
+/// Use `path` instead of `git` from PATH (empty / None keeps the default).
+/// Only the first call wins.
+pub fn set_binary(path: Option<&str>) {
+    if let Some(path) = path.filter(|p| !p.is_empty()) {
+        let _ = GIT_BINARY.set(path.to_string());
+    }
+}
+
+/// Program to run: `WT_GIT` > configured `git_binary` > "git".
+fn program(env_override: Option<&str>, configured: Option<&str>) -> String {
+    env_override
+        .filter(|p| !p.is_empty())
+        .or(configured)
+        .unwrap_or("git")
+        .to_string()
+}
+
+/// `Command` for `git <args>`; every git invocation goes through here.
+/// With `wt -v` the command line is echoed to stderr first.
+pub fn git_command(args: &[&str]) -> Command {
+    if crate::util::is_verbose() {
+        eprintln!("+ {}", command_line(args));
+    }
+    let mut cmd = Command::new(program(
+        std::env::var("WT_GIT").ok().as_deref(),
+        GIT_BINARY.get().map(String::as_str),
+    ));
+    cmd.args(args);
+    cmd
+}
+
+/// `git <args>` as a copy-pasteable shell line (`-C <dir>` included, so the
+/// working directory is visible).
+fn command_line(args: &[&str]) -> String {
+    let mut line = String::from("git");
+    for arg in args {
+        line.push(' ');
+        if !arg.is_empty()
+            && arg
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./=:@^~+,%".contains(c))
+        {
+            line.push_str(arg);
+        } else {
+            line.push('\'');
+            line.push_str(&arg.replace('\'', r"'\''"));
+            line.push('\'');
+        }
+    }
+    line
+}
+
 /// 执行 git 命令，失败时从 stderr+stdout 提取错误信息
 fn run(args: &[&str]) -> Result<()> {
-    let output = Command::new("git").args(args).output()?;
+    let output = git_command(args).output()?;
     if !output.status.success() {
         return Err(Error::Command(extract_error(&output)));
     }
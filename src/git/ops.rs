@@ -2,12 +2,50 @@
 // git/ops - Git 执行操作
 // ===========================================================================
 
-use std::process::Command;
+use std::path::Path;
 
-use super::{run, Result};
+use super::{git_command, path_str, run, Error, Result};
 
 /// Run git merge
-pub fn merge(branch: &str, squash: bool, no_ff: bool, message: Option<&str>) -> Result<()> {
+///
+/// `no_verify` skips the repo's git hooks (`pre-merge-commit`, `commit-msg`).
+pub fn merge(
+    branch: &str,
+    squash: bool,
+    no_ff: bool,
+    message: Option<&str>,
+    no_verify: bool,
+) -> Result<()> {
+    run(&merge_args(branch, squash, no_ff, message, no_verify))
+}
+
+/// Run git merge inside the worktree at `path` (`git -C <path> merge ...`)
+pub fn merge_in(
+    path: &Path,
+    branch: &str,
+    squash: bool,
+    no_ff: bool,
+    message: Option<&str>,
+    no_verify: bool,
+) -> Result<()> {
+    let mut args = vec!["-C", path_str(path)?];
+    args.extend(merge_args(branch, squash, no_ff, message, no_verify));
+    run(&args)
+}
+
+/// `git -C <path> merge --ff-only <branch>`: moves the branch at `path` to
+/// `branch`, or fails if that isn't a fast-forward.
+pub fn merge_ff_only_in(path: &Path, branch: &str) -> Result<()> {
+    run(&["-C", path_str(path)?, "merge", "--ff-only", branch])
+}
+
+fn merge_args<'a>(
+    branch: &'a str,
+    squash: bool,
+    no_ff: bool,
+    message: Option<&'a str>,
+    no_verify: bool,
+) -> Vec<&'a str> {
     let mut args = vec!["merge"];
     if squash {
         args.push("--squash");
@@ -15,12 +53,15 @@ pub fn merge(branch: &str, squash: bool, no_ff: bool, message: Option<&str>) ->
     if no_ff {
         args.push("--no-ff");
     }
+    if no_verify {
+        args.push("--no-verify");
+    }
     if let Some(msg) = message {
         args.push("-m");
         args.push(msg);
     }
     args.push(branch);
-    run(&args)
+    args
 }
 
 /// Dry-run merge to check for conflicts without leaving state.
@@ -31,19 +72,65 @@ pub fn merge(branch: &str, squash: bool, no_ff: bool, message: Option<&str>) ->
 ///
 /// Returns Ok(true) if merge would be clean, Ok(false) on conflict.
 pub fn dry_run_merge(branch: &str, squash: bool) -> Result<bool> {
+    dry_run_merge_in(Path::new("."), branch, squash)
+}
+
+/// `dry_run_merge` inside the worktree at `path`
+pub fn dry_run_merge_in(path: &Path, branch: &str, squash: bool) -> Result<bool> {
+    Ok(dry_run_merge_conflicts_in(path, branch, squash)?.is_none())
+}
+
+/// Dry-run merge that also reports what conflicted.
+///
+/// Returns Ok(None) if the merge would be clean, Ok(Some(paths)) otherwise.
+/// `paths` is read before the abort and may be empty when git failed for a
+/// reason other than a content conflict.
+pub fn dry_run_merge_conflicts_in(
+    path: &Path,
+    branch: &str,
+    squash: bool,
+) -> Result<Option<Vec<String>>> {
+    let dir = path_str(path)?;
     let result = if squash {
-        run(&["merge", "--squash", "--no-commit", branch])
+        run(&["-C", dir, "merge", "--squash", "--no-commit", branch])
     } else {
-        run(&["merge", "--no-commit", "--no-ff", branch])
+        run(&["-C", dir, "merge", "--no-commit", "--no-ff", branch])
+    };
+    let conflicts = match result {
+        Ok(_) => None,
+        Err(_) => Some(conflicted_files_in(path).unwrap_or_default()),
     };
-    let clean = result.is_ok();
     if squash {
         // `git merge --squash` never sets MERGE_HEAD, so `--abort` errors.
-        let _ = run(&["reset", "--hard", "HEAD"]);
+        let _ = run(&["-C", dir, "reset", "--hard", "HEAD"]);
     } else {
-        let _ = run(&["merge", "--abort"]);
+        let _ = run(&["-C", dir, "merge", "--abort"]);
     }
-    Ok(clean)
+    Ok(conflicts)
+}
+
+/// Paths with unresolved conflicts in the current repo
+pub fn conflicted_files() -> Result<Vec<String>> {
+    conflicted_files_in(Path::new("."))
+}
+
+/// `conflicted_files` inside the worktree at `path`
+pub fn conflicted_files_in(path: &Path) -> Result<Vec<String>> {
+    let output = git_command(&[
+        "-C",
+        path_str(path)?,
+        "diff",
+        "--name-only",
+        "--diff-filter=U",
+    ])
+    .output()?;
+    if !output.status.success() {
+        return Err(Error::Command(super::extract_error(&output)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
 }
 
 /// Run git rebase
@@ -51,25 +138,122 @@ pub fn rebase(onto: &str) -> Result<()> {
     run(&["rebase", onto])
 }
 
+/// Run git rebase inside the worktree at `path` (`git -C <path> rebase <onto>`)
+pub fn rebase_in(path: &Path, onto: &str) -> Result<()> {
+    run(&["-C", path_str(path)?, "rebase", onto])
+}
+
+/// `git -C <path> rebase -i <onto>` with inherited stdio so the todo
+/// editor (`GIT_SEQUENCE_EDITOR` / `core.editor`) can take the terminal.
+pub fn rebase_interactive_in(path: &Path, onto: &str) -> Result<()> {
+    let status = git_command(&["-C", path_str(path)?, "rebase", "-i", onto]).status()?;
+    if !status.success() {
+        return Err(Error::Command(format!(
+            "git rebase -i {onto} exited with {status}"
+        )));
+    }
+    Ok(())
+}
+
 /// Checkout a branch
 pub fn checkout(branch: &str) -> Result<()> {
     run(&["checkout", branch])
 }
 
+/// Checkout a branch in the worktree at `path`
+pub fn checkout_in(path: &Path, branch: &str) -> Result<()> {
+    run(&["-C", path_str(path)?, "checkout", branch])
+}
+
 /// Commit staged changes
 pub fn commit(message: &str) -> Result<()> {
-    run(&["commit", "-m", message])
+    commit_with(message, None, None, false)
+}
+
+/// Commit staged changes in the worktree at `path`
+pub fn commit_in(path: &Path, message: &str) -> Result<()> {
+    commit_with_in(path, message, None, None, false)
+}
+
+/// Commit staged changes with optional author / date overrides.
+///
+/// `author` is passed as `--author` ("Name <email>"); `date` sets both
+/// GIT_AUTHOR_DATE and GIT_COMMITTER_DATE, so identical inputs yield an
+/// identical commit id. `no_verify` skips the repo's `pre-commit` /
+/// `commit-msg` git hooks.
+pub fn commit_with(
+    message: &str,
+    author: Option<&str>,
+    date: Option<&str>,
+    no_verify: bool,
+) -> Result<()> {
+    run_commit(None, message, author, date, no_verify)
+}
+
+/// `commit_with` inside the worktree at `path`
+pub fn commit_with_in(
+    path: &Path,
+    message: &str,
+    author: Option<&str>,
+    date: Option<&str>,
+    no_verify: bool,
+) -> Result<()> {
+    run_commit(Some(path_str(path)?), message, author, date, no_verify)
+}
+
+fn run_commit(
+    dir: Option<&str>,
+    message: &str,
+    author: Option<&str>,
+    date: Option<&str>,
+    no_verify: bool,
+) -> Result<()> {
+    let author = author.map(|a| format!("--author={a}"));
+    let mut args = Vec::new();
+    if let Some(dir) = dir {
+        args.extend(["-C", dir]);
+    }
+    args.extend(["commit", "-m", message]);
+    if no_verify {
+        args.push("--no-verify");
+    }
+    args.extend(author.as_deref());
+    let mut cmd = git_command(&args);
+    if let Some(date) = date {
+        cmd.env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date);
+    }
+    let output = cmd.output()?;
+    if !output.status.success() {
+        return Err(Error::Command(super::extract_error(&output)));
+    }
+    Ok(())
+}
+
+/// Outcome of a non-fatal `fetch`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchStatus {
+    Fetched,
+    /// git's reason (offline, unknown remote, ...), for a warning
+    Failed(String),
 }
 
-/// Fetch updates from remote
-pub fn fetch() -> Result<()> {
-    let output = Command::new("git").args(["fetch", "--quiet"]).output()?;
+/// Fetch updates from `remote` (`general.remote`). A failing fetch is not
+/// an error — callers go on with local refs — but it is reported, so they
+/// can say so instead of passing stale refs off as fresh.
+pub fn fetch(remote: &str) -> Result<FetchStatus> {
+    let output = git_command(&["fetch", "--quiet", remote]).output()?;
 
     if !output.status.success() {
-        // Fetch failing is often not critical, just warn
+        return Ok(FetchStatus::Failed(super::extract_error(&output)));
     }
 
-    Ok(())
+    Ok(FetchStatus::Fetched)
+}
+
+/// `git fetch <remote>`; unlike `fetch`, a failure is an error
+pub fn fetch_remote(remote: &str) -> Result<()> {
+    run(&["fetch", "--quiet", remote])
 }
 
 /// Abort an in-progress rebase
@@ -95,21 +279,112 @@ pub fn reset_merge() -> Result<()> {
     run(&["reset", "--merge"])
 }
 
+/// `reset_merge` inside the worktree at `path`
+pub fn reset_merge_in(path: &Path) -> Result<()> {
+    run(&["-C", path_str(path)?, "reset", "--merge"])
+}
+
 /// Continue an in-progress merge (after conflict resolution)
 pub fn merge_continue() -> Result<()> {
     run(&["commit", "--no-edit"])
 }
 
+/// Stash all changes (including untracked) in `dir`.
+///
+/// Returns the stash commit id, or None when there was nothing to stash.
+/// Callers pop by id rather than `stash@{0}`: the stash ref is shared by
+/// every worktree, so another stash may land on top in the meantime.
+pub fn stash_push(dir: &Path, message: &str) -> Result<Option<String>> {
+    let dir = path_str(dir)?;
+    let before = stash_top(dir);
+    run(&[
+        "-C",
+        dir,
+        "stash",
+        "push",
+        "--include-untracked",
+        "-m",
+        message,
+    ])?;
+    let after = stash_top(dir);
+    Ok(after.filter(|a| before.as_ref() != Some(a)))
+}
+
+/// Pop the stash entry with commit id `id` in `dir`.
+///
+/// On conflict git keeps the entry, so nothing is lost; the error tells
+/// the caller to surface the id.
+pub fn stash_pop(dir: &Path, id: &str) -> Result<()> {
+    let dir = path_str(dir)?;
+    let output = git_command(&["-C", dir, "stash", "list", "--format=%H"]).output()?;
+    let index = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .position(|l| l.trim() == id)
+        .ok_or_else(|| Error::Command(format!("stash {id} not found")))?;
+    run(&["-C", dir, "stash", "pop", &format!("stash@{{{index}}}")])
+}
+
+fn stash_top(dir: &str) -> Option<String> {
+    git_command(&["-C", dir, "rev-parse", "-q", "--verify", "refs/stash"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
 /// 获取 git 目录路径
 fn git_dir() -> Option<std::path::PathBuf> {
-    Command::new("git")
-        .args(["rev-parse", "--git-dir"])
+    git_command(&["rev-parse", "--git-dir"])
         .output()
         .ok()
         .filter(|o| o.status.success())
         .map(|o| std::path::PathBuf::from(String::from_utf8_lossy(&o.stdout).trim()))
 }
 
+/// Editor git would use for a commit message (`git var GIT_EDITOR`:
+/// GIT_EDITOR > core.editor > $VISUAL > $EDITOR > vi)
+pub fn editor() -> Result<String> {
+    let output = git_command(&["var", "GIT_EDITOR"]).output()?;
+    if !output.status.success() {
+        return Err(Error::Command(super::extract_error(&output)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Absolute per-worktree git dir of the worktree at `path`
+/// (`.git` for the main repo, `.git/worktrees/<name>` for linked ones).
+pub fn git_dir_in(path: &Path) -> Result<std::path::PathBuf> {
+    let output =
+        git_command(&["-C", path_str(path)?, "rev-parse", "--absolute-git-dir"]).output()?;
+    if !output.status.success() {
+        return Err(Error::Command(super::extract_error(&output)));
+    }
+    Ok(std::path::PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
+}
+
+/// No rebase / merge / cherry-pick / revert in progress and no unmerged
+/// paths in the worktree at `path`. Uncommitted changes don't count.
+pub fn is_worktree_clean(path: &Path) -> Result<bool> {
+    // Per-worktree dir (.git/worktrees/<name>), where the state files live
+    let dir = git_dir_in(path)?;
+    let in_progress = [
+        "rebase-merge",
+        "rebase-apply",
+        "MERGE_HEAD",
+        "CHERRY_PICK_HEAD",
+        "REVERT_HEAD",
+    ]
+    .iter()
+    .any(|f| dir.join(f).exists());
+    if in_progress {
+        return Ok(false);
+    }
+    // A conflicted `merge --squash` leaves no MERGE_HEAD, only unmerged paths
+    Ok(conflicted_files_in(path)?.is_empty())
+}
+
 /// Check if a rebase is in progress
 pub fn is_rebase_in_progress() -> bool {
     git_dir().is_some_and(|d| d.join("rebase-merge").exists() || d.join("rebase-apply").exists())
@@ -119,3 +394,14 @@ pub fn is_rebase_in_progress() -> bool {
 pub fn is_merge_in_progress() -> bool {
     git_dir().is_some_and(|d| d.join("MERGE_HEAD").exists())
 }
+
+/// `is_rebase_in_progress` for the worktree at `path`
+pub fn is_rebase_in_progress_in(path: &Path) -> bool {
+    git_dir_in(path)
+        .is_ok_and(|d| d.join("rebase-merge").exists() || d.join("rebase-apply").exists())
+}
+
+/// `is_merge_in_progress` for the worktree at `path`
+pub fn is_merge_in_progress_in(path: &Path) -> bool {
+    git_dir_in(path).is_ok_and(|d| d.join("MERGE_HEAD").exists())
+}
@@ -2,54 +2,79 @@
 // git/repo - 仓库信息查询
 // ===========================================================================
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::sync::Mutex;
 
-use super::{Error, Result};
+use super::{extract_error, git_command, path_str, Error, Result};
 
 /// Get the root directory of the main git repository (not worktree)
 ///
-/// Uses --git-common-dir to handle worktrees correctly.
+/// Uses --git-common-dir to handle worktrees correctly. A common dir that
+/// is not `<root>/.git` (`GIT_DIR=/elsewhere/repo.git`, `--separate-git-dir`)
+/// says nothing about where the work tree is, so git's `--show-toplevel`
+/// decides instead — which also honors `GIT_WORK_TREE` / `core.worktree`.
 pub fn repo_root() -> Result<PathBuf> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--git-common-dir"])
-        .output()?;
+    let output = git_command(&["rev-parse", "--git-common-dir", "--git-dir"]).output()?;
 
     if !output.status.success() {
         return Err(Error::NotInRepo);
     }
 
-    let git_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
-
-    // Convert to absolute path if relative
-    let git_dir = if git_dir.is_absolute() {
-        git_dir
-    } else {
-        std::env::current_dir()?.join(&git_dir)
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let (Some(common_dir), Some(git_dir)) = (lines.next(), lines.next()) else {
+        return Err(Error::NotInRepo);
     };
+    let common_dir = absolute_canonical(common_dir)?;
 
-    // Canonicalize to resolve symlinks
-    let git_dir = git_dir.canonicalize().map_err(|_| Error::NotInRepo)?;
+    // Standard layout, reached from the main worktree, a linked one
+    // (.git/worktrees/<name>), or a GIT_DIR pointing at either.
+    if common_dir.file_name().is_some_and(|n| n == ".git") {
+        return common_dir
+            .parent()
+            .map(|p| p.to_path_buf())
+            .ok_or(Error::NotInRepo);
+    }
 
-    // Find the .git directory and return its parent
-    let git_dir = if git_dir.ends_with(".git") {
-        git_dir
-    } else {
-        // e.g. /path/to/repo/.git/worktrees/branch -> find .git
-        let mut current = git_dir.as_path();
-        loop {
-            if current.ends_with(".git") {
-                break;
-            }
-            current = current.parent().ok_or(Error::NotInRepo)?;
+    // In the main worktree (or GIT_DIR at the common dir): git knows its
+    // top level.
+    if absolute_canonical(git_dir)? == common_dir {
+        let output = git_command(&["rev-parse", "--show-toplevel"]).output()?;
+        if !output.status.success() {
+            // Bare repository: no main work tree to anchor worktrees on
+            return Err(Error::NotInRepo);
         }
-        current.to_path_buf()
-    };
+        return absolute_canonical(String::from_utf8_lossy(&output.stdout).trim());
+    }
 
-    git_dir
-        .parent()
-        .map(|p| p.to_path_buf())
-        .ok_or(Error::NotInRepo)
+    // Linked worktree of such a repo: only core.worktree records the main
+    // work tree.
+    let output =
+        git_command(&["-C", path_str(&common_dir)?, "config", "core.worktree"]).output()?;
+    let configured = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || configured.is_empty() {
+        return Err(Error::Command(format!(
+            "cannot locate the main worktree of {}; run wt from the main worktree",
+            common_dir.display()
+        )));
+    }
+    common_dir
+        .join(configured)
+        .canonicalize()
+        .map_err(|_| Error::NotInRepo)
+}
+
+/// `git rev-parse` prints paths relative to the cwd; canonicalize so the
+/// root (and the workspace_id hashed from it) is stable across symlinks.
+fn absolute_canonical(path: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(path);
+    let path = if path.is_absolute() {
+        path
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+    path.canonicalize().map_err(|_| Error::NotInRepo)
 }
 
 /// Get the name of the current repository (directory name)
@@ -67,6 +92,10 @@ pub fn repo_name() -> Result<String> {
 /// This ensures repos with the same directory name but different paths get
 /// unique workspace directories.
 pub fn workspace_id() -> Result<String> {
+    cached("workspace_id".into(), compute_workspace_id)
+}
+
+fn compute_workspace_id() -> Result<String> {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
@@ -80,52 +109,150 @@ pub fn workspace_id() -> Result<String> {
     Ok(format!("{}-{:06x}", name, hash & 0xFFFFFF))
 }
 
-/// Get the current branch name
-pub fn current_branch() -> Result<String> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()?;
+/// What HEAD points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadState {
+    Branch(String),
+    /// Detached at this commit (short hash); also mid-rebase
+    Detached(String),
+}
 
-    if !output.status.success() {
+impl HeadState {
+    /// What `git checkout` needs to come back here: the branch, or the
+    /// commit for a detached HEAD.
+    pub fn checkout_ref(&self) -> &str {
+        match self {
+            HeadState::Branch(name) | HeadState::Detached(name) => name,
+        }
+    }
+}
+
+/// HEAD of the current worktree. Unlike `rev-parse --abbrev-ref HEAD`,
+/// never reports the literal "HEAD" as a branch, and works on an unborn
+/// branch.
+pub fn head_state() -> Result<HeadState> {
+    head_state_with(&[])
+}
+
+/// `head_state` of the worktree at `path`
+pub fn head_state_in(path: &Path) -> Result<HeadState> {
+    head_state_with(&["-C", path_str(path)?])
+}
+
+fn head_state_with(prefix: &[&str]) -> Result<HeadState> {
+    let git = |args: &[&str]| git_command(&[prefix, args].concat()).output();
+
+    let output = git(&["symbolic-ref", "--quiet", "--short", "HEAD"])?;
+    if output.status.success() {
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return Ok(HeadState::Branch(name));
+    }
+    // --quiet: exit 1 means "not a symbolic ref", anything else is an error
+    if output.status.code() != Some(1) {
         return Err(Error::NotInRepo);
     }
+    let output = git(&["rev-parse", "--short", "HEAD"])?;
+    if !output.status.success() {
+        return Err(Error::Command(extract_error(&output)));
+    }
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(HeadState::Detached(commit))
+}
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+/// Get the current branch name; `Error::DetachedHead` when there is none
+pub fn current_branch() -> Result<String> {
+    match head_state()? {
+        HeadState::Branch(name) => Ok(name),
+        HeadState::Detached(commit) => Err(Error::DetachedHead(commit)),
+    }
 }
 
-/// Detect the trunk branch.
+/// Detect the trunk branch. `remote` is `general.remote` (usually origin).
 ///
-/// Priority: origin/HEAD (remote-authoritative) > main > master > "main"
+/// Priority: <remote>/HEAD (remote-authoritative) > main > master > the only
+/// local branch > the only <remote> branch > "main"
 ///
-/// origin/HEAD wins because it reflects the upstream's actual default branch,
+/// <remote>/HEAD wins because it reflects the upstream's actual default branch,
 /// avoiding silently picking `main` when the real trunk is `master` (or vice
-/// versa) just because both happen to exist locally.
-pub fn detect_trunk() -> Result<String> {
-    let output = Command::new("git")
-        .args(["symbolic-ref", "refs/remotes/origin/HEAD"])
-        .output()?;
+/// versa) just because both happen to exist locally. The single-branch
+/// steps cover e.g. a `develop`-only repo; they stay offline (no
+/// `git remote show`) since trunk detection runs on most commands.
+pub fn detect_trunk(remote: &str) -> Result<String> {
+    cached(format!("trunk:{remote}"), || compute_trunk(remote))
+}
+
+fn compute_trunk(remote: &str) -> Result<String> {
+    let prefix = format!("refs/remotes/{remote}/");
+    let output = git_command(&["symbolic-ref", &format!("{prefix}HEAD")]).output()?;
 
     if output.status.success() {
         let full = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if let Some(branch) = full.strip_prefix("refs/remotes/origin/") {
+        if let Some(branch) = full.strip_prefix(&prefix) {
             return Ok(branch.to_string());
         }
     }
 
+    // Remote-only counts too: a clone without origin/HEAD set may have
+    // `origin/master` but no local `master` yet.
     for branch in ["main", "master"] {
-        if branch_exists(branch)? {
+        if any_branch_exists(branch)? {
             return Ok(branch.to_string());
         }
     }
 
+    if let [only] = local_branches()?.as_slice() {
+        return Ok(only.clone());
+    }
+    if let [only] = remote_branches(remote)?.as_slice() {
+        return Ok(only.clone());
+    }
+
     Ok("main".to_string())
 }
 
+/// Branch names under `refs/remotes/<remote>/` (as of the last fetch),
+/// without the remote prefix and without the `HEAD` symref.
+pub fn remote_branches(remote: &str) -> Result<Vec<String>> {
+    let prefix = format!("refs/remotes/{remote}/");
+    let output = git_command(&["for-each-ref", "--format=%(refname)", &prefix]).output()?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|l| l.strip_prefix(&prefix))
+        .filter(|b| *b != "HEAD")
+        .map(|b| b.to_string())
+        .collect())
+}
+
+/// Local branch -> its upstream (`origin/foo`), for branches that have one
+pub fn branch_upstreams() -> Result<HashMap<String, String>> {
+    let output = git_command(&[
+        "for-each-ref",
+        "--format=%(refname:short)%09%(upstream:short)",
+        "refs/heads/",
+    ])
+    .output()?;
+
+    if !output.status.success() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|l| l.split_once('\t'))
+        .filter(|(_, upstream)| !upstream.is_empty())
+        .map(|(branch, upstream)| (branch.to_string(), upstream.to_string()))
+        .collect())
+}
+
 /// List all local branch names (one subprocess instead of N branch_exists calls)
 pub fn local_branches() -> Result<Vec<String>> {
-    let output = Command::new("git")
-        .args(["for-each-ref", "--format=%(refname:short)", "refs/heads/"])
-        .output()?;
+    let output =
+        git_command(&["for-each-ref", "--format=%(refname:short)", "refs/heads/"]).output()?;
 
     if !output.status.success() {
         return Ok(Vec::new());
@@ -140,18 +267,75 @@ pub fn local_branches() -> Result<Vec<String>> {
 
 /// Check if a branch exists
 pub fn branch_exists(name: &str) -> Result<bool> {
-    let output = Command::new("git")
-        .args([
-            "show-ref",
-            "--verify",
-            "--quiet",
-            &format!("refs/heads/{name}"),
-        ])
-        .output()?;
+    let output = git_command(&[
+        "show-ref",
+        "--verify",
+        "--quiet",
+        &format!("refs/heads/{name}"),
+    ])
+    .output()?;
+
+    Ok(output.status.success())
+}
+
+/// Validate a user-supplied branch name with git's own ref rules.
+///
+/// Checked as `refs/heads/<name>` rather than via `--branch`, which would
+/// expand `@{-1}`-style shorthands instead of rejecting them. Leading `-`
+/// is refused separately: it is a legal ref but `git branch` rejects it and
+/// it would parse as an option downstream.
+pub fn validate_branch_name(name: &str) -> Result<()> {
+    if name.starts_with('-') {
+        return Err(Error::InvalidBranchName(name.to_string()));
+    }
+    let output = git_command(&["check-ref-format", &format!("refs/heads/{name}")]).output()?;
+    if !output.status.success() {
+        return Err(Error::InvalidBranchName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Check if `refs/remotes/<remote>/<branch>` exists (as of the last fetch)
+pub fn remote_branch_exists(remote: &str, branch: &str) -> Result<bool> {
+    let output = git_command(&[
+        "show-ref",
+        "--verify",
+        "--quiet",
+        &format!("refs/remotes/{remote}/{branch}"),
+    ])
+    .output()?;
 
     Ok(output.status.success())
 }
 
+/// Check if `name` exists locally or on any configured remote
+pub fn any_branch_exists(name: &str) -> Result<bool> {
+    if branch_exists(name)? {
+        return Ok(true);
+    }
+    for remote in remotes()? {
+        if remote_branch_exists(&remote, name)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// List configured remote names
+pub fn remotes() -> Result<Vec<String>> {
+    let output = git_command(&["remote"]).output()?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
 /// Check if current working directory is inside the given path
 pub fn is_cwd_inside(path: &Path) -> bool {
     std::env::current_dir()
@@ -163,7 +347,7 @@ pub fn is_cwd_inside(path: &Path) -> bool {
 
 /// Get current commit hash
 pub fn current_commit() -> Result<String> {
-    let output = Command::new("git").args(["rev-parse", "HEAD"]).output()?;
+    let output = git_command(&["rev-parse", "HEAD"]).output()?;
 
     if !output.status.success() {
         return Err(Error::NotInRepo);
@@ -171,3 +355,89 @@ pub fn current_commit() -> Result<String> {
 
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
+
+/// `current_commit` of the worktree at `path`
+pub fn current_commit_in(path: &Path) -> Result<String> {
+    let output = git_command(&["-C", path_str(path)?, "rev-parse", "HEAD"]).output()?;
+
+    if !output.status.success() {
+        return Err(Error::Command(extract_error(&output)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Full commit id `rev` names (`HEAD~1`, `HEAD@{2}`, a tag or id), or
+/// `None` when it names no commit.
+pub fn resolve_commit(rev: &str) -> Result<Option<String>> {
+    if rev.starts_with('-') {
+        return Ok(None);
+    }
+    let output = git_command(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{rev}^{{commit}}"),
+    ])
+    .output()?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+/// Index file of the worktree at `path` (linked worktrees keep their own
+/// under the common dir)
+pub fn index_path_in(path: &Path) -> Result<PathBuf> {
+    let output =
+        git_command(&["-C", path_str(path)?, "rev-parse", "--git-path", "index"]).output()?;
+
+    if !output.status.success() {
+        return Err(Error::Command(extract_error(&output)));
+    }
+
+    // Relative to `path` in the main worktree, absolute in linked ones.
+    Ok(path.join(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+// ---------------------------------------------------------------------------
+// Per-run cache
+// ---------------------------------------------------------------------------
+
+/// (lookup, cwd) -> result; None while caching is off.
+static RUN_CACHE: Mutex<Option<HashMap<(String, PathBuf), String>>> = Mutex::new(None);
+
+/// Cache `workspace_id` / `detect_trunk` per cwd for the rest of the
+/// process (`Cli::run` turns it on: each lookup is several git calls, and
+/// one command asks many times). Keyed by cwd, so a command that moves to
+/// the main repo or another worktree looks up afresh. Off by default, so
+/// a long-lived library user always sees current refs.
+pub fn set_run_cache(enabled: bool) {
+    let mut cache = RUN_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    *cache = enabled.then(HashMap::new);
+}
+
+fn cached(lookup: String, compute: impl FnOnce() -> Result<String>) -> Result<String> {
+    let Ok(cwd) = std::env::current_dir() else {
+        return compute();
+    };
+    let key = (lookup, cwd);
+    if let Some(hit) = RUN_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|cache| cache.get(&key))
+    {
+        return Ok(hit.clone());
+    }
+    // Not held across git: errors aren't cached, and a slow lookup
+    // doesn't block other threads.
+    let value = compute()?;
+    if let Some(cache) = RUN_CACHE.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        cache.insert(key, value.clone());
+    }
+    Ok(value)
+}
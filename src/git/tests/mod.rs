@@ -245,6 +245,60 @@ fn test_repo_root() {
     });
 }
 
+#[test]
+fn test_repo_root_separate_git_dir() {
+    // Common dir is store.git, not <root>/.git: walking up from it would
+    // never reach the work tree.
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let store = dir.path().join("store.git");
+    StdCommand::new("git")
+        .args(["init", "--separate-git-dir"])
+        .arg(&store)
+        .arg(&repo)
+        .output()
+        .expect("git init failed");
+
+    let sub = repo.join("src");
+    std::fs::create_dir_all(&sub).unwrap();
+    with_cwd(&sub, || {
+        assert_eq!(repo_root().unwrap(), repo.canonicalize().unwrap());
+    });
+}
+
+#[test]
+fn test_main_worktree_is_repo_root() {
+    let dir = setup_test_repo();
+    with_cwd(dir.path(), || {
+        let main = main_worktree().unwrap();
+        assert_eq!(main, Some(repo_root().unwrap()));
+    });
+}
+
+#[test]
+fn test_find_worktree_by_branch() {
+    let dir = setup_test_repo();
+    let outside = tempdir().unwrap();
+    let wt_path = outside.path().join("manual");
+    StdCommand::new("git")
+        .args(["worktree", "add", "-b", "manual"])
+        .arg(&wt_path)
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    with_cwd(dir.path(), || {
+        let found = find_worktree_by_branch("manual").unwrap().unwrap();
+        assert_eq!(
+            found.path.canonicalize().unwrap(),
+            wt_path.canonicalize().unwrap()
+        );
+        // The main worktree holds `main` but is never returned
+        assert!(find_worktree_by_branch("main").unwrap().is_none());
+        assert!(find_worktree_by_branch("missing").unwrap().is_none());
+    });
+}
+
 #[test]
 fn test_repo_root_not_in_repo() {
     let dir = tempdir().unwrap();
@@ -311,11 +365,47 @@ fn test_current_branch() {
     });
 }
 
+#[test]
+fn test_head_state_detached() {
+    let dir = setup_test_repo();
+    with_cwd(dir.path(), || {
+        assert_eq!(head_state().unwrap(), HeadState::Branch("main".into()));
+
+        StdCommand::new("git")
+            .args(["checkout", "-q", "--detach"])
+            .output()
+            .unwrap();
+        let HeadState::Detached(commit) = head_state().unwrap() else {
+            panic!("expected a detached HEAD");
+        };
+        assert!(current_commit().unwrap().starts_with(&commit));
+        assert!(matches!(current_branch(), Err(Error::DetachedHead(c)) if c == commit));
+        assert_eq!(
+            head_state_in(dir.path()).unwrap(),
+            HeadState::Detached(commit)
+        );
+    });
+}
+
+#[test]
+fn test_head_state_unborn_branch() {
+    let dir = tempdir().unwrap();
+    StdCommand::new("git")
+        .args(["init", "-q", "-b", "fresh"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(
+        head_state_in(dir.path()).unwrap(),
+        HeadState::Branch("fresh".into())
+    );
+}
+
 #[test]
 fn test_detect_trunk() {
     let dir = setup_test_repo();
     with_cwd(dir.path(), || {
-        let trunk = detect_trunk();
+        let trunk = detect_trunk("origin");
         assert!(trunk.is_ok());
         assert_eq!(trunk.unwrap(), "main");
     });
@@ -341,6 +431,149 @@ fn test_branch_exists_false() {
     });
 }
 
+#[test]
+fn test_validate_branch_name_accepts_issue_keys() {
+    for name in ["PROJ-123", "feature/PROJ-123", "ABC-1-fix", "swift-fox"] {
+        assert!(validate_branch_name(name).is_ok(), "{name}");
+    }
+}
+
+#[test]
+fn test_validate_branch_name_rejects_invalid() {
+    for name in [
+        "",
+        "-PROJ-123",
+        "bad..name",
+        "has space",
+        "end.lock",
+        "x~1",
+        "@{-1}",
+    ] {
+        assert!(
+            matches!(validate_branch_name(name), Err(Error::InvalidBranchName(_))),
+            "{name}"
+        );
+    }
+}
+
+/// Repo whose `origin` is a bare clone carrying an extra `remote-only`
+/// branch; locally there is also a `local-only` branch that was never pushed.
+fn setup_repo_with_remote() -> (tempfile::TempDir, tempfile::TempDir) {
+    let dir = setup_test_repo();
+    let remote = tempdir().unwrap();
+    let git = |cwd: &Path, args: &[&str]| {
+        let out = StdCommand::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+    };
+
+    git(remote.path(), &["init", "--bare"]);
+    let url = remote.path().to_str().unwrap();
+    git(dir.path(), &["remote", "add", "origin", url]);
+    git(dir.path(), &["push", "origin", "main"]);
+    git(dir.path(), &["push", "origin", "main:remote-only"]);
+    git(dir.path(), &["fetch", "origin"]);
+    git(dir.path(), &["branch", "local-only"]);
+    (dir, remote)
+}
+
+#[test]
+fn test_remote_branch_exists() {
+    let (dir, _remote) = setup_repo_with_remote();
+    with_cwd(dir.path(), || {
+        // both local and remote
+        assert!(remote_branch_exists("origin", "main").unwrap());
+        assert!(branch_exists("main").unwrap());
+        // remote only
+        assert!(remote_branch_exists("origin", "remote-only").unwrap());
+        assert!(!branch_exists("remote-only").unwrap());
+        // local only
+        assert!(!remote_branch_exists("origin", "local-only").unwrap());
+        assert!(!remote_branch_exists("upstream", "main").unwrap());
+    });
+}
+
+#[test]
+fn test_delete_remote_branch() {
+    let (dir, remote) = setup_repo_with_remote();
+    with_cwd(dir.path(), || {
+        assert!(delete_remote_branch("origin", "remote-only").unwrap());
+        // Already gone: reported, not an error
+        assert!(!delete_remote_branch("origin", "remote-only").unwrap());
+        assert!(delete_remote_branch("nowhere", "main").is_err());
+    });
+    let out = StdCommand::new("git")
+        .args(["branch", "--list", "remote-only"])
+        .current_dir(remote.path())
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&out.stdout).trim().is_empty());
+}
+
+#[test]
+fn test_any_branch_exists() {
+    let (dir, _remote) = setup_repo_with_remote();
+    with_cwd(dir.path(), || {
+        assert!(any_branch_exists("main").unwrap());
+        assert!(any_branch_exists("remote-only").unwrap());
+        assert!(any_branch_exists("local-only").unwrap());
+        assert!(!any_branch_exists("nowhere").unwrap());
+    });
+}
+
+#[test]
+fn test_remotes() {
+    let (dir, _remote) = setup_repo_with_remote();
+    with_cwd(dir.path(), || {
+        assert_eq!(remotes().unwrap(), vec!["origin".to_string()]);
+    });
+    let plain = setup_test_repo();
+    with_cwd(plain.path(), || {
+        assert!(remotes().unwrap().is_empty());
+    });
+}
+
+/// `general.remote` picks whose HEAD decides the trunk.
+#[test]
+fn test_detect_trunk_uses_given_remote() {
+    let dir = setup_test_repo();
+    let remote = tempdir().unwrap();
+    with_cwd(dir.path(), || {
+        let git = |args: &[&str]| {
+            let out = StdCommand::new("git").args(args).output().unwrap();
+            assert!(out.status.success(), "git {args:?}: {out:?}");
+        };
+        git(&["init", "-q", "--bare", remote.path().to_str().unwrap()]);
+        git(&["remote", "add", "upstream", remote.path().to_str().unwrap()]);
+        git(&["push", "-q", "upstream", "main:develop"]);
+        git(&["remote", "set-head", "upstream", "develop"]);
+        assert_eq!(detect_trunk("upstream").unwrap(), "develop");
+        // No origin at all: the local fallbacks apply
+        assert_eq!(detect_trunk("origin").unwrap(), "main");
+    });
+}
+
+/// No origin/HEAD and no local main/master: a remote `master` is enough.
+#[test]
+fn test_detect_trunk_remote_only() {
+    let (dir, _remote) = setup_repo_with_remote();
+    with_cwd(dir.path(), || {
+        let git = |args: &[&str]| {
+            StdCommand::new("git").args(args).output().unwrap();
+        };
+        git(&["push", "origin", "main:master"]);
+        git(&["fetch", "origin"]);
+        git(&["checkout", "-q", "-b", "work"]);
+        git(&["branch", "-D", "main"]);
+        git(&["push", "origin", "--delete", "main"]);
+        git(&["fetch", "--prune", "origin"]);
+        assert_eq!(detect_trunk("origin").unwrap(), "master");
+    });
+}
+
 #[test]
 fn test_current_commit() {
     let dir = setup_test_repo();
@@ -352,6 +585,78 @@ fn test_current_commit() {
     });
 }
 
+#[test]
+fn test_create_worktree_branch_checked_out_in_main_tree() {
+    let dir = setup_test_repo();
+    let wt_path = dir.path().join("dup");
+
+    with_cwd(dir.path(), || {
+        let err = create_worktree(&wt_path, "main", "main").unwrap_err();
+        assert!(
+            matches!(&err, Error::WorktreeExists(b) if b == "main"),
+            "{err:?}"
+        );
+        assert!(!wt_path.exists());
+    });
+}
+
+#[test]
+fn test_create_worktree_branch_being_rebased_elsewhere() {
+    let dir = setup_test_repo();
+    let git = |cwd: &std::path::Path, args: &[&str]| {
+        StdCommand::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap()
+    };
+
+    with_cwd(dir.path(), || {
+        // main and feature both edit README.md; rebasing feature stops on the conflict
+        let linked = dir.path().join("linked");
+        create_worktree(&linked, "feature", "main").unwrap();
+        std::fs::write(linked.join("README.md"), "feature\n").unwrap();
+        git(&linked, &["commit", "-qam", "feature"]);
+        std::fs::write(dir.path().join("README.md"), "main\n").unwrap();
+        git(dir.path(), &["commit", "-qam", "main"]);
+        assert!(!git(&linked, &["rebase", "main"]).status.success());
+        // Listed as detached, so only git's own check catches it
+        assert!(list_worktrees()
+            .unwrap()
+            .iter()
+            .all(|wt| wt.branch.as_deref() != Some("feature")));
+
+        let err = create_worktree(&dir.path().join("dup"), "feature", "main").unwrap_err();
+        assert!(
+            matches!(&err, Error::WorktreeExists(b) if b == "feature"),
+            "{err:?}"
+        );
+    });
+}
+
+#[test]
+fn test_current_commit_in_and_index_path_in() {
+    let dir = setup_test_repo();
+    let wt_path = dir.path().join("linked");
+
+    with_cwd(dir.path(), || {
+        create_worktree(&wt_path, "linked", "main").unwrap();
+        assert_eq!(
+            current_commit_in(&wt_path).unwrap(),
+            current_commit().unwrap()
+        );
+
+        let main_index = index_path_in(dir.path()).unwrap();
+        assert_eq!(main_index, dir.path().join(".git/index"));
+        assert!(main_index.exists());
+
+        // Linked worktrees have their own index under the common dir
+        let linked_index = index_path_in(&wt_path).unwrap();
+        assert_ne!(linked_index, main_index);
+        assert!(linked_index.exists());
+    });
+}
+
 // ===========================================================================
 // parse_shortstat tests (pure function)
 // ===========================================================================
@@ -389,3 +694,116 @@ fn test_parse_shortstat_single_change() {
     assert_eq!(stat.insertions, 1);
     assert_eq!(stat.deletions, 1);
 }
+
+#[test]
+fn test_program_precedence() {
+    assert_eq!(program(None, None), "git");
+    assert_eq!(program(None, Some("/opt/git/bin/git")), "/opt/git/bin/git");
+    assert_eq!(
+        program(Some("/usr/bin/git"), Some("/opt/git")),
+        "/usr/bin/git"
+    );
+    // Empty WT_GIT counts as unset
+    assert_eq!(program(Some(""), Some("/opt/git")), "/opt/git");
+}
+
+#[test]
+fn test_command_line_quotes_only_when_needed() {
+    assert_eq!(
+        command_line(&["-C", "/tmp/wt/feat", "rebase", "main"]),
+        "git -C /tmp/wt/feat rebase main"
+    );
+    assert_eq!(
+        command_line(&["commit", "-m", "it's done", ""]),
+        r"git commit -m 'it'\''s done' ''"
+    );
+}
+
+/// `develop`-only repo without a remote: the single local branch is trunk.
+#[test]
+fn test_detect_trunk_single_local_branch() {
+    let dir = setup_test_repo();
+    with_cwd(dir.path(), || {
+        StdCommand::new("git")
+            .args(["branch", "-M", "develop"])
+            .output()
+            .unwrap();
+        assert_eq!(detect_trunk("origin").unwrap(), "develop");
+
+        // Two candidates and neither is main/master: fall back to "main"
+        StdCommand::new("git")
+            .args(["branch", "feature"])
+            .output()
+            .unwrap();
+        assert_eq!(detect_trunk("origin").unwrap(), "main");
+    });
+}
+
+/// With the run cache on, lookups repeat per cwd; another cwd looks up afresh.
+#[test]
+fn test_run_cache_is_per_cwd() {
+    let dir = setup_test_repo();
+    let other = setup_test_repo();
+    with_cwd(dir.path(), || {
+        set_run_cache(true);
+        let id = workspace_id().unwrap();
+        assert_eq!(detect_trunk("origin").unwrap(), "main");
+
+        // Renaming the only branch changes the answer, but not this run's
+        StdCommand::new("git")
+            .args(["branch", "-M", "develop"])
+            .output()
+            .unwrap();
+        assert_eq!(detect_trunk("origin").unwrap(), "main");
+        assert_eq!(workspace_id().unwrap(), id);
+
+        std::env::set_current_dir(other.path()).unwrap();
+        assert_ne!(workspace_id().unwrap(), id);
+
+        std::env::set_current_dir(dir.path()).unwrap();
+        set_run_cache(false);
+        assert_eq!(detect_trunk("origin").unwrap(), "develop");
+    });
+}
+
+/// No origin/HEAD, several local branches, but origin only has `develop`.
+#[test]
+fn test_detect_trunk_single_remote_branch() {
+    let (dir, _remote) = setup_repo_with_remote();
+    with_cwd(dir.path(), || {
+        let git = |args: &[&str]| {
+            StdCommand::new("git").args(args).output().unwrap();
+        };
+        git(&["push", "origin", "main:develop"]);
+        git(&["push", "origin", "--delete", "main", "remote-only"]);
+        git(&["checkout", "-q", "-b", "develop"]);
+        git(&["branch", "-D", "main"]);
+        git(&["fetch", "--prune", "origin"]);
+        assert_eq!(remote_branches("origin").unwrap(), vec!["develop"]);
+        assert_eq!(detect_trunk("origin").unwrap(), "develop");
+    });
+}
+
+#[test]
+fn test_worktree_path_for_cwd() {
+    let dir = setup_test_repo();
+    let outside = tempdir().unwrap();
+    let wt_path = outside.path().join("cwd-wt");
+    StdCommand::new("git")
+        .args(["worktree", "add", "-b", "cwd-wt"])
+        .arg(&wt_path)
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    let nested = wt_path.join("sub");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    with_cwd(&nested, || {
+        let found = worktree_path_for_cwd().unwrap().unwrap();
+        assert_eq!(found.branch.as_deref(), Some("cwd-wt"));
+    });
+    // The main worktree doesn't count
+    with_cwd(dir.path(), || {
+        assert!(worktree_path_for_cwd().unwrap().is_none());
+    });
+}
@@ -25,6 +25,45 @@ fn test_has_uncommitted_changes_dirty() {
     });
 }
 
+/// Status queries must not rewrite the index (index.lock) under an agent.
+#[test]
+fn test_uncommitted_count_in_leaves_index_untouched() {
+    use filetime::FileTime;
+
+    let dir = setup_test_repo();
+    let index = dir.path().join(".git/index");
+    // Stat info out of date: a plain `git status` would refresh the index
+    let past = FileTime::from_unix_time(1_600_000_000, 0);
+    filetime::set_file_mtime(&index, past).unwrap();
+    filetime::set_file_mtime(dir.path().join("README.md"), FileTime::now()).unwrap();
+
+    assert_eq!(uncommitted_count_in(dir.path()).unwrap(), 0);
+    assert!(diff_shortstat_in(dir.path()).is_ok());
+    let mtime = FileTime::from_last_modification_time(&std::fs::metadata(&index).unwrap());
+    assert_eq!(mtime, past);
+}
+
+/// `status.showUntrackedFiles=no` must not hide new files from the count.
+#[test]
+fn test_uncommitted_count_in_ignores_untracked_config() {
+    let dir = setup_test_repo();
+    StdCommand::new("git")
+        .args(["config", "status.showUntrackedFiles", "no"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    std::fs::write(dir.path().join("new_file.txt"), "content").unwrap();
+    assert_eq!(uncommitted_count_in(dir.path()).unwrap(), 1);
+}
+
+/// A broken repo is an error, not "0 uncommitted".
+#[test]
+fn test_uncommitted_count_in_errors_on_corrupt_index() {
+    let dir = setup_test_repo();
+    std::fs::write(dir.path().join(".git/index"), "not an index").unwrap();
+    assert!(uncommitted_count_in(dir.path()).is_err());
+}
+
 #[test]
 fn test_list_worktrees() {
     let dir = setup_test_repo();
@@ -77,18 +116,37 @@ fn test_commit_count() {
 fn test_fetch() {
     let dir = setup_test_repo();
     with_cwd(dir.path(), || {
-        let result = fetch();
-        assert!(result.is_ok());
+        // No remote configured: reported, not an error
+        let result = fetch("origin");
+        assert!(matches!(result, Ok(FetchStatus::Failed(_))));
     });
 }
 
 #[test]
-fn test_is_merged() {
+fn test_is_ancestor() {
     let dir = setup_test_repo();
+    let git = |args: &[&str]| {
+        let output = StdCommand::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    };
+    git(&["branch", "merged"]);
+    git(&["checkout", "-b", "ahead"]);
+    git(&["commit", "--allow-empty", "-m", "Ahead"]);
+    git(&["checkout", "--orphan", "unrelated"]);
+    git(&["commit", "--allow-empty", "-m", "Root"]);
+
     with_cwd(dir.path(), || {
-        let result = is_merged("main", "main");
-        assert!(result.is_ok());
-        assert!(result.unwrap());
+        assert!(is_ancestor("main", "main").unwrap());
+        assert!(is_ancestor("merged", "main").unwrap());
+        assert!(is_ancestor("main", "ahead").unwrap());
+        assert!(!is_ancestor("ahead", "main").unwrap());
+        assert!(!is_ancestor("unrelated", "main").unwrap());
+        assert!(!is_ancestor("main", "unrelated").unwrap());
+        assert!(is_ancestor("no-such-branch", "main").is_err());
     });
 }
 
@@ -244,7 +302,7 @@ fn test_merge_fast_forward() {
 
     with_cwd(dir.path(), || {
         // Merge should work (fast-forward or no-op)
-        let result = merge("already-merged", false, false, None);
+        let result = merge("already-merged", false, false, None, false);
         // May succeed or say "already up to date"
         let _ = result;
     });
@@ -371,3 +429,380 @@ fn test_has_changes_from_trunk_with_uncommitted_changes() {
         assert!(has.unwrap(), "Should detect uncommitted changes");
     });
 }
+
+// ===========================================================================
+// work_summary tests
+// ===========================================================================
+
+#[test]
+fn test_work_summary_counts_commits_and_uncommitted() {
+    let dir = setup_test_repo();
+    let git = |args: &[&str]| {
+        StdCommand::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap()
+    };
+
+    assert_eq!(
+        work_summary("main", dir.path()).unwrap(),
+        WorkSummary::default()
+    );
+
+    git(&["checkout", "-b", "feature"]);
+    std::fs::write(dir.path().join("feature.txt"), "a\nb\n").unwrap();
+    git(&["add", "feature.txt"]);
+    git(&["commit", "-m", "Add feature"]);
+    std::fs::write(dir.path().join("dirty.txt"), "wip").unwrap();
+    std::fs::write(dir.path().join("feature.txt"), "a\n").unwrap();
+
+    let summary = work_summary("main", dir.path()).unwrap();
+    assert_eq!(summary.commits_ahead, 1);
+    assert_eq!(summary.uncommitted, 2);
+    assert_eq!(summary.insertions, 2);
+    assert_eq!(summary.deletions, 1);
+    assert!(summary.has_work());
+}
+
+#[test]
+fn test_work_summary_unknown_target_is_error() {
+    let dir = setup_test_repo();
+    assert!(work_summary("no-such-branch", dir.path()).is_err());
+}
+
+// ===========================================================================
+// stash_push / stash_pop tests
+// ===========================================================================
+#[test]
+fn test_stash_push_clean_returns_none() {
+    let dir = setup_test_repo();
+    assert_eq!(stash_push(dir.path(), "wt test").unwrap(), None);
+}
+
+#[test]
+fn test_stash_push_and_pop_roundtrip() {
+    let dir = setup_test_repo();
+    std::fs::write(dir.path().join("README.md"), "edited\n").unwrap();
+    std::fs::write(dir.path().join("untracked.txt"), "new").unwrap();
+
+    let id = stash_push(dir.path(), "wt test").unwrap().expect("stashed");
+    assert!(!dir.path().join("untracked.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("README.md")).unwrap(),
+        "# Test\n"
+    );
+
+    stash_pop(dir.path(), &id).unwrap();
+    assert!(dir.path().join("untracked.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("README.md")).unwrap(),
+        "edited\n"
+    );
+}
+
+/// Popping by id survives another stash being pushed on top.
+#[test]
+fn test_stash_pop_by_id_not_top() {
+    let dir = setup_test_repo();
+    std::fs::write(dir.path().join("mine.txt"), "mine").unwrap();
+    let mine = stash_push(dir.path(), "mine").unwrap().unwrap();
+
+    std::fs::write(dir.path().join("other.txt"), "other").unwrap();
+    let _other = stash_push(dir.path(), "other").unwrap().unwrap();
+
+    stash_pop(dir.path(), &mine).unwrap();
+    assert!(dir.path().join("mine.txt").exists());
+    assert!(!dir.path().join("other.txt").exists());
+}
+
+#[test]
+fn test_stash_pop_conflict_keeps_entry() {
+    let dir = setup_test_repo();
+    std::fs::write(dir.path().join("README.md"), "stashed\n").unwrap();
+    let id = stash_push(dir.path(), "wt test").unwrap().unwrap();
+
+    std::fs::write(dir.path().join("README.md"), "committed\n").unwrap();
+    StdCommand::new("git")
+        .args(["commit", "-am", "diverge"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(stash_pop(dir.path(), &id).is_err());
+    let list = StdCommand::new("git")
+        .args(["stash", "list", "--format=%H"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&list.stdout).contains(&id));
+}
+
+#[test]
+fn test_stash_pop_unknown_id() {
+    let dir = setup_test_repo();
+    assert!(stash_pop(dir.path(), "0000000000000000000000000000000000000000").is_err());
+}
+
+// ===========================================================================
+// commit_with tests
+// ===========================================================================
+#[test]
+fn test_commit_with_author_and_date() {
+    let dir = setup_test_repo();
+    std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+    StdCommand::new("git")
+        .args(["add", "."])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    with_cwd(dir.path(), || {
+        commit_with(
+            "Pinned",
+            Some("Bot <bot@example.com>"),
+            Some("2024-01-15T10:30:00Z"),
+            false,
+        )
+        .unwrap();
+    });
+
+    let out = StdCommand::new("git")
+        .args(["log", "-1", "--format=%an <%ae>|%aI|%cI"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    let out = String::from_utf8_lossy(&out.stdout);
+    let parts: Vec<&str> = out.trim().split('|').collect();
+    assert_eq!(parts[0], "Bot <bot@example.com>");
+    assert!(parts[1].starts_with("2024-01-15T10:30:00"), "{out}");
+    assert!(parts[2].starts_with("2024-01-15T10:30:00"), "{out}");
+}
+
+#[test]
+fn test_commit_without_overrides_uses_ambient_identity() {
+    let dir = setup_test_repo();
+    std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+    StdCommand::new("git")
+        .args(["add", "."])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    with_cwd(dir.path(), || commit("Plain").unwrap());
+
+    let out = StdCommand::new("git")
+        .args(["log", "-1", "--format=%an"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "Test");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_commit_with_no_verify_skips_pre_commit_hook() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = setup_test_repo();
+    let hook = dir.path().join(".git/hooks/pre-commit");
+    std::fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    std::fs::write(&hook, "#!/bin/sh\nexit 1\n").unwrap();
+    std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    std::fs::write(dir.path().join("c.txt"), "c").unwrap();
+    StdCommand::new("git")
+        .args(["add", "."])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    with_cwd(dir.path(), || {
+        assert!(commit("Blocked").is_err());
+        commit_with("Allowed", None, None, true).unwrap();
+    });
+}
+
+// ===========================================================================
+// Path-aware (_in) variants
+// ===========================================================================
+
+// Worktree `feature` at <repo>/feature-wt, behind main by one commit.
+fn setup_behind_worktree(dir: &Path) -> PathBuf {
+    let wt_path = dir.join("feature-wt");
+    with_cwd(dir, || {
+        create_worktree(&wt_path, "feature", "main").unwrap();
+    });
+    std::fs::write(dir.join("trunk.txt"), "trunk").unwrap();
+    for args in [&["add", "trunk.txt"][..], &["commit", "-m", "Trunk update"]] {
+        StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+    }
+    wt_path
+}
+
+#[test]
+fn test_rebase_in_targets_given_worktree() {
+    let dir = setup_test_repo();
+    let wt_path = setup_behind_worktree(dir.path());
+
+    // Runs from the main repo; only the worktree moves.
+    with_cwd(dir.path(), || rebase_in(&wt_path, "main").unwrap());
+    assert!(wt_path.join("trunk.txt").exists());
+    with_cwd(dir.path(), || {
+        assert_eq!(current_branch().unwrap(), "main");
+    });
+}
+
+#[test]
+fn test_merge_in_targets_given_worktree() {
+    let dir = setup_test_repo();
+    let wt_path = setup_behind_worktree(dir.path());
+
+    with_cwd(dir.path(), || {
+        merge_in(&wt_path, "main", false, false, None, false).unwrap();
+    });
+    assert!(wt_path.join("trunk.txt").exists());
+}
+
+#[test]
+fn test_checkout_in_targets_given_worktree() {
+    let dir = setup_test_repo();
+    let wt_path = setup_behind_worktree(dir.path());
+    StdCommand::new("git")
+        .args(["branch", "other"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    with_cwd(dir.path(), || {
+        checkout_in(&wt_path, "other").unwrap();
+        assert_eq!(current_branch().unwrap(), "main");
+    });
+    with_cwd(&wt_path, || assert_eq!(current_branch().unwrap(), "other"));
+}
+
+#[test]
+fn test_commit_in_and_has_staged_changes_in() {
+    let dir = setup_test_repo();
+    let wt_path = setup_behind_worktree(dir.path());
+    std::fs::write(wt_path.join("wt.txt"), "wt").unwrap();
+    StdCommand::new("git")
+        .args(["add", "wt.txt"])
+        .current_dir(&wt_path)
+        .output()
+        .unwrap();
+
+    with_cwd(dir.path(), || {
+        assert!(has_staged_changes_in(&wt_path).unwrap());
+        assert!(!has_staged_changes().unwrap());
+        commit_in(&wt_path, "Worktree commit").unwrap();
+        assert!(!has_staged_changes_in(&wt_path).unwrap());
+        assert_eq!(commit_count("main", "feature").unwrap(), 1);
+    });
+}
+
+#[test]
+fn test_dry_run_merge_in_and_reset_merge_in() {
+    let dir = setup_test_repo();
+    let wt_path = setup_behind_worktree(dir.path());
+
+    with_cwd(dir.path(), || {
+        // feature is behind main: merging main into it is clean, and the
+        // dry run leaves nothing staged behind.
+        assert!(dry_run_merge_in(&wt_path, "main", true).unwrap());
+        assert!(!has_staged_changes_in(&wt_path).unwrap());
+
+        merge_in(&wt_path, "main", true, false, None, false).unwrap();
+        assert!(has_staged_changes_in(&wt_path).unwrap());
+        reset_merge_in(&wt_path).unwrap();
+        assert!(!has_staged_changes_in(&wt_path).unwrap());
+    });
+}
+
+#[test]
+fn test_conflicted_files_lists_unmerged_paths() {
+    let dir = setup_test_repo();
+
+    with_cwd(dir.path(), || {
+        assert!(conflicted_files().unwrap().is_empty());
+
+        run(&["checkout", "-b", "feature"]).unwrap();
+        std::fs::write("README.md", "feature\n").unwrap();
+        run(&["commit", "-am", "Feature edit"]).unwrap();
+        run(&["checkout", "main"]).unwrap();
+        std::fs::write("README.md", "main\n").unwrap();
+        run(&["commit", "-am", "Main edit"]).unwrap();
+
+        assert!(merge("feature", false, true, None, false).is_err());
+        assert_eq!(conflicted_files().unwrap(), vec!["README.md"]);
+        merge_abort().unwrap();
+    });
+}
+
+#[test]
+fn test_is_worktree_clean() {
+    let dir = setup_test_repo();
+    let wt_path = setup_behind_worktree(dir.path());
+
+    with_cwd(dir.path(), || {
+        assert!(is_worktree_clean(&wt_path).unwrap());
+        // Uncommitted edits alone don't count
+        std::fs::write(wt_path.join("scratch.txt"), "x\n").unwrap();
+        assert!(is_worktree_clean(&wt_path).unwrap());
+
+        // Conflicting squash merge in the worktree: unmerged paths, no MERGE_HEAD
+        std::fs::write(wt_path.join("README.md"), "feature\n").unwrap();
+        run(&["-C", wt_path.to_str().unwrap(), "commit", "-am", "Edit"]).unwrap();
+        std::fs::write("README.md", "main\n").unwrap();
+        run(&["commit", "-am", "Main edit"]).unwrap();
+        assert!(merge_in(&wt_path, "main", true, false, None, false).is_err());
+        assert!(!is_worktree_clean(&wt_path).unwrap());
+        // The main repo is unaffected
+        assert!(is_worktree_clean(dir.path()).unwrap());
+        reset_merge_in(&wt_path).unwrap();
+
+        // Regular merge in progress
+        assert!(merge_in(&wt_path, "main", false, true, None, false).is_err());
+        assert!(!is_worktree_clean(&wt_path).unwrap());
+        reset_merge_in(&wt_path).unwrap();
+        assert!(is_worktree_clean(&wt_path).unwrap());
+    });
+}
+
+#[test]
+fn test_dry_run_merge_conflicts_in_reports_paths() {
+    let dir = setup_test_repo();
+
+    with_cwd(dir.path(), || {
+        run(&["checkout", "-b", "feature"]).unwrap();
+        std::fs::write("README.md", "feature\n").unwrap();
+        run(&["commit", "-am", "Feature edit"]).unwrap();
+        run(&["checkout", "main"]).unwrap();
+        std::fs::write("README.md", "main\n").unwrap();
+        run(&["commit", "-am", "Main edit"]).unwrap();
+
+        for squash in [true, false] {
+            let conflicts = dry_run_merge_conflicts_in(Path::new("."), "feature", squash).unwrap();
+            assert_eq!(conflicts, Some(vec!["README.md".to_string()]));
+            // Nothing left behind by the dry run
+            assert!(conflicted_files().unwrap().is_empty());
+            assert!(!is_merge_in_progress());
+        }
+    });
+}
+
+#[test]
+fn test_delete_branch_in_runs_against_repo() {
+    let dir = setup_test_repo();
+    let wt_path = setup_behind_worktree(dir.path());
+
+    with_cwd(dir.path(), || {
+        remove_worktree(&wt_path, false).unwrap();
+        delete_branch_in(dir.path(), "feature", true).unwrap();
+        assert!(!branch_exists("feature").unwrap());
+    });
+}
@@ -3,9 +3,8 @@
 // ===========================================================================
 
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
-use super::{path_str, run, Error, Result};
+use super::{extract_error, git_command, path_str, run, Error, Result};
 
 /// Create a new worktree
 pub fn create_worktree(path: &Path, branch: &str, base: &str) -> Result<()> {
@@ -22,16 +21,46 @@ pub fn create_worktree(path: &Path, branch: &str, base: &str) -> Result<()> {
             return Err(Error::WorktreeExists(branch.to_string()));
         }
 
-        // Branch exists but no worktree - just check it out
-        run(&["worktree", "add", path_str, branch])?;
+        // Branch exists but no worktree - just check it out. A worktree
+        // mid-rebase of `branch` lists as detached, so git has the final say.
+        let output = git_command(&["worktree", "add", path_str, branch]).output()?;
+        if !output.status.success() {
+            let msg = extract_error(&output);
+            if is_checked_out_elsewhere(&msg) {
+                return Err(Error::WorktreeExists(branch.to_string()));
+            }
+            return Err(Error::Command(msg));
+        }
     } else {
-        // Branch doesn't exist - create it from base
-        run(&["worktree", "add", "-b", branch, path_str, base])?;
+        // Branch doesn't exist - create it from base. --no-track: from a
+        // remote base (`wt new --pull`) git would otherwise make the new
+        // branch track e.g. origin/main.
+        run(&[
+            "worktree",
+            "add",
+            "--no-track",
+            "-b",
+            branch,
+            path_str,
+            base,
+        ])?;
     }
 
     Ok(())
 }
 
+/// git's refusal to check out a branch another worktree holds
+/// ("already checked out at" before 2.42, "already used by worktree" after).
+fn is_checked_out_elsewhere(msg: &str) -> bool {
+    msg.contains("is already checked out at") || msg.contains("is already used by worktree")
+}
+
+/// Create a detached worktree at `commitish` (no branch is created or
+/// checked out, so the ref stays free for other worktrees).
+pub fn create_detached_worktree(path: &Path, commitish: &str) -> Result<()> {
+    run(&["worktree", "add", "--detach", path_str(path)?, commitish])
+}
+
 /// Remove a worktree
 pub fn remove_worktree(path: &Path, force: bool) -> Result<()> {
     let mut args = vec!["worktree", "remove"];
@@ -49,9 +78,7 @@ pub fn move_worktree(old_path: &Path, new_path: &Path) -> Result<()> {
 
 /// List all worktrees
 pub fn list_worktrees() -> Result<Vec<WorktreeInfo>> {
-    let output = Command::new("git")
-        .args(["worktree", "list", "--porcelain"])
-        .output()?;
+    let output = git_command(&["worktree", "list", "--porcelain"]).output()?;
 
     if !output.status.success() {
         return Err(Error::NotInRepo);
@@ -61,6 +88,44 @@ pub fn list_worktrees() -> Result<Vec<WorktreeInfo>> {
     Ok(parse_worktree_list(&content))
 }
 
+/// The main working tree, as git records it (first `worktree list` entry).
+///
+/// Ok(None) for a bare repository: `repo_root()` would name the directory
+/// holding the bare git dir, which is not a checkout.
+pub fn main_worktree() -> Result<Option<PathBuf>> {
+    let main = list_worktrees()?
+        .into_iter()
+        .next()
+        .ok_or(Error::NotInRepo)?;
+    if main.is_bare {
+        return Ok(None);
+    }
+    // With --separate-git-dir git lists the git dir itself here.
+    if !main.path.join(".git").exists() {
+        return super::repo_root().map(Some);
+    }
+    Ok(Some(main.path.canonicalize().unwrap_or(main.path)))
+}
+
+/// Linked worktree with `branch` checked out, wherever it lives (the main
+/// working tree, always listed first, is never returned).
+pub fn find_worktree_by_branch(branch: &str) -> Result<Option<WorktreeInfo>> {
+    Ok(list_worktrees()?
+        .into_iter()
+        .skip(1)
+        .find(|wt| wt.branch.as_deref() == Some(branch)))
+}
+
+/// Linked worktree containing the current directory (innermost one if they
+/// nest). The main working tree is never returned.
+pub fn worktree_path_for_cwd() -> Result<Option<WorktreeInfo>> {
+    Ok(list_worktrees()?
+        .into_iter()
+        .skip(1)
+        .filter(|wt| super::is_cwd_inside(&wt.path))
+        .max_by_key(|wt| wt.path.components().count()))
+}
+
 /// Parse git worktree list --porcelain output
 pub fn parse_worktree_list(content: &str) -> Vec<WorktreeInfo> {
     let mut worktrees = Vec::new();
@@ -76,6 +141,7 @@ pub fn parse_worktree_list(content: &str) -> Vec<WorktreeInfo> {
                 branch: None,
                 commit: None,
                 is_bare: false,
+                upstream: None,
             });
         } else if let Some(ref mut wt) = current {
             if let Some(branch) = line.strip_prefix("branch refs/heads/") {
@@ -101,4 +167,16 @@ pub struct WorktreeInfo {
     pub branch: Option<String>,
     pub commit: Option<String>,
     pub is_bare: bool,
+    /// Tracking branch (`origin/foo`). Not filled by `list_worktrees`;
+    /// see `fill_upstreams`.
+    pub upstream: Option<String>,
+}
+
+/// Set `upstream` on each worktree from one `git for-each-ref` call.
+pub fn fill_upstreams<'a>(worktrees: impl IntoIterator<Item = &'a mut WorktreeInfo>) -> Result<()> {
+    let upstreams = super::branch_upstreams()?;
+    for wt in worktrees {
+        wt.upstream = wt.branch.as_ref().and_then(|b| upstreams.get(b).cloned());
+    }
+    Ok(())
 }
@@ -2,6 +2,17 @@
 // agent-worktree - Git Worktree Workflow Tool for AI Coding Agents
 // ===========================================================================
 
+/// `eprintln!` for progress/status lines; `wt --quiet` drops them.
+/// Warnings, errors and recovery hints keep using `eprintln!`.
+#[macro_export]
+macro_rules! progress {
+    ($($arg:tt)*) => {
+        if !$crate::util::is_quiet() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
 pub mod cli;
 pub mod complete;
 pub mod config;
@@ -12,5 +23,7 @@ pub mod prompt;
 pub mod shell;
 pub mod update;
 pub mod util;
+pub mod workspace;
 
 pub use config::Config;
+pub use workspace::Workspace;
@@ -1,8 +1,8 @@
 use agent_worktree::cli::Cli;
 use agent_worktree::config::Config;
-use agent_worktree::update;
+use agent_worktree::{progress, update};
 use clap::Parser;
-use std::thread::JoinHandle;
+use std::sync::mpsc::{self, Receiver};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -12,7 +12,7 @@ fn main() {
 
     // Check for updates (once per day), runs in background
     let base_dir = Config::base_dir().ok();
-    let update_handle = base_dir.as_ref().and_then(|dir| {
+    let update_done = base_dir.as_ref().and_then(|dir| {
         if update::should_check(dir) {
             Some(spawn_update_check(dir.clone()))
         } else {
@@ -23,27 +23,34 @@ fn main() {
     let cli = Cli::parse();
     let result = cli.run();
 
-    // Wait for update check to complete before exiting
-    if let Some(handle) = update_handle {
-        let _ = handle.join();
+    // Wait for update check to complete before exiting, but never longer
+    // than its own request timeout: a hung request must not delay exit.
+    if let Some(done) = update_done {
+        let _ = done.recv_timeout(update::CHECK_TIMEOUT);
     }
 
     if let Err(e) = result {
-        eprintln!("error: {e}");
-        std::process::exit(1);
+        if !e.is_reported() {
+            eprintln!("error: {e}");
+        }
+        std::process::exit(e.exit_code());
     }
 }
 
-fn spawn_update_check(base_dir: std::path::PathBuf) -> JoinHandle<()> {
+fn spawn_update_check(base_dir: std::path::PathBuf) -> Receiver<()> {
+    let (tx, rx) = mpsc::channel();
     std::thread::spawn(move || {
-        if let Ok(Some(latest)) = update::check_update(VERSION) {
-            eprintln!(
+        if let Ok(Some(latest)) = update::check_update(VERSION, update::channel(&base_dir)) {
+            progress!(
                 "\x1b[33mA new version of agent-worktree is available: {} -> {}\x1b[0m",
-                VERSION, latest
+                VERSION,
+                latest
             );
-            eprintln!("\x1b[33mRun `wt update` to update\x1b[0m");
+            progress!("\x1b[33mRun `wt update` to update\x1b[0m");
         }
         // Mark that we checked (ignore errors)
         let _ = update::mark_checked(&base_dir);
-    })
+        let _ = tx.send(());
+    });
+    rx
 }
@@ -17,6 +17,23 @@ pub fn meta_path(wt_dir: &Path, branch: &str) -> PathBuf {
     wt_dir.join(format!("{branch}.toml"))
 }
 
+/// Hook output under `hooks.log_hooks`: {wt_dir}/{branch}.hooks.log
+pub fn hooks_log_path(wt_dir: &Path, branch: &str) -> PathBuf {
+    wt_dir.join(format!("{branch}.hooks.log"))
+}
+
+/// Branch the main repo was on while a merge of `branch` runs there, for
+/// `wt merge --abort`: {wt_dir}/{branch}.merge-prev
+pub fn merge_prev_path(wt_dir: &Path, branch: &str) -> PathBuf {
+    wt_dir.join(format!("{branch}.merge-prev"))
+}
+
+/// `wt merge --edit` scratch file for `branch`'s squash message:
+/// {wt_dir}/{branch}.merge-msg
+pub fn merge_msg_path(wt_dir: &Path, branch: &str) -> PathBuf {
+    wt_dir.join(format!("{branch}.merge-msg"))
+}
+
 /// Compatibility loader: prefer .toml, fallback to .status.toml.
 pub fn meta_path_with_fallback(wt_dir: &Path, branch: &str) -> PathBuf {
     let new = meta_path(wt_dir, branch);
@@ -53,26 +70,89 @@ pub enum Error {
     MissingBaseBranch,
 }
 
+/// Highest meta schema this binary understands. Bump when adding fields
+/// whose absence would be misread by older binaries.
+///
+/// - v2: `snap_command` (an older `wt` rewriting the file drops it)
+pub const SCHEMA_VERSION: u32 = 2;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorktreeMeta {
+    /// Files written before versioning existed parse as v1.
+    pub schema_version: u32,
     pub created_at: DateTime<Utc>,
     pub base_branch: String,
+    /// Last `wt cd` into this worktree; absent until the first visit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_accessed: Option<DateTime<Utc>>,
+    /// Free-form note on what the worktree is for (`wt new --desc`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Set while a snap session owns this worktree; cleared when the
+    /// session exits or is aborted (`wt snap-abort`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snap_state: Option<SnapState>,
+    /// Last agent command run in snap mode. Outlives the session, so
+    /// `wt snap <branch>` without a command can rerun it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snap_command: Option<String>,
+}
+
+/// Runtime state of an active snap session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapState {
+    /// Agent command the shell loop is running.
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+}
+
+impl SnapState {
+    pub fn new(command: String) -> Self {
+        Self {
+            command,
+            started_at: Utc::now(),
+        }
+    }
 }
 
 impl WorktreeMeta {
     pub fn new(base_branch: String) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             created_at: Utc::now(),
             base_branch,
+            last_accessed: None,
+            description: None,
+            snap_state: None,
+            snap_command: None,
         }
     }
 
     /// Load from file. Falls back to legacy schema (uses `trunk` when
     /// `base_branch` is absent) so pre-existing worktrees keep working.
-    /// Unknown fields (e.g. dropped `base_commit`, `snap_command`, `trunk`)
+    /// Unknown fields (e.g. dropped `base_commit`, `trunk`)
     /// are silently ignored.
+    ///
+    /// A file from a newer `wt` (higher `schema_version`) still loads — its
+    /// unknown fields are dropped — but warns so mixed-version setups are
+    /// noticed instead of silently losing data.
     pub fn load(path: &Path) -> Result<Self> {
-        Self::parse(&std::fs::read_to_string(path)?)
+        let meta = Self::parse(&std::fs::read_to_string(path)?)?;
+        if meta.is_newer_schema() {
+            eprintln!(
+                "Warning: {} uses metadata schema v{} (this wt understands v{}). \
+                 Run 'wt update' to avoid losing newer fields.",
+                path.display(),
+                meta.schema_version,
+                SCHEMA_VERSION
+            );
+        }
+        Ok(meta)
+    }
+
+    /// Written by a newer `wt` than this one.
+    pub fn is_newer_schema(&self) -> bool {
+        self.schema_version > SCHEMA_VERSION
     }
 
     fn parse(content: &str) -> Result<Self> {
@@ -82,14 +162,23 @@ impl WorktreeMeta {
             .or(raw.trunk)
             .ok_or(Error::MissingBaseBranch)?;
         Ok(Self {
+            schema_version: raw.schema_version,
             created_at: raw.created_at,
             base_branch,
+            last_accessed: raw.last_accessed,
+            description: raw.description,
+            snap_state: raw.snap_state,
+            snap_command: raw.snap_command,
         })
     }
 
-    /// Save to file
+    /// Save to file. An older file is stamped with the current schema, since
+    /// it may now carry fields its version doesn't cover; a newer one keeps
+    /// its version.
     pub fn save(&self, path: &Path) -> Result<()> {
-        let content = toml::to_string_pretty(self)?;
+        let mut stamped = self.clone();
+        stamped.schema_version = self.schema_version.max(SCHEMA_VERSION);
+        let content = toml::to_string_pretty(&stamped)?;
         std::fs::write(path, content)?;
         Ok(())
     }
@@ -100,11 +189,64 @@ impl WorktreeMeta {
 /// (serde's `#[serde(alias)]` is order-dependent and would not guarantee it).
 #[derive(Deserialize)]
 struct RawMeta {
+    #[serde(default = "legacy_schema_version")]
+    schema_version: u32,
     created_at: DateTime<Utc>,
     #[serde(default)]
     base_branch: Option<String>,
     #[serde(default)]
     trunk: Option<String>,
+    #[serde(default)]
+    last_accessed: Option<DateTime<Utc>>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    snap_state: Option<SnapState>,
+    #[serde(default)]
+    snap_command: Option<String>,
+}
+
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// Stamp `last_accessed = now` on an existing meta file. Best-effort: a
+/// worktree without meta (or with unreadable meta) is left untouched.
+pub fn touch_last_accessed(wt_dir: &Path, branch: &str) -> Result<()> {
+    let path = meta_path_with_fallback(wt_dir, branch);
+    let mut meta = WorktreeMeta::load(&path)?;
+    meta.last_accessed = Some(Utc::now());
+    meta.save(&path)
+}
+
+/// Record a snap session on an existing meta file (`wt snap`), remembering
+/// its command as `snap_command`.
+///
+/// Returns false without writing if a session is already recorded.
+pub fn set_snap_state(wt_dir: &Path, branch: &str, state: SnapState) -> Result<bool> {
+    let path = meta_path_with_fallback(wt_dir, branch);
+    let mut meta = WorktreeMeta::load(&path)?;
+    if meta.snap_state.is_some() {
+        return Ok(false);
+    }
+    meta.snap_command = Some(state.command.clone());
+    meta.snap_state = Some(state);
+    meta.save(&path)?;
+    Ok(true)
+}
+
+/// Drop any snap session state from an existing meta file.
+///
+/// Returns whether there was a session to clear; the file is only rewritten
+/// in that case.
+pub fn clear_snap_state(wt_dir: &Path, branch: &str) -> Result<bool> {
+    let path = meta_path_with_fallback(wt_dir, branch);
+    let mut meta = WorktreeMeta::load(&path)?;
+    if meta.snap_state.take().is_none() {
+        return Ok(false);
+    }
+    meta.save(&path)?;
+    Ok(true)
 }
 
 // ---------------------------------------------------------------------------
@@ -191,6 +333,7 @@ snap_command = "claude"
 "#;
         let meta = WorktreeMeta::parse(toml).unwrap();
         assert_eq!(meta.base_branch, "main");
+        assert_eq!(meta.snap_command.as_deref(), Some("claude"));
     }
 
     /// New `base_branch` wins over legacy `trunk` when both present.
@@ -216,6 +359,166 @@ created_at = "2024-01-15T10:30:00Z"
         ));
     }
 
+    #[test]
+    fn test_parse_without_last_accessed() {
+        let toml = r#"
+created_at = "2024-01-15T10:30:00Z"
+base_branch = "main"
+"#;
+        let meta = WorktreeMeta::parse(toml).unwrap();
+        assert!(meta.last_accessed.is_none());
+    }
+
+    #[test]
+    fn test_schema_version_written_on_save() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.toml");
+        WorktreeMeta::new("main".to_string()).save(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains(&format!("schema_version = {SCHEMA_VERSION}")));
+        // v2 added snap_command; a v1 binary must see this file as newer
+        assert!(content.contains("schema_version = 2"));
+    }
+
+    #[test]
+    fn test_schema_version_defaults_to_v1() {
+        let toml = r#"
+created_at = "2024-01-15T10:30:00Z"
+base_branch = "main"
+"#;
+        let meta = WorktreeMeta::parse(toml).unwrap();
+        assert_eq!(meta.schema_version, 1);
+        assert!(!meta.is_newer_schema());
+    }
+
+    #[test]
+    fn test_legacy_schema_upgraded_on_save() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.toml");
+        std::fs::write(
+            &path,
+            "created_at = \"2024-01-15T10:30:00Z\"\nbase_branch = \"main\"\n",
+        )
+        .unwrap();
+
+        let mut meta = WorktreeMeta::load(&path).unwrap();
+        assert_eq!(meta.schema_version, 1);
+        meta.snap_command = Some("claude".to_string());
+        meta.save(&path).unwrap();
+
+        let reloaded = WorktreeMeta::load(&path).unwrap();
+        assert_eq!(reloaded.schema_version, SCHEMA_VERSION);
+        assert_eq!(reloaded.snap_command.as_deref(), Some("claude"));
+    }
+
+    /// Newer files still load (unknown fields ignored) and keep their version
+    /// on re-save, so an older binary doesn't downgrade the marker.
+    #[test]
+    fn test_newer_schema_loads_and_is_preserved() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.toml");
+        let content = format!(
+            "schema_version = {}\ncreated_at = \"2024-01-15T10:30:00Z\"\nbase_branch = \"main\"\nfuture_field = true\n",
+            SCHEMA_VERSION + 1
+        );
+        std::fs::write(&path, content).unwrap();
+
+        let meta = WorktreeMeta::load(&path).unwrap();
+        assert!(meta.is_newer_schema());
+        assert_eq!(meta.base_branch, "main");
+
+        meta.save(&path).unwrap();
+        let reloaded = WorktreeMeta::load(&path).unwrap();
+        assert_eq!(reloaded.schema_version, SCHEMA_VERSION + 1);
+    }
+
+    #[test]
+    fn test_description_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.toml");
+
+        let mut meta = WorktreeMeta::new("main".to_string());
+        meta.description = Some("fix login race".to_string());
+        meta.save(&path).unwrap();
+
+        let loaded = WorktreeMeta::load(&path).unwrap();
+        assert_eq!(loaded.description.as_deref(), Some("fix login race"));
+    }
+
+    #[test]
+    fn test_description_omitted_when_none() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.toml");
+        WorktreeMeta::new("main".to_string()).save(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("description"));
+        assert!(WorktreeMeta::load(&path).unwrap().description.is_none());
+    }
+
+    #[test]
+    fn test_touch_last_accessed() {
+        let dir = tempdir().unwrap();
+        let path = meta_path(dir.path(), "br");
+        WorktreeMeta::new("main".to_string()).save(&path).unwrap();
+
+        touch_last_accessed(dir.path(), "br").unwrap();
+
+        let loaded = WorktreeMeta::load(&path).unwrap();
+        assert!(loaded.last_accessed.is_some());
+        assert_eq!(loaded.base_branch, "main");
+    }
+
+    #[test]
+    fn test_touch_last_accessed_missing_meta() {
+        let dir = tempdir().unwrap();
+        assert!(touch_last_accessed(dir.path(), "br").is_err());
+        assert!(!meta_path(dir.path(), "br").exists());
+    }
+
+    #[test]
+    fn test_snap_state_roundtrip_and_clear() {
+        let dir = tempdir().unwrap();
+        let path = meta_path(dir.path(), "br");
+        let mut meta = WorktreeMeta::new("main".to_string());
+        meta.snap_state = Some(SnapState::new("claude".to_string()));
+        meta.save(&path).unwrap();
+
+        let loaded = WorktreeMeta::load(&path).unwrap();
+        assert_eq!(loaded.snap_state.unwrap().command, "claude");
+
+        assert!(clear_snap_state(dir.path(), "br").unwrap());
+        let loaded = WorktreeMeta::load(&path).unwrap();
+        assert!(loaded.snap_state.is_none());
+        assert_eq!(loaded.base_branch, "main");
+
+        // Nothing left to clear
+        assert!(!clear_snap_state(dir.path(), "br").unwrap());
+    }
+
+    #[test]
+    fn test_set_snap_state_refuses_active_session() {
+        let dir = tempdir().unwrap();
+        let path = meta_path(dir.path(), "br");
+        WorktreeMeta::new("main".to_string()).save(&path).unwrap();
+
+        assert!(set_snap_state(dir.path(), "br", SnapState::new("claude".into())).unwrap());
+        assert!(!set_snap_state(dir.path(), "br", SnapState::new("codex".into())).unwrap());
+        let loaded = WorktreeMeta::load(&path).unwrap();
+        assert_eq!(loaded.snap_state.unwrap().command, "claude");
+        assert_eq!(loaded.snap_command.as_deref(), Some("claude"));
+
+        // The command outlives the session
+        assert!(clear_snap_state(dir.path(), "br").unwrap());
+        let loaded = WorktreeMeta::load(&path).unwrap();
+        assert_eq!(loaded.snap_command.as_deref(), Some("claude"));
+
+        // No meta: error, nothing written
+        assert!(set_snap_state(dir.path(), "other", SnapState::new("x".into())).is_err());
+        assert!(!meta_path(dir.path(), "other").exists());
+    }
+
     #[test]
     fn test_meta_path() {
         let dir = std::path::PathBuf::from("/tmp/wt");
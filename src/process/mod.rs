@@ -2,8 +2,11 @@
 // process - External Process Management (Agents & Hooks)
 // ===========================================================================
 
-use std::path::Path;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process::{Command, ExitStatus, Stdio};
+use std::sync::Mutex;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -12,8 +15,22 @@ pub enum Error {
     #[error("failed to spawn process: {0}")]
     Spawn(#[from] std::io::Error),
 
-    #[error("hook '{0}' failed")]
-    HookFailed(String),
+    /// Command, and the `log_hooks` file holding its output
+    #[error("hook '{0}' failed{log}", log = log_note(.1))]
+    HookFailed(String, Option<PathBuf>),
+
+    /// Every failed hook of a parallel run
+    #[error("hooks failed: {}{log}", .0.iter().map(|h| format!("'{h}'")).collect::<Vec<_>>().join(", "), log = log_note(.1))]
+    HooksFailed(Vec<String>, Option<PathBuf>),
+
+    #[error("failed to open hook log: {0}")]
+    Log(std::io::Error),
+}
+
+fn log_note(log: &Option<PathBuf>) -> String {
+    log.as_ref()
+        .map(|p| format!(" (output in {})", p.display()))
+        .unwrap_or_default()
 }
 
 /// Worktree context exposed to hooks as environment variables.
@@ -48,43 +65,242 @@ impl HookEnv<'_> {
 /// `env` is layered on top of the inherited environment, so hooks see the
 /// WT_* worktree context alongside the user's normal shell variables.
 pub fn run_interactive(command: &str, cwd: &Path, env: &HookEnv) -> Result<ExitStatus> {
+    let status = shell(command)
+        .current_dir(cwd)
+        .envs(env.vars())
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()?;
+    Ok(status)
+}
+
+/// Run `program` with `args` as given — no shell in between, so arguments
+/// keep their spaces and metacharacters. Otherwise like `run_interactive`.
+pub fn run_program(
+    program: &str,
+    args: &[String],
+    cwd: &Path,
+    env: &HookEnv,
+) -> Result<ExitStatus> {
+    let status = Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .envs(env.vars())
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()?;
+    Ok(status)
+}
+
+/// `command` as a shell snippet (`sh -c` / `cmd /C`)
+fn shell(command: &str) -> Command {
     let (shell, flag) = if cfg!(windows) {
         ("cmd", "/C")
     } else {
         ("sh", "-c")
     };
-    let status = Command::new(shell)
-        .args([flag, command])
+    let mut cmd = Command::new(shell);
+    cmd.args([flag, command]);
+    cmd
+}
+
+/// `run_interactive`, with stdout/stderr also appended to `log` (after a
+/// `$ <command>` line). Both still stream to the terminal, but through
+/// pipes: the hook no longer sees a tty there.
+fn run_logged(command: &str, cwd: &Path, env: &HookEnv, log: &Path) -> Result<ExitStatus> {
+    let mut file = open_log(log)?;
+    writeln!(file, "$ {command}").map_err(Error::Log)?;
+    let file = Mutex::new(file);
+
+    let mut child = shell(command)
         .current_dir(cwd)
         .envs(env.vars())
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    std::thread::scope(|scope| {
+        scope.spawn(|| tee(stdout, std::io::stdout(), &file));
+        scope.spawn(|| tee(stderr, std::io::stderr(), &file));
+    });
+    Ok(child.wait()?)
+}
+
+fn open_log(log: &Path) -> Result<File> {
+    if let Some(parent) = log.parent() {
+        std::fs::create_dir_all(parent).map_err(Error::Log)?;
+    }
+    File::options()
+        .create(true)
+        .append(true)
+        .open(log)
+        .map_err(Error::Log)
+}
+
+/// One hook of `run_hooks_parallel`: every output line (terminal and log)
+/// starts with `[<command>] `. No stdin — the hooks would fight over it.
+fn run_prefixed(
+    command: &str,
+    cwd: &Path,
+    env: &HookEnv,
+    log: Option<&Mutex<File>>,
+) -> Result<ExitStatus> {
+    if let Some(Ok(mut log)) = log.map(Mutex::lock) {
+        writeln!(log, "$ {command}").ok();
+    }
+    let mut child = shell(command)
+        .current_dir(cwd)
+        .envs(env.vars())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let prefix = format!("[{command}] ");
+    std::thread::scope(|scope| {
+        scope.spawn(|| tee_lines(stdout, std::io::stdout(), &prefix, log));
+        scope.spawn(|| tee_lines(stderr, std::io::stderr(), &prefix, log));
+    });
+    Ok(child.wait()?)
+}
+
+/// `tee` by whole lines, each prefixed, so parallel hooks interleave by
+/// line rather than mid-line.
+fn tee_lines(from: impl Read, mut to: impl Write, prefix: &str, log: Option<&Mutex<File>>) {
+    let mut from = BufReader::new(from);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        line.extend_from_slice(prefix.as_bytes());
+        match from.read_until(b'\n', &mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        if line.last() != Some(&b'\n') {
+            line.push(b'\n');
+        }
+        to.write_all(&line).ok();
+        if let Some(Ok(mut log)) = log.map(Mutex::lock) {
+            log.write_all(&line).ok();
+        }
+    }
+}
+
+/// Copy `from` to both `to` and `log` until EOF. Write errors are ignored:
+/// a full disk or closed terminal must not stall the hook.
+fn tee(mut from: impl Read, mut to: impl Write, log: &Mutex<File>) {
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = match from.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        to.write_all(&buf[..n]).ok();
+        to.flush().ok();
+        if let Ok(mut log) = log.lock() {
+            log.write_all(&buf[..n]).ok();
+        }
+    }
+}
+
+/// Run `editor` (a shell snippet, like git's core.editor) on `file`,
+/// attached to the terminal.
+pub fn run_editor(editor: &str, file: &Path) -> Result<ExitStatus> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", &format!("{editor} \"{}\"", file.display())]);
+        cmd
+    } else {
+        // The path goes in as "$1", never spliced into the shell string
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", &format!("{editor} \"$1\""), "editor"])
+            .arg(file);
+        cmd
+    };
+    Ok(cmd
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .status()?;
-    Ok(status)
+        .status()?)
 }
 
-/// Run a hook command
-pub fn run_hook(command: &str, cwd: &Path, env: &HookEnv) -> Result<()> {
-    let status = run_interactive(command, cwd, env)?;
+/// Run a hook command; with `log`, its output is also appended there.
+pub fn run_hook(command: &str, cwd: &Path, env: &HookEnv, log: Option<&Path>) -> Result<()> {
+    let status = match log {
+        Some(log) => run_logged(command, cwd, env, log)?,
+        None => run_interactive(command, cwd, env)?,
+    };
 
     if !status.success() {
-        return Err(Error::HookFailed(command.to_string()));
+        return Err(Error::HookFailed(
+            command.to_string(),
+            log.map(Path::to_path_buf),
+        ));
     }
 
     Ok(())
 }
 
 /// Run multiple hooks in sequence
-pub fn run_hooks(hooks: &[String], cwd: &Path, env: &HookEnv) -> Result<()> {
+pub fn run_hooks(hooks: &[String], cwd: &Path, env: &HookEnv, log: Option<&Path>) -> Result<()> {
     for hook in hooks {
-        eprintln!("Running hook: {hook}...");
-        run_hook(hook, cwd, env)?;
-        eprintln!("Hook done: {hook}");
+        progress!("Running hook: {hook}...");
+        run_hook(hook, cwd, env, log)?;
+        progress!("Hook done: {hook}");
     }
     Ok(())
 }
 
+/// Start all `hooks` at once and wait for every one; output lines are
+/// prefixed per hook. Fails listing all failed hooks, not just the first.
+pub fn run_hooks_parallel(
+    hooks: &[String],
+    cwd: &Path,
+    env: &HookEnv,
+    log: Option<&Path>,
+) -> Result<()> {
+    let file = log.map(open_log).transpose()?.map(Mutex::new);
+    progress!("Running {} hooks in parallel...", hooks.len());
+    let results: Vec<(&String, Result<ExitStatus>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = hooks
+            .iter()
+            .map(|hook| {
+                let file = file.as_ref();
+                scope.spawn(move || (hook, run_prefixed(hook, cwd, env, file)))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("hook thread panicked"))
+            .collect()
+    });
+
+    let mut failed = Vec::new();
+    for (hook, result) in results {
+        match result {
+            Ok(status) if status.success() => progress!("Hook done: {hook}"),
+            Ok(status) => {
+                eprintln!("[{hook}] exited with {status}");
+                failed.push(hook.clone());
+            }
+            Err(e) => {
+                eprintln!("[{hook}] {e}");
+                failed.push(hook.clone());
+            }
+        }
+    }
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::HooksFailed(failed, log.map(Path::to_path_buf)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,8 +321,13 @@ mod tests {
     // =========================================================================
     #[test]
     fn test_error_display() {
-        let err = Error::HookFailed("npm install".to_string());
+        let err = Error::HookFailed("npm install".to_string(), None);
         assert_eq!(err.to_string(), "hook 'npm install' failed");
+        let err = Error::HookFailed("npm install".to_string(), Some("/wt/a.hooks.log".into()));
+        assert_eq!(
+            err.to_string(),
+            "hook 'npm install' failed (output in /wt/a.hooks.log)"
+        );
     }
 
     // =========================================================================
@@ -186,7 +407,7 @@ mod tests {
             "echo \"$WT_MAIN_REPO|$WT_BRANCH|$WT_BASE_BRANCH\" > {}",
             out.display()
         );
-        run_hook(&cmd, dir.path(), &env).unwrap();
+        run_hook(&cmd, dir.path(), &env, None).unwrap();
         let content = std::fs::read_to_string(&out).unwrap();
         assert_eq!(content.trim(), "/main/repo|swift-fox|trunk");
     }
@@ -199,7 +420,7 @@ mod tests {
         // $WT_WORKTREE carries the worktree path verbatim. ($PWD is not used:
         // current_dir sets the real cwd but does not rewrite the $PWD var.)
         let cmd = format!("echo \"$WT_WORKTREE\" > {}", out.display());
-        run_hook(&cmd, dir.path(), &env).unwrap();
+        run_hook(&cmd, dir.path(), &env, None).unwrap();
         let content = std::fs::read_to_string(&out).unwrap();
         assert_eq!(content.trim(), dir.path().display().to_string());
     }
@@ -207,20 +428,66 @@ mod tests {
     // =========================================================================
     // run_hook tests
     // =========================================================================
+    #[test]
+    fn test_run_hook_appends_output_to_log() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("logs/b.hooks.log");
+        let env = dummy_env(dir.path());
+
+        run_hook("echo one", dir.path(), &env, Some(&log)).unwrap();
+        let err = run_hook("echo two >&2; false", dir.path(), &env, Some(&log)).unwrap_err();
+        assert!(matches!(err, Error::HookFailed(_, Some(ref p)) if p == &log));
+
+        let content = std::fs::read_to_string(&log).unwrap();
+        assert_eq!(content, "$ echo one\none\n$ echo two >&2; false\ntwo\n");
+    }
+
+    #[test]
+    fn test_run_hooks_parallel_runs_all_and_lists_failures() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("p.hooks.log");
+        let hooks = vec![
+            "echo a > a.txt; false".to_string(),
+            "echo b > b.txt".to_string(),
+            "echo c; exit 3".to_string(),
+        ];
+        let err =
+            run_hooks_parallel(&hooks, dir.path(), &dummy_env(dir.path()), Some(&log)).unwrap_err();
+        assert!(dir.path().join("a.txt").exists());
+        assert!(dir.path().join("b.txt").exists());
+        match err {
+            Error::HooksFailed(failed, _) => {
+                assert_eq!(failed, [hooks[0].as_str(), hooks[2].as_str()])
+            }
+            e => panic!("Expected HooksFailed, got {e}"),
+        }
+        let content = std::fs::read_to_string(&log).unwrap();
+        assert!(content.contains("[echo c; exit 3] c\n"), "{content}");
+    }
+
+    #[test]
+    fn test_hooks_failed_display_lists_all() {
+        let err = Error::HooksFailed(vec!["a".into(), "b".into()], None);
+        assert_eq!(err.to_string(), "hooks failed: 'a', 'b'");
+    }
+
     #[test]
     fn test_run_hook_success() {
         let dir = tempdir().unwrap();
-        let result = run_hook("true", dir.path(), &dummy_env(dir.path()));
+        let result = run_hook("true", dir.path(), &dummy_env(dir.path()), None);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_run_hook_failure() {
         let dir = tempdir().unwrap();
-        let result = run_hook("false", dir.path(), &dummy_env(dir.path()));
+        let result = run_hook("false", dir.path(), &dummy_env(dir.path()), None);
         assert!(result.is_err());
         match result.unwrap_err() {
-            Error::HookFailed(cmd) => assert_eq!(cmd, "false"),
+            Error::HookFailed(cmd, log) => {
+                assert_eq!(cmd, "false");
+                assert!(log.is_none());
+            }
             _ => panic!("Expected HookFailed error"),
         }
     }
@@ -231,7 +498,7 @@ mod tests {
         let file_path = dir.path().join("hook_created.txt");
 
         let cmd = format!("echo test > {}", file_path.display());
-        let result = run_hook(&cmd, dir.path(), &dummy_env(dir.path()));
+        let result = run_hook(&cmd, dir.path(), &dummy_env(dir.path()), None);
         assert!(result.is_ok());
         assert!(file_path.exists());
     }
@@ -243,7 +510,7 @@ mod tests {
     fn test_run_hooks_empty() {
         let dir = tempdir().unwrap();
         let hooks: Vec<String> = vec![];
-        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()));
+        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()), None);
         assert!(result.is_ok());
     }
 
@@ -251,7 +518,7 @@ mod tests {
     fn test_run_hooks_single() {
         let dir = tempdir().unwrap();
         let hooks = vec!["true".to_string()];
-        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()));
+        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()), None);
         assert!(result.is_ok());
     }
 
@@ -263,7 +530,7 @@ mod tests {
             "echo hello".to_string(),
             "true".to_string(),
         ];
-        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()));
+        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()), None);
         assert!(result.is_ok());
     }
 
@@ -279,7 +546,7 @@ mod tests {
             format!("touch {}", file2.display()),
         ];
 
-        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()));
+        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()), None);
         assert!(result.is_err());
         assert!(file1.exists()); // First hook ran
         assert!(!file2.exists()); // Third hook didn't run
@@ -296,7 +563,7 @@ mod tests {
             format!("echo three >> {}", file.display()),
         ];
 
-        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()));
+        let result = run_hooks(&hooks, dir.path(), &dummy_env(dir.path()), None);
         assert!(result.is_ok());
 
         let content = std::fs::read_to_string(&file).unwrap();
@@ -2,6 +2,9 @@
 // prompt - Interactive User Input
 // ===========================================================================
 
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use dialoguer::Confirm;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -11,17 +14,57 @@ pub enum Error {
     #[error("user cancelled")]
     Cancelled,
 
+    #[error("cannot ask \"{0}\": stdin is not a terminal (pass --yes to proceed)")]
+    NotInteractive(String),
+
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
 
-/// Ask for confirmation
+/// Set once from the global `--yes` flag before any command runs.
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Make every subsequent `confirm` answer yes without prompting (`wt -y`).
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+/// What `confirm` should do, given `--yes` and whether stdin is a TTY.
+#[derive(Debug, PartialEq, Eq)]
+enum ConfirmMode {
+    AutoYes,
+    Ask,
+    Refuse,
+}
+
+fn confirm_mode(assume_yes: bool, interactive: bool) -> ConfirmMode {
+    if assume_yes {
+        ConfirmMode::AutoYes
+    } else if interactive {
+        ConfirmMode::Ask
+    } else {
+        ConfirmMode::Refuse
+    }
+}
+
+/// Ask for confirmation.
+///
+/// With `--yes` this returns true without prompting. Without it, a non-TTY
+/// stdin (CI, agents) errors instead of blocking forever on input that
+/// will never come.
 pub fn confirm(message: &str) -> Result<bool> {
-    Confirm::new()
-        .with_prompt(message)
-        .default(false)
-        .interact()
-        .map_err(|_| Error::Cancelled)
+    match confirm_mode(
+        ASSUME_YES.load(Ordering::Relaxed),
+        std::io::stdin().is_terminal(),
+    ) {
+        ConfirmMode::AutoYes => Ok(true),
+        ConfirmMode::Refuse => Err(Error::NotInteractive(message.to_string())),
+        ConfirmMode::Ask => Confirm::new()
+            .with_prompt(message)
+            .default(false)
+            .interact()
+            .map_err(|_| Error::Cancelled),
+    }
 }
 
 /// Present options after agent exits with uncommitted changes
@@ -125,6 +168,30 @@ mod tests {
         assert_eq!(err.to_string(), "user cancelled");
     }
 
+    #[test]
+    fn test_not_interactive_error_mentions_yes() {
+        let err = Error::NotInteractive("Remove 2 worktrees?".to_string());
+        let msg = err.to_string();
+        assert!(msg.contains("Remove 2 worktrees?"));
+        assert!(msg.contains("--yes"));
+    }
+
+    #[test]
+    fn test_confirm_mode_yes_skips_prompt() {
+        assert_eq!(confirm_mode(true, true), ConfirmMode::AutoYes);
+        assert_eq!(confirm_mode(true, false), ConfirmMode::AutoYes);
+    }
+
+    #[test]
+    fn test_confirm_mode_tty_asks() {
+        assert_eq!(confirm_mode(false, true), ConfirmMode::Ask);
+    }
+
+    #[test]
+    fn test_confirm_mode_no_tty_refuses() {
+        assert_eq!(confirm_mode(false, false), ConfirmMode::Refuse);
+    }
+
     #[test]
     fn test_parse_snap_choice_r() {
         assert_eq!(parse_snap_choice("r"), Some(SnapExitChoice::Reopen));
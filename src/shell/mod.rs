@@ -26,6 +26,7 @@ pub enum Shell {
     Zsh,
     Fish,
     PowerShell,
+    Nu,
 }
 
 impl Shell {
@@ -36,15 +37,27 @@ impl Shell {
             return Some(Shell::PowerShell);
         }
 
-        // Unix: check $SHELL
+        // Unix: the shell actually running `wt` beats $SHELL, which is only
+        // the login shell (e.g. a bash user who launched zsh by hand).
         #[cfg(not(windows))]
         {
-            std::env::var("SHELL")
-                .ok()
-                .and_then(|s| Self::from_path(&s))
+            parent_process_name()
+                .and_then(|name| Self::from_process_name(&name))
+                .or_else(|| {
+                    std::env::var("SHELL")
+                        .ok()
+                        .and_then(|s| Self::from_path(&s))
+                })
         }
     }
 
+    /// Parse a process name as reported by `/proc/<pid>/comm` or `ps`.
+    ///
+    /// Login shells are reported with a leading dash (`-zsh`).
+    pub fn from_process_name(name: &str) -> Option<Self> {
+        Self::from_path(name.trim().trim_start_matches('-'))
+    }
+
     pub fn from_path(path: &str) -> Option<Self> {
         let path_lower = path.to_lowercase();
         if path_lower.ends_with("bash") {
@@ -55,6 +68,8 @@ impl Shell {
             Some(Shell::Fish)
         } else if path_lower.contains("powershell") || path_lower.ends_with("pwsh") {
             Some(Shell::PowerShell)
+        } else if is_nu_binary(&path_lower) {
+            Some(Shell::Nu)
         } else {
             None
         }
@@ -66,6 +81,7 @@ impl Shell {
             "zsh" => Some(Shell::Zsh),
             "fish" => Some(Shell::Fish),
             "powershell" | "pwsh" => Some(Shell::PowerShell),
+            "nu" | "nushell" => Some(Shell::Nu),
             _ => None,
         }
     }
@@ -110,6 +126,17 @@ impl Shell {
                         .join("Microsoft.PowerShell_profile.ps1")
                 }
             }
+            // Nushell: %APPDATA%\nushell\config.nu on Windows, XDG path elsewhere
+            Shell::Nu => {
+                #[cfg(windows)]
+                {
+                    base.config_dir().join("nushell").join("config.nu")
+                }
+                #[cfg(not(windows))]
+                {
+                    home.join(".config").join("nushell").join("config.nu")
+                }
+            }
         })
     }
 
@@ -118,19 +145,51 @@ impl Shell {
             Shell::Bash | Shell::Zsh => BASH_ZSH_WRAPPER,
             Shell::Fish => FISH_WRAPPER,
             Shell::PowerShell => POWERSHELL_WRAPPER,
+            Shell::Nu => NU_WRAPPER,
         }
     }
 }
 
+/// Executable name of the parent process, if it can be determined.
+#[cfg(target_os = "linux")]
+fn parent_process_name() -> Option<String> {
+    let ppid = std::os::unix::process::parent_id();
+    std::fs::read_to_string(format!("/proc/{ppid}/comm"))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Executable name of the parent process, if it can be determined.
+#[cfg(all(unix, not(target_os = "linux")))]
+fn parent_process_name() -> Option<String> {
+    let ppid = std::os::unix::process::parent_id();
+    let output = std::process::Command::new("ps")
+        .args(["-o", "comm=", "-p", &ppid.to_string()])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Match `nu` / `nu.exe` by file name only — a bare `ends_with("nu")` would
+/// also catch unrelated binaries like `/usr/bin/menu`.
+fn is_nu_binary(path_lower: &str) -> bool {
+    let name = path_lower.rsplit(['/', '\\']).next().unwrap_or(path_lower);
+    name == "nu" || name == "nu.exe"
+}
+
 const MARKER_BEGIN: &str = "# === agent-worktree BEGIN ===";
 const MARKER_END: &str = "# === agent-worktree END ===";
 
 // ---------------------------------------------------------------------------
 // Shell Wrapper 脚本
 //
-// 协议约定（修改 snap 行为时，三套脚本必须同步更新）：
+// 协议约定（修改 snap 行为时，四套脚本必须同步更新）：
 // - snap-continue 退出码: 0=完成(cd 回 repo root), 2=重新打开 agent, 3=退出(留在 worktree)
 // - path_file 格式: 单行=目标路径, 双行=第一行路径+第二行命令(snap 模式)
+// - path_file 不存在 = 不 cd（wt 删除它表示已在目标位置）
 // ---------------------------------------------------------------------------
 
 const BASH_ZSH_WRAPPER: &str = r#"# === agent-worktree BEGIN ===
@@ -155,21 +214,21 @@ wt() {
   path_file=$(mktemp 2>/dev/null) || path_file="${TMPDIR:-/tmp}/wt-path-$$"
   case "$1" in
     cd)
-      "$wt_bin" "$@" --path-file "$path_file" || { rm -f "$path_file"; return $?; }
+      "$wt_bin" "$@" --path-file "$path_file" || { rm -f -- "$path_file"; return $?; }
       if [[ -f "$path_file" ]]; then
-        target_path=$(<"$path_file"); rm -f "$path_file"; cd "$target_path"
+        target_path=$(<"$path_file"); rm -f -- "$path_file"; cd -- "$target_path"
       fi
       ;;
-    new)
-      # Check for snap mode (-s/--snap)
-      if [[ " $* " == *" -s "* ]] || [[ " $* " == *" --snap "* ]]; then
-        "$wt_bin" "$@" --path-file "$path_file" || { rm -f "$path_file"; return $?; }
+    new|snap)
+      # Check for snap mode (wt snap, or new -s/--snap)
+      if [[ "$1" == snap ]] || [[ " $* " == *" -s "* ]] || [[ " $* " == *" --snap "* ]]; then
+        "$wt_bin" "$@" --path-file "$path_file" || { rm -f -- "$path_file"; return $?; }
         if [[ -f "$path_file" ]]; then
-          target_path="$(head -n1 "$path_file")"
-          snap_cmd="$(tail -n1 "$path_file")"
-          rm -f "$path_file"
+          target_path="$(head -n1 -- "$path_file")"
+          snap_cmd="$(tail -n1 -- "$path_file")"
+          rm -f -- "$path_file"
           [[ "$target_path" == "$snap_cmd" ]] && snap_cmd=""
-          [[ -n "$target_path" ]] && cd "$target_path"
+          [[ -n "$target_path" ]] && cd -- "$target_path"
           # Run snap mode loop in shell (preserves TTY)
           if [[ -n "$snap_cmd" ]]; then
             reopen_count=0
@@ -194,20 +253,20 @@ wt() {
               case $continue_status in
                 0)
                   if [[ -f "$path_file" ]]; then
-                    target_path=$(<"$path_file"); rm -f "$path_file"; cd "$target_path"
+                    target_path=$(<"$path_file"); rm -f -- "$path_file"; cd -- "$target_path"
                   fi
                   break
                   ;;
                 2)
-                  rm -f "$path_file" 2>/dev/null
+                  rm -f -- "$path_file" 2>/dev/null
                   reopen_count=$((reopen_count + 1))
                   ;;
                 3)
-                  rm -f "$path_file" 2>/dev/null
+                  rm -f -- "$path_file" 2>/dev/null
                   break
                   ;;
                 *)
-                  rm -f "$path_file" 2>/dev/null
+                  rm -f -- "$path_file" 2>/dev/null
                   break
                   ;;
               esac
@@ -215,20 +274,20 @@ wt() {
           fi
         fi
       else
-        "$wt_bin" "$@" --path-file "$path_file" || { rm -f "$path_file"; return $?; }
+        "$wt_bin" "$@" --path-file "$path_file" || { rm -f -- "$path_file"; return $?; }
         if [[ -f "$path_file" ]]; then
-          target_path=$(<"$path_file"); rm -f "$path_file"; cd "$target_path"
+          target_path=$(<"$path_file"); rm -f -- "$path_file"; cd -- "$target_path"
         fi
       fi
       ;;
-    rm|mv|merge|clean)
-      "$wt_bin" "$@" --path-file "$path_file" || { rm -f "$path_file"; return $?; }
+    rm|mv|rename|merge|clean|snap-abort)
+      "$wt_bin" "$@" --path-file "$path_file" || { rm -f -- "$path_file"; return $?; }
       if [[ -f "$path_file" ]]; then
-        target_path=$(<"$path_file"); rm -f "$path_file"; cd "$target_path"
+        target_path=$(<"$path_file"); rm -f -- "$path_file"; cd -- "$target_path"
       fi
       ;;
     *)
-      rm -f "$path_file" 2>/dev/null
+      rm -f -- "$path_file" 2>/dev/null
       "$wt_bin" "$@"
       ;;
   esac
@@ -258,17 +317,17 @@ function wt
   set -l path_file (mktemp)
   switch $argv[1]
     case cd
-      $wt_bin $argv --path-file $path_file; or begin; rm -f $path_file; return $status; end
-      if test -f $path_file; cd (cat $path_file); rm -f $path_file; end
-    case new
-      if contains -- -s $argv; or contains -- --snap $argv
-        $wt_bin $argv --path-file $path_file; or begin; rm -f $path_file; return $status; end
-        if test -f $path_file
-          set -l target_path (head -n1 $path_file)
-          set -l snap_cmd (tail -n1 $path_file)
-          rm -f $path_file
+      $wt_bin $argv --path-file "$path_file"; or begin; rm -f -- "$path_file"; return $status; end
+      if test -f "$path_file"; cd (cat "$path_file"); rm -f -- "$path_file"; end
+    case new snap
+      if test "$argv[1]" = snap; or contains -- -s $argv; or contains -- --snap $argv
+        $wt_bin $argv --path-file "$path_file"; or begin; rm -f -- "$path_file"; return $status; end
+        if test -f "$path_file"
+          set -l target_path (head -n1 "$path_file")
+          set -l snap_cmd (tail -n1 "$path_file")
+          rm -f -- "$path_file"
           test "$target_path" = "$snap_cmd"; and set snap_cmd ""
-          test -n "$target_path"; and cd $target_path
+          test -n "$target_path"; and cd "$target_path"
           if test -n "$snap_cmd"
             set -l reopen_count 0
             while true
@@ -276,44 +335,44 @@ function wt
                 echo "[wt] Reopen #$reopen_count"
               end
               echo "Entering snap mode: $snap_cmd"
-              echo "Worktree: "(basename $target_path)
+              echo "Worktree: "(basename "$target_path")
               echo "---"
               eval $snap_cmd
               set -l agent_status $status
               if test $agent_status -ne 0
                 echo "[wt] Agent exited with status $agent_status; checking worktree state..."
               end
-              $wt_bin snap-continue --path-file $path_file
+              $wt_bin snap-continue --path-file "$path_file"
               set -l continue_status $status
               # 0: done, cd to main; 2: reopen agent; 3: exit, stay in worktree
               switch $continue_status
                 case 0
-                  if test -f $path_file
-                    cd (cat $path_file); rm -f $path_file
+                  if test -f "$path_file"
+                    cd (cat "$path_file"); rm -f -- "$path_file"
                   end
                   break
                 case 2
-                  rm -f $path_file 2>/dev/null
+                  rm -f -- "$path_file" 2>/dev/null
                   set reopen_count (math $reopen_count + 1)
                 case 3
-                  rm -f $path_file 2>/dev/null
+                  rm -f -- "$path_file" 2>/dev/null
                   break
                 case '*'
-                  rm -f $path_file 2>/dev/null
+                  rm -f -- "$path_file" 2>/dev/null
                   break
               end
             end
           end
         end
       else
-        $wt_bin $argv --path-file $path_file; or begin; rm -f $path_file; return $status; end
-        if test -f $path_file; cd (cat $path_file); rm -f $path_file; end
+        $wt_bin $argv --path-file "$path_file"; or begin; rm -f -- "$path_file"; return $status; end
+        if test -f "$path_file"; cd (cat "$path_file"); rm -f -- "$path_file"; end
       end
-    case rm mv merge clean
-      $wt_bin $argv --path-file $path_file; or begin; rm -f $path_file; return $status; end
-      if test -f $path_file; cd (cat $path_file); rm -f $path_file; end
+    case rm mv rename merge clean snap-abort
+      $wt_bin $argv --path-file "$path_file"; or begin; rm -f -- "$path_file"; return $status; end
+      if test -f "$path_file"; cd (cat "$path_file"); rm -f -- "$path_file"; end
     case '*'
-      rm -f $path_file
+      rm -f -- "$path_file"
       $wt_bin $argv
   end
 end
@@ -337,8 +396,8 @@ function wt {
       if ($LASTEXITCODE -ne 0) { Remove-Item $pathFile -ErrorAction SilentlyContinue; return $LASTEXITCODE }
       if (Test-Path $pathFile) { Set-Location (Get-Content $pathFile); Remove-Item $pathFile }
     }
-    'new' {
-      if ($args -contains '-s' -or $args -contains '--snap') {
+    { $_ -in 'new', 'snap' } {
+      if ($args[0] -eq 'snap' -or $args -contains '-s' -or $args -contains '--snap') {
         & $wtBin.Source @args --path-file $pathFile
         if ($LASTEXITCODE -ne 0) { Remove-Item $pathFile -ErrorAction SilentlyContinue; return $LASTEXITCODE }
         if (Test-Path $pathFile) {
@@ -389,7 +448,7 @@ function wt {
         if (Test-Path $pathFile) { Set-Location (Get-Content $pathFile); Remove-Item $pathFile }
       }
     }
-    { $_ -in 'rm', 'mv', 'merge', 'clean' } {
+    { $_ -in 'rm', 'mv', 'rename', 'merge', 'clean', 'snap-abort' } {
       & $wtBin.Source @args --path-file $pathFile
       if ($LASTEXITCODE -ne 0) { Remove-Item $pathFile -ErrorAction SilentlyContinue; return $LASTEXITCODE }
       if (Test-Path $pathFile) { Set-Location (Get-Content $pathFile); Remove-Item $pathFile }
@@ -411,6 +470,80 @@ if ($_wtBin) {
 Remove-Variable _wtBin -ErrorAction SilentlyContinue
 # === agent-worktree END ==="#;
 
+// Nushell has no `eval`: the snap command runs through `nu -c` so quoted
+// arguments behave the same as in the POSIX wrappers. Externals that exit
+// non-zero may raise an error (newer nu) or only set LAST_EXIT_CODE (older
+// nu); every call is wrapped in `try` and then checks the exit code.
+const NU_WRAPPER: &str = r#"# === agent-worktree BEGIN ===
+def --env --wrapped wt [...args: string] {
+  let wt_bins = (which -a wt | where type == "external")
+  if ($wt_bins | is-empty) {
+    print -e "wt: binary not found. Install: npm install -g agent-worktree"
+    return
+  }
+  let wt_bin = ($wt_bins | first | get path)
+  if ("-h" in $args) or ("--help" in $args) {
+    ^$wt_bin ...$args
+    return
+  }
+  let path_file = (mktemp -t)
+  let subcommand = ($args | get 0? | default "")
+  if $subcommand in ["cd" "rm" "mv" "rename" "merge" "clean" "snap-abort" "new" "snap"] {
+    try { ^$wt_bin ...$args --path-file $path_file } catch { }
+    if $env.LAST_EXIT_CODE != 0 {
+      rm -f $path_file
+      return
+    }
+    if not ($path_file | path exists) { return }
+    let lines = (open --raw $path_file | lines)
+    rm -f $path_file
+    let target_path = ($lines | get 0? | default "")
+    let snap_cmd = if ($subcommand == "snap") or (($subcommand == "new") and (("-s" in $args) or ("--snap" in $args))) {
+      $lines | get 1? | default ""
+    } else {
+      ""
+    }
+    let snap_cmd = if $snap_cmd == $target_path { "" } else { $snap_cmd }
+    if $target_path != "" { cd $target_path }
+    if $snap_cmd != "" {
+      mut reopen_count = 0
+      loop {
+        if $reopen_count > 0 {
+          print $"[wt] Reopen #($reopen_count)"
+        }
+        print $"Entering snap mode: ($snap_cmd)"
+        print $"Worktree: ($target_path | path basename)"
+        print "---"
+        try { ^$nu.current-exe -c $snap_cmd } catch { }
+        let agent_status = $env.LAST_EXIT_CODE
+        if $agent_status != 0 {
+          print $"[wt] Agent exited with status ($agent_status); checking worktree state..."
+        }
+        try { ^$wt_bin snap-continue --path-file $path_file } catch { }
+        let continue_status = $env.LAST_EXIT_CODE
+        # 0: done, cd to main; 2: reopen agent; 3: exit, stay in worktree
+        if $continue_status == 0 {
+          if ($path_file | path exists) {
+            cd (open --raw $path_file | str trim)
+            rm -f $path_file
+          }
+          break
+        } else if $continue_status == 2 {
+          rm -f $path_file
+          $reopen_count += 1
+        } else {
+          rm -f $path_file
+          break
+        }
+      }
+    }
+  } else {
+    rm -f $path_file
+    ^$wt_bin ...$args
+  }
+}
+# === agent-worktree END ==="#;
+
 // Fish completions go to a dedicated file (auto-sourced by fish)
 const FISH_COMPLETIONS: &str = r#"# Dynamic completions for wt (auto-generated by wt setup)
 set -l _wt_bin (type --force-path wt 2>/dev/null)
@@ -19,6 +19,17 @@ fn test_shell_from_path() {
     assert_eq!(Shell::from_path("/bin/sh"), None);
 }
 
+#[test]
+fn test_shell_from_path_nu() {
+    assert_eq!(Shell::from_path("/usr/local/bin/nu"), Some(Shell::Nu));
+    assert_eq!(
+        Shell::from_path("C:\\Users\\me\\.cargo\\bin\\nu.exe"),
+        Some(Shell::Nu)
+    );
+    // Only the file name counts, not any path ending in "nu"
+    assert_eq!(Shell::from_path("/usr/bin/menu"), None);
+}
+
 #[test]
 fn test_shell_from_path_case_insensitive() {
     assert_eq!(Shell::from_path("/bin/BASH"), Some(Shell::Bash));
@@ -35,6 +46,19 @@ fn test_shell_from_path_powershell_variants() {
     );
 }
 
+#[test]
+fn test_shell_from_process_name() {
+    assert_eq!(Shell::from_process_name("zsh"), Some(Shell::Zsh));
+    assert_eq!(Shell::from_process_name("-bash"), Some(Shell::Bash));
+    assert_eq!(Shell::from_process_name("-nu\n"), Some(Shell::Nu));
+    assert_eq!(
+        Shell::from_process_name("/opt/homebrew/bin/fish"),
+        Some(Shell::Fish)
+    );
+    assert_eq!(Shell::from_process_name("node"), None);
+    assert_eq!(Shell::from_process_name(""), None);
+}
+
 // =========================================================================
 // Shell::from_name tests
 // =========================================================================
@@ -45,6 +69,8 @@ fn test_shell_from_name() {
     assert_eq!(Shell::from_name("Fish"), Some(Shell::Fish));
     assert_eq!(Shell::from_name("powershell"), Some(Shell::PowerShell));
     assert_eq!(Shell::from_name("pwsh"), Some(Shell::PowerShell));
+    assert_eq!(Shell::from_name("nu"), Some(Shell::Nu));
+    assert_eq!(Shell::from_name("Nushell"), Some(Shell::Nu));
     assert_eq!(Shell::from_name("ksh"), None);
 }
 
@@ -107,6 +133,14 @@ fn test_shell_config_file_powershell() {
         .contains("Microsoft.PowerShell_profile.ps1"));
 }
 
+#[test]
+fn test_shell_config_file_nu() {
+    let config = Shell::Nu.config_file();
+    assert!(config.is_ok());
+    let path = config.unwrap();
+    assert!(path.to_string_lossy().contains("config.nu"));
+}
+
 // =========================================================================
 // Shell::wrapper_script tests
 // =========================================================================
@@ -123,6 +157,10 @@ fn test_wrapper_script_contains_markers() {
     let ps_wrapper = Shell::PowerShell.wrapper_script();
     assert!(ps_wrapper.contains(MARKER_BEGIN));
     assert!(ps_wrapper.contains(MARKER_END));
+
+    let nu_wrapper = Shell::Nu.wrapper_script();
+    assert!(nu_wrapper.contains(MARKER_BEGIN));
+    assert!(nu_wrapper.contains(MARKER_END));
 }
 
 #[test]
@@ -156,6 +194,61 @@ fn test_wrapper_script_handles_cd_command() {
     assert!(ps.contains("'cd'"));
 }
 
+#[test]
+fn test_wrapper_script_handles_snap_command() {
+    let bash = Shell::Bash.wrapper_script();
+    assert!(bash.contains("new|snap)"));
+    assert!(bash.contains(r#"[[ "$1" == snap ]]"#));
+
+    let fish = Shell::Fish.wrapper_script();
+    assert!(fish.contains("case new snap"));
+
+    let ps = Shell::PowerShell.wrapper_script();
+    assert!(ps.contains("$args[0] -eq 'snap'"));
+
+    let nu = Shell::Nu.wrapper_script();
+    assert!(nu.contains(r#"($subcommand == "snap")"#));
+}
+
+/// Every expansion of `var` in `script` must sit inside double quotes.
+fn assert_var_quoted(script: &str, var: &str) {
+    for (idx, _) in script.match_indices(var) {
+        let prev = script[..idx].chars().last();
+        assert_eq!(
+            prev,
+            Some('"'),
+            "unquoted {var} in: {}",
+            script[idx.saturating_sub(40)..]
+                .lines()
+                .next()
+                .unwrap_or("")
+        );
+    }
+}
+
+#[test]
+fn test_posix_wrappers_quote_temp_path() {
+    // $TMPDIR may contain spaces (macOS), so the temp path and the target
+    // read back from it must never be word-split.
+    for script in [Shell::Bash.wrapper_script(), Shell::Fish.wrapper_script()] {
+        assert_var_quoted(script, "$path_file");
+        assert_var_quoted(script, "$target_path");
+        for line in script.lines().filter(|l| l.contains("rm -f")) {
+            assert!(line.contains("rm -f -- \""), "rm without -- in: {line}");
+        }
+    }
+    let bash = Shell::Bash.wrapper_script();
+    for line in bash
+        .lines()
+        .filter(|l| l.contains("cd \"") || l.contains("cd -"))
+    {
+        assert!(
+            line.contains("cd -- \"$target_path\""),
+            "bare cd in: {line}"
+        );
+    }
+}
+
 // =========================================================================
 // remove_wrapper tests
 // =========================================================================
@@ -427,6 +520,16 @@ fn test_powershell_wrapper_script_syntax() {
     assert!(wrapper.contains("Set-Location"));
 }
 
+#[test]
+fn test_nu_wrapper_script_syntax() {
+    let wrapper = Shell::Nu.wrapper_script();
+    // Nushell needs `def --env` so `cd` survives the function call
+    assert!(wrapper.contains("def --env --wrapped wt"));
+    assert!(wrapper.contains("--path-file $path_file"));
+    assert!(wrapper.contains("snap-continue"));
+    assert!(wrapper.contains("cd $target_path"));
+}
+
 // =========================================================================
 // Shell equality and clone tests
 // =========================================================================
@@ -2,14 +2,25 @@
 // update - Version Update Check
 // ===========================================================================
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::path::Path;
 use std::time::{Duration, SystemTime};
 
+pub use crate::config::UpdateChannel;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60); // 24 hours
 const MARKER_FILE: &str = "last_update_check";
 
+/// Upper bound for the registry request; also caps how long `main` waits
+/// for the background check before exiting.
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Set (to anything but empty / "0") to disable the background check.
+pub const NO_UPDATE_ENV: &str = "AGENT_WORKTREE_NO_UPDATE";
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("network error: {0}")]
@@ -23,7 +34,29 @@ pub enum Error {
 }
 
 /// Check if we should perform update check (once per day)
+///
+/// Opt out with `AGENT_WORKTREE_NO_UPDATE=1` or `check_updates = false`
+/// under `[general]` in the global config.
 pub fn should_check(base_dir: &Path) -> bool {
+    should_check_with(base_dir, std::env::var(NO_UPDATE_ENV).ok().as_deref())
+}
+
+// Split out so tests can cover the opt-out without mutating process-global
+// env state (unsafe + racy under parallel tests).
+fn should_check_with(base_dir: &Path, no_update_env: Option<&str>) -> bool {
+    if no_update_env.is_some_and(|v| !v.is_empty() && v != "0") {
+        return false;
+    }
+    // An unreadable config shouldn't silence the check; `wt` reports the
+    // parse error itself when it loads the config for the command.
+    let enabled = crate::config::Config::load_global(base_dir)
+        .ok()
+        .and_then(|g| g.general.check_updates)
+        .unwrap_or(true);
+    if !enabled {
+        return false;
+    }
+
     let marker = base_dir.join(MARKER_FILE);
     if !marker.exists() {
         return true;
@@ -36,6 +69,15 @@ pub fn should_check(base_dir: &Path) -> bool {
         .unwrap_or(true)
 }
 
+/// Update channel from `[general] update_channel` in the global config
+/// (unreadable config = stable)
+pub fn channel(base_dir: &Path) -> UpdateChannel {
+    crate::config::Config::load_global(base_dir)
+        .ok()
+        .and_then(|g| g.general.update_channel)
+        .unwrap_or_default()
+}
+
 /// Mark that we've checked for updates
 pub fn mark_checked(base_dir: &Path) -> Result<()> {
     let marker = base_dir.join(MARKER_FILE);
@@ -44,56 +86,134 @@ pub fn mark_checked(base_dir: &Path) -> Result<()> {
 }
 
 /// Compare versions: returns true if latest > current
+///
+/// Semver precedence: `1.0.0-rc.1 < 1.0.0`, `1.0.0-alpha < 1.0.0-beta`.
+/// Build metadata (`+...`) is ignored.
 pub fn compare_versions(current: &str, latest: &str) -> bool {
+    cmp_versions(latest, current) == Ordering::Greater
+}
+
+fn cmp_versions(a: &str, b: &str) -> Ordering {
+    // 快速路径：纯数字版本无需拆 pre-release
+    if !a.contains(['-', '+']) && !b.contains(['-', '+']) {
+        return cmp_core(a, b);
+    }
+    let (a_core, a_pre) = split_version(a);
+    let (b_core, b_pre) = split_version(b);
+    cmp_core(a_core, b_core).then_with(|| cmp_pre(a_pre, b_pre))
+}
+
+/// "1.2.3-rc.1+build" -> ("1.2.3", Some("rc.1"))
+fn split_version(v: &str) -> (&str, Option<&str>) {
+    let v = v.split_once('+').map_or(v, |(v, _)| v);
+    match v.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (v, None),
+    }
+}
+
+fn cmp_core(a: &str, b: &str) -> Ordering {
     let parse = |v: &str| -> Vec<u32> { v.split('.').filter_map(|s| s.parse().ok()).collect() };
 
-    let current_parts = parse(current);
-    let latest_parts = parse(latest);
+    let a_parts = parse(a);
+    let b_parts = parse(b);
 
-    for i in 0..current_parts.len().max(latest_parts.len()) {
-        let c = current_parts.get(i).copied().unwrap_or(0);
-        let l = latest_parts.get(i).copied().unwrap_or(0);
-        if l > c {
-            return true;
+    for i in 0..a_parts.len().max(b_parts.len()) {
+        let a = a_parts.get(i).copied().unwrap_or(0);
+        let b = b_parts.get(i).copied().unwrap_or(0);
+        match a.cmp(&b) {
+            Ordering::Equal => continue,
+            other => return other,
         }
-        if l < c {
-            return false;
+    }
+    Ordering::Equal
+}
+
+/// Pre-release 比较：无 pre-release 的正式版更大；标识逐段比较，
+/// 数字段按数值、数字段 < 字母段，其余按字典序，前缀相同时段数少的更小
+fn cmp_pre(a: Option<&str>, b: Option<&str>) -> Ordering {
+    let (a, b) = match (a, b) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(a), Some(b)) => (a, b),
+    };
+
+    let mut a_ids = a.split('.');
+    let mut b_ids = b.split('.');
+    loop {
+        let ord = match (a_ids.next(), b_ids.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+        if ord != Ordering::Equal {
+            return ord;
         }
     }
-    false
+}
+
+/// Highest version across npm dist-tags (`latest`, `next`, `beta`, ...)
+fn newest_tagged(tags: &HashMap<String, String>) -> Option<&str> {
+    tags.values()
+        .map(String::as_str)
+        .max_by(|a, b| cmp_versions(a, b))
 }
 
 /// Check for updates from npm registry
 /// Returns Some(latest_version) if update available, None otherwise
-pub fn check_update(current_version: &str) -> Result<Option<String>> {
-    let url = "https://registry.npmjs.org/agent-worktree/latest";
+///
+/// Stable reads the `latest` dist-tag; prerelease considers every dist-tag.
+pub fn check_update(current_version: &str, channel: UpdateChannel) -> Result<Option<String>> {
+    let latest = match channel {
+        UpdateChannel::Stable => {
+            // 解析 JSON 获取 version 字段
+            #[derive(serde::Deserialize)]
+            struct NpmPackage {
+                version: String,
+            }
+            let body = fetch("https://registry.npmjs.org/agent-worktree/latest")?;
+            let pkg: NpmPackage =
+                serde_json::from_str(&body).map_err(|e| Error::Parse(e.to_string()))?;
+            pkg.version
+        }
+        UpdateChannel::Prerelease => {
+            let body = fetch("https://registry.npmjs.org/-/package/agent-worktree/dist-tags")?;
+            let tags: HashMap<String, String> =
+                serde_json::from_str(&body).map_err(|e| Error::Parse(e.to_string()))?;
+            newest_tagged(&tags)
+                .ok_or_else(|| Error::Parse("no dist-tags published".into()))?
+                .to_string()
+        }
+    };
+
+    if compare_versions(current_version, &latest) {
+        Ok(Some(latest))
+    } else {
+        Ok(None)
+    }
+}
 
+fn fetch(url: &str) -> Result<String> {
     let agent = ureq::Agent::new_with_config(
         ureq::config::Config::builder()
-            .timeout_global(Some(std::time::Duration::from_secs(5)))
+            .timeout_global(Some(CHECK_TIMEOUT))
             .build(),
     );
 
-    let body: String = agent
+    agent
         .get(url)
         .call()
         .map_err(|e| Error::Network(e.to_string()))?
         .body_mut()
         .read_to_string()
-        .map_err(|e| Error::Parse(e.to_string()))?;
-
-    // 解析 JSON 获取 version 字段
-    #[derive(serde::Deserialize)]
-    struct NpmPackage {
-        version: String,
-    }
-    let pkg: NpmPackage = serde_json::from_str(&body).map_err(|e| Error::Parse(e.to_string()))?;
-
-    if compare_versions(current_version, &pkg.version) {
-        Ok(Some(pkg.version))
-    } else {
-        Ok(None)
-    }
+        .map_err(|e| Error::Parse(e.to_string()))
 }
 
 #[cfg(test)]
@@ -132,6 +252,38 @@ mod tests {
         assert!(should_check(temp.path()));
     }
 
+    #[test]
+    fn test_should_check_env_opt_out() {
+        let temp = TempDir::new().unwrap();
+        assert!(!should_check_with(temp.path(), Some("1")));
+        assert!(!should_check_with(temp.path(), Some("true")));
+    }
+
+    #[test]
+    fn test_should_check_env_empty_or_zero_ignored() {
+        let temp = TempDir::new().unwrap();
+        assert!(should_check_with(temp.path(), Some("")));
+        assert!(should_check_with(temp.path(), Some("0")));
+    }
+
+    #[test]
+    fn test_should_check_config_opt_out() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("config.toml"),
+            "[general]\ncheck_updates = false\n",
+        )
+        .unwrap();
+        assert!(!should_check_with(temp.path(), None));
+
+        std::fs::write(
+            temp.path().join("config.toml"),
+            "[general]\ncheck_updates = true\n",
+        )
+        .unwrap();
+        assert!(should_check_with(temp.path(), None));
+    }
+
     #[test]
     fn test_mark_checked_creates_marker() {
         let temp = TempDir::new().unwrap();
@@ -177,4 +329,51 @@ mod tests {
         // Large numbers
         assert!(compare_versions("0.9.9", "0.10.0"));
     }
+
+    #[test]
+    fn test_compare_versions_prerelease_below_release() {
+        assert!(compare_versions("1.0.0-rc.1", "1.0.0"));
+        assert!(!compare_versions("1.0.0", "1.0.0-rc.1"));
+        // A pre-release of the next version still beats the current release
+        assert!(compare_versions("0.9.0", "1.0.0-rc.1"));
+    }
+
+    #[test]
+    fn test_compare_versions_prerelease_precedence() {
+        assert!(compare_versions("1.0.0-alpha", "1.0.0-beta"));
+        assert!(compare_versions("1.0.0-alpha", "1.0.0-alpha.1"));
+        assert!(compare_versions("1.0.0-alpha.1", "1.0.0-alpha.beta"));
+        assert!(compare_versions("1.0.0-beta.2", "1.0.0-beta.11"));
+        assert!(!compare_versions("1.0.0-beta.1", "1.0.0-beta.1"));
+    }
+
+    #[test]
+    fn test_compare_versions_ignores_build_metadata() {
+        assert!(!compare_versions("1.0.0+a", "1.0.0+b"));
+        assert!(compare_versions("1.0.0-rc.1+a", "1.0.0"));
+    }
+
+    #[test]
+    fn test_newest_tagged_picks_highest() {
+        let tags = HashMap::from([
+            ("latest".to_string(), "0.5.0".to_string()),
+            ("next".to_string(), "0.6.0-beta.2".to_string()),
+            ("old".to_string(), "0.6.0-alpha".to_string()),
+        ]);
+        assert_eq!(newest_tagged(&tags), Some("0.6.0-beta.2"));
+        assert_eq!(newest_tagged(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_channel_from_global_config() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(channel(temp.path()), UpdateChannel::Stable);
+
+        std::fs::write(
+            temp.path().join("config.toml"),
+            "[general]\nupdate_channel = \"prerelease\"\n",
+        )
+        .unwrap();
+        assert_eq!(channel(temp.path()), UpdateChannel::Prerelease);
+    }
 }
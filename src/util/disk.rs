@@ -0,0 +1,110 @@
+// ===========================================================================
+// Disk Usage
+// ===========================================================================
+//
+// Size of the workspaces directory and free space on its filesystem, so
+// `wt df` / `wt new` can warn before agent worktrees fill a small disk.
+
+use std::path::Path;
+
+pub const GIB: u64 = 1024 * 1024 * 1024;
+
+/// Total size in bytes of regular files under `path`.
+///
+/// Symlinks are not followed (a `post_create` symlink to the main repo's
+/// `node_modules` must not be counted against every worktree). Unreadable
+/// entries are skipped rather than failing the whole walk.
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(ft) if ft.is_dir() => dir_size(&entry.path()),
+            Ok(ft) if ft.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Free bytes available to the current user on the filesystem holding
+/// `path`. Walks up to the nearest existing ancestor so it works before the
+/// workspaces directory is first created. `None` when undeterminable.
+pub fn free_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    // statvfs on unix, GetDiskFreeSpaceExW on Windows
+    fs2::available_space(existing).ok()
+}
+
+/// Human-readable size using binary units ("512 B", "1.5 KiB", "2.0 GiB").
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_dir_size_empty() {
+        let dir = tempdir().unwrap();
+        assert_eq!(dir_size(dir.path()), 0);
+    }
+
+    #[test]
+    fn test_dir_size_missing() {
+        let dir = tempdir().unwrap();
+        assert_eq!(dir_size(&dir.path().join("nope")), 0);
+    }
+
+    #[test]
+    fn test_dir_size_nested() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a"), vec![0u8; 100]).unwrap();
+        std::fs::create_dir_all(dir.path().join("sub/deep")).unwrap();
+        std::fs::write(dir.path().join("sub/b"), vec![0u8; 20]).unwrap();
+        std::fs::write(dir.path().join("sub/deep/c"), vec![0u8; 3]).unwrap();
+        assert_eq!(dir_size(dir.path()), 123);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dir_size_skips_symlinks() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("target");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::write(target.join("big"), vec![0u8; 1000]).unwrap();
+        let wt = dir.path().join("wt");
+        std::fs::create_dir(&wt).unwrap();
+        std::os::unix::fs::symlink(&target, wt.join("link")).unwrap();
+        assert_eq!(dir_size(&wt), 0);
+    }
+
+    #[test]
+    fn test_free_space_nonexistent_uses_ancestor() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("a/b/c");
+        assert!(free_space(&missing).is_some());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_bytes(2 * GIB), "2.0 GiB");
+    }
+}
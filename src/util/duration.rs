@@ -0,0 +1,51 @@
+// ===========================================================================
+// util/duration - Human duration arguments ("30m", "6h", "7d")
+// ===========================================================================
+
+use chrono::Duration;
+
+/// Parse `<N><unit>` with unit `m` (minutes), `h` (hours), `d` (days) or
+/// `w` (weeks). Shaped for clap's `value_parser`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let invalid = || format!("invalid duration '{s}' (expected e.g. 30m, 6h, 7d, 2w)");
+    let split = s
+        .len()
+        .checked_sub(1)
+        .filter(|&i| s.is_char_boundary(i))
+        .ok_or_else(invalid)?;
+    let (n, unit) = s.split_at(split);
+    let n: i64 = n.parse().map_err(|_| invalid())?;
+    if n < 0 {
+        return Err(invalid());
+    }
+    let duration = match unit {
+        "m" => Duration::try_minutes(n),
+        "h" => Duration::try_hours(n),
+        "d" => Duration::try_days(n),
+        "w" => Duration::try_weeks(n),
+        _ => None,
+    };
+    duration.ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_each_unit() {
+        assert_eq!(parse_duration("30m"), Ok(Duration::minutes(30)));
+        assert_eq!(parse_duration("6h"), Ok(Duration::hours(6)));
+        assert_eq!(parse_duration("7d"), Ok(Duration::days(7)));
+        assert_eq!(parse_duration("2w"), Ok(Duration::weeks(2)));
+        assert_eq!(parse_duration(" 0d "), Ok(Duration::zero()));
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        for bad in ["", "d", "7", "7x", "-1d", "1.5h", "7 d", "天"] {
+            assert!(parse_duration(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+}
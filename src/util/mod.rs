@@ -3,5 +3,11 @@
 // ===========================================================================
 
 mod branch_name;
+mod disk;
+mod duration;
+mod output;
 
 pub use branch_name::{generate_branch_name, generate_unique_branch_name};
+pub use disk::{dir_size, format_bytes, free_space, GIB};
+pub use duration::parse_duration;
+pub use output::{is_quiet, is_verbose, set_quiet, set_verbose};
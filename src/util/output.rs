@@ -0,0 +1,29 @@
+// ===========================================================================
+// util/output - Output switches (wt --quiet / --verbose)
+// ===========================================================================
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once from the global `--quiet` flag before any command runs.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Set once from the global `--verbose` flag before any command runs.
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Silence every subsequent `progress!` line (`wt -q`).
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Echo every git command line to stderr (`wt -v`).
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
@@ -0,0 +1,777 @@
+// ===========================================================================
+// workspace - Library facade over one repo's managed worktrees
+// ===========================================================================
+//
+// The core of new / ls / rm / merge without stdout, stderr or path-file
+// side effects, so the crate can be embedded (e.g. in a TUI). The CLI
+// commands wrap it and add hooks, prompts and messages on top.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{locate_worktree, worktree_path, Error, Result};
+use crate::config::{Config, MergeStrategy};
+use crate::git::{self, WorktreeInfo};
+use crate::meta::{self, WorktreeMeta};
+
+/// Managed worktrees of the repo containing the cwd
+pub struct Workspace {
+    config: Config,
+    repo_root: PathBuf,
+    wt_dir: PathBuf,
+}
+
+/// Held while a worktree is being created; see `Workspace::lock_create`.
+/// Dropping it (or the process dying) releases the lock.
+pub struct CreateLock {
+    _file: std::fs::File,
+}
+
+/// Result of `Workspace::create_with`
+#[derive(Debug)]
+pub struct Created {
+    pub path: PathBuf,
+    /// One "<repo-relative path>: <error>" per copy_files entry not copied;
+    /// the worktree itself is usable regardless.
+    pub copy_failures: Vec<String>,
+}
+
+impl Workspace {
+    /// Workspace of the repo containing the current directory.
+    pub fn open(config: Config) -> Result<Self> {
+        let repo_root = git::repo_root()?;
+        let wt_dir = config.wt_dir()?;
+        Ok(Self {
+            config,
+            repo_root,
+            wt_dir,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Main repo root
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
+    /// `{workspaces_dir}/{workspace_id}`: worktrees and their meta files
+    pub fn wt_dir(&self) -> &Path {
+        &self.wt_dir
+    }
+
+    /// Worktrees living under the workspace dir, in `git worktree list` order.
+    pub fn list(&self) -> Result<Vec<WorktreeInfo>> {
+        Ok(git::list_worktrees()?
+            .into_iter()
+            .filter(|wt| wt.path.starts_with(&self.wt_dir))
+            .collect())
+    }
+
+    /// Serialize worktree creation across `wt` processes (parallel agents):
+    /// blocks until no other process holds `{wt_dir}/.lock`. Hold it from
+    /// picking the branch name until the worktree exists, so two runs can't
+    /// both see a name as free.
+    pub fn lock_create(&self) -> Result<CreateLock> {
+        std::fs::create_dir_all(&self.wt_dir).map_err(|e| Error::Other(e.to_string()))?;
+        let path = self.wt_dir.join(".lock");
+        let file = std::fs::File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| Error::Other(format!("{}: {e}", path.display())))?;
+        file.lock()
+            .map_err(|e| Error::Other(format!("Cannot lock {}: {e}", path.display())))?;
+        Ok(CreateLock { _file: file })
+    }
+
+    /// New worktree for `branch` from `base` (default: the trunk for
+    /// `branch`), with meta and the configured copy_files. No hooks run.
+    pub fn create(&self, branch: &str, base: Option<&str>) -> Result<PathBuf> {
+        git::validate_branch_name(branch)?;
+        let base = base
+            .map(str::to_string)
+            .unwrap_or_else(|| self.config.resolve_trunk_for(branch));
+        if !git::branch_exists(&base)? {
+            return Err(Error::Other(format!("Branch '{base}' does not exist")));
+        }
+        if branch == base {
+            return Err(Error::Other(format!(
+                "Cannot create '{branch}' from itself"
+            )));
+        }
+        let _lock = self.lock_create()?;
+        let created = self.create_with(branch, WorktreeMeta::new(base), &self.config.copy_files)?;
+        Ok(created.path)
+    }
+
+    /// Worktree at the managed path for `branch`, checked out from
+    /// `meta.base_branch`, plus its meta file and the `copy` patterns
+    /// copied from the main repo.
+    pub fn create_with(
+        &self,
+        branch: &str,
+        meta: WorktreeMeta,
+        copy: &[String],
+    ) -> Result<Created> {
+        let start = meta.base_branch.clone();
+        self.create_from(branch, &start, meta, copy)
+    }
+
+    /// Like `create_with`, but checked out from `start` (e.g.
+    /// `origin/main`); `meta.base_branch` stays the merge target.
+    pub fn create_from(
+        &self,
+        branch: &str,
+        start: &str,
+        meta: WorktreeMeta,
+        copy: &[String],
+    ) -> Result<Created> {
+        let path = worktree_path(&self.wt_dir, branch)?;
+        std::fs::create_dir_all(&self.wt_dir).map_err(|e| Error::Other(e.to_string()))?;
+
+        git::create_worktree(&path, branch, start)?;
+
+        meta.save(&meta::meta_path(&self.wt_dir, branch))
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let copy_failures = copy_files(
+            &self.repo_root,
+            &path,
+            copy,
+            self.config.copy_respect_gitignore,
+        )?;
+        Ok(Created {
+            path,
+            copy_failures,
+        })
+    }
+
+    /// Remove `branch`'s worktree, its meta, and (best-effort) the branch.
+    /// Returns the removed path.
+    ///
+    /// A cwd inside that worktree moves to the repo root first: git can't
+    /// run from a deleted directory.
+    pub fn remove(&self, branch: &str, force: bool) -> Result<PathBuf> {
+        let path = locate_worktree(&self.wt_dir, branch)?;
+        if git::is_cwd_inside(&path) {
+            std::env::set_current_dir(&self.repo_root).map_err(|e| Error::Other(e.to_string()))?;
+        }
+
+        git::remove_worktree(&path, force)?;
+        // Unmerged branches survive a non-force remove, like `git branch -d`
+        let _ = git::delete_branch_in(&self.repo_root, branch, force);
+        meta::remove_meta(&self.wt_dir, branch);
+        Ok(path)
+    }
+
+    /// Branch `branch` merges into: `into` when given, else its meta base
+    /// branch while that still exists, else the trunk for `branch`.
+    pub fn merge_target(&self, branch: &str, into: Option<&str>) -> String {
+        meta::resolve_effective_target(
+            &self.wt_dir,
+            branch,
+            into,
+            |b| git::branch_exists(b).unwrap_or(false),
+            &self.config.resolve_trunk_for(branch),
+        )
+    }
+
+    /// Where a merge into `target` runs. git refuses to check out a branch
+    /// another worktree owns, so a target held elsewhere is merged in place,
+    /// in that worktree; otherwise in the main repo.
+    ///
+    /// Errors when git still records such a worktree but its directory is
+    /// gone: the branch can be checked out neither there nor (until pruned)
+    /// in the main repo.
+    pub fn merge_site(&self, target: &str) -> Result<MergeSite> {
+        let Some(wt) = git::find_worktree_by_branch(target)? else {
+            return Ok(MergeSite {
+                dir: self.repo_root.clone(),
+                worktree: None,
+            });
+        };
+        if !wt.path.is_dir() {
+            return Err(Error::Other(format!(
+                "Branch '{target}' is checked out in a worktree that no longer exists ({}).\n\
+                 Run 'git worktree prune' so the main repo can check it out, then retry.",
+                wt.path.display()
+            )));
+        }
+        Ok(MergeSite {
+            dir: wt.path.clone(),
+            worktree: Some(wt.path),
+        })
+    }
+
+    /// Merge `branch` into its merge target (see `merge_target`). Returns
+    /// false when there was nothing to merge.
+    ///
+    /// Unlike `wt merge` this runs no hooks, never autostashes and leaves
+    /// the worktree in place. Conflicts are `Error::MergeConflict`; see
+    /// `merge_into` for where HEAD ends up.
+    pub fn merge(&self, branch: &str, strategy: MergeStrategy) -> Result<bool> {
+        let target = self.merge_target(branch, None);
+        if branch == target {
+            return Err(Error::MergeIntoTrunk(target));
+        }
+        match self.merge_into(branch, &target, strategy, &CommitOverrides::default())? {
+            MergeResult::Merged => Ok(true),
+            MergeResult::UpToDate => Ok(false),
+            MergeResult::Conflicts(_) => Err(Error::MergeConflict),
+        }
+    }
+
+    /// Merge `branch` into `target` at `merge_site(target)`, which must be
+    /// clean. All git work runs with `-C`; the process cwd is left alone.
+    ///
+    /// A merged main repo is left on `target`. On conflicts, up to date, or
+    /// any failure, nothing is merged and HEAD is back where it was; a
+    /// process killed in between leaves a marker for `abort_merge`.
+    pub fn merge_into(
+        &self,
+        branch: &str,
+        target: &str,
+        strategy: MergeStrategy,
+        commit: &CommitOverrides,
+    ) -> Result<MergeResult> {
+        let site = self.merge_site(target)?;
+        site.check_idle()?;
+        let dir = site.dir.as_path();
+        if git::uncommitted_count_in(dir)? > 0 {
+            return Err(Error::UncommittedChanges(site.location()));
+        }
+
+        // A target worktree is already on `target`; its HEAD never moves.
+        // In the main repo, record the way back (a detached HEAD goes back
+        // to its commit) before checking the target out.
+        let mut _marker = None;
+        let original = match site.worktree {
+            Some(_) => None,
+            None => {
+                let orig = git::head_state_in(dir)?.checkout_ref().to_string();
+                let path = meta::merge_prev_path(&self.wt_dir, branch);
+                _marker = Some(PrevBranchMarker::write(path, &orig)?);
+                git::checkout_in(dir, target)?;
+                Some(orig)
+            }
+        };
+        let restore = || {
+            if let Some(orig) = &original {
+                let _ = git::checkout_in(dir, orig);
+            }
+        };
+
+        // A fast-forward can't conflict; `execute_merge` checks divergence.
+        if strategy != MergeStrategy::FfOnly {
+            match git::dry_run_merge_conflicts_in(dir, branch, strategy.is_squash()) {
+                Ok(None) => {}
+                Ok(Some(conflicts)) => {
+                    restore();
+                    return Ok(MergeResult::Conflicts(conflicts));
+                }
+                Err(e) => {
+                    restore();
+                    return Err(e.into());
+                }
+            }
+        }
+
+        match execute_merge(dir, branch, target, strategy, commit) {
+            Ok(true) => Ok(MergeResult::Merged),
+            Ok(false) => {
+                restore();
+                Ok(MergeResult::UpToDate)
+            }
+            Err(e) => {
+                // Rare after a clean dry run; list unmerged paths before
+                // the rollback discards them.
+                let conflicts = git::conflicted_files_in(dir).unwrap_or_default();
+                let _ = git::reset_merge_in(dir);
+                restore();
+                if conflicts.is_empty() {
+                    Err(e)
+                } else {
+                    Ok(MergeResult::Conflicts(conflicts))
+                }
+            }
+        }
+    }
+
+    /// Undo what a killed merge of `branch` left in the main repo (staged
+    /// squash, MERGE_HEAD, HEAD on the target) and check out the branch
+    /// recorded before it started. Returns that branch.
+    pub fn abort_merge(&self, branch: &str) -> Result<String> {
+        let marker = meta::merge_prev_path(&self.wt_dir, branch);
+        let prev = match std::fs::read_to_string(&marker) {
+            Ok(content) => content.trim().to_string(),
+            Err(_) => {
+                return Err(Error::Other(format!(
+                    "No interrupted wt merge of '{branch}' to abort"
+                )))
+            }
+        };
+
+        git::reset_merge_in(&self.repo_root)?;
+        git::checkout_in(&self.repo_root, &prev)?;
+        let _ = std::fs::remove_file(&marker);
+        Ok(prev)
+    }
+}
+
+/// Where a merge runs; see `Workspace::merge_site`
+#[derive(Debug)]
+pub struct MergeSite {
+    pub dir: PathBuf,
+    /// Set when `dir` is the target's own worktree, not the main repo
+    pub worktree: Option<PathBuf>,
+}
+
+impl MergeSite {
+    /// "Main repo" or "Worktree at <path>", for messages
+    pub fn location(&self) -> String {
+        match &self.worktree {
+            Some(path) => format!("Worktree at {}", path.display()),
+            None => "Main repo".to_string(),
+        }
+    }
+
+    /// No merge or rebase already under way there
+    pub fn check_idle(&self) -> Result<()> {
+        if git::is_merge_in_progress_in(&self.dir) {
+            return Err(Error::Other(format!(
+                "{} has a merge in progress.",
+                self.location()
+            )));
+        }
+        if git::is_rebase_in_progress_in(&self.dir) {
+            return Err(Error::Other(format!(
+                "{} has a rebase in progress.",
+                self.location()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How `Workspace::merge_into` ended
+#[derive(Debug, PartialEq, Eq)]
+pub enum MergeResult {
+    /// New commits landed on the target
+    Merged,
+    /// Nothing to merge: the target already has the branch
+    UpToDate,
+    /// Nothing merged; the conflicting paths (empty when git listed none)
+    Conflicts(Vec<String>),
+}
+
+/// Overrides for the commit a merge creates (`wt merge --author` /
+/// `--date` / `--no-verify` / `--edit`).
+#[derive(Default)]
+pub struct CommitOverrides<'a> {
+    pub author: Option<&'a str>,
+    pub date: Option<&'a str>,
+    pub no_verify: bool,
+    /// Rewrites the generated squash message before committing
+    pub edit: Option<&'a dyn Fn(&str) -> String>,
+}
+
+/// `meta::merge_prev_path`: the branch the main repo was on before a merge
+/// checked out the target. Keyed by the branch being merged and kept out of
+/// the shared `.git`, so merges from other worktrees can't clobber it.
+struct PrevBranchMarker {
+    path: PathBuf,
+}
+
+impl PrevBranchMarker {
+    fn write(path: PathBuf, branch: &str) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| Error::Other(e.to_string()))?;
+        }
+        std::fs::write(&path, format!("{branch}\n")).map_err(|e| Error::Other(e.to_string()))?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for PrevBranchMarker {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Build commit message for squash merge
+///
+/// - Single commit: use that commit's message directly
+/// - Multiple commits: "Merge branch 'x'" + list all commits
+/// - No commits: "Merge branch 'x'"
+pub fn build_merge_message(branch: &str, log: &str) -> String {
+    let lines: Vec<&str> = log.lines().filter(|l| !l.is_empty()).collect();
+
+    match lines.len() {
+        0 => format!("Merge branch '{branch}'"),
+        1 => {
+            // Single commit → strip hash prefix, use message directly
+            let line = lines[0];
+            line.split_once(' ')
+                .map(|(_, msg)| msg.to_string())
+                .unwrap_or_else(|| format!("Merge branch '{branch}'"))
+        }
+        _ => {
+            let mut msg = format!("Merge branch '{branch}'\n\n");
+            for line in &lines {
+                msg.push_str(&format!("* {line}\n"));
+            }
+            msg.trim_end().to_string()
+        }
+    }
+}
+
+/// `ff-only` needs `target` to be an ancestor of `branch`, i.e. no commits
+/// on `target` that `branch` lacks.
+pub fn check_fast_forward(branch: &str, target: &str) -> Result<()> {
+    if git::is_ancestor(target, branch)? {
+        return Ok(());
+    }
+    let behind = git::commit_count(branch, target)?;
+    Err(Error::Other(format!(
+        "Cannot fast-forward {target} to {branch}: {target} has {behind} commit(s) \
+         {branch} lacks.\n\
+         Run 'wt sync' (rebase) first, or merge with -s squash / -s merge."
+    )))
+}
+
+/// Execute squash/merge/ff-only in `repo`. `repo` must already be on trunk.
+///
+/// Returns true if changes were merged, false if already up to date.
+fn execute_merge(
+    repo: &Path,
+    branch: &str,
+    trunk: &str,
+    strategy: MergeStrategy,
+    overrides: &CommitOverrides,
+) -> Result<bool> {
+    let log = git::log_oneline(trunk, branch).unwrap_or_default();
+    let msg = build_merge_message(branch, &log);
+
+    match strategy {
+        MergeStrategy::Squash => {
+            git::merge_in(repo, branch, true, false, None, overrides.no_verify)?;
+            if git::has_staged_changes_in(repo)? {
+                let msg = match overrides.edit {
+                    Some(edit) => edit(&msg),
+                    None => msg,
+                };
+                git::commit_with_in(
+                    repo,
+                    &msg,
+                    overrides.author,
+                    overrides.date,
+                    overrides.no_verify,
+                )?;
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        }
+        MergeStrategy::Merge => {
+            // Detect "already up to date" before invoking git: when there
+            // are no commits ahead, `git merge --no-ff` succeeds silently
+            // without producing a merge commit. Returning Ok(true) in that
+            // case would print "Merge complete" and (with -d) cleanup a
+            // worktree even though nothing happened — caller relies on the
+            // bool to know whether to proceed.
+            if git::is_ancestor(branch, trunk)? {
+                return Ok(false);
+            }
+            git::merge_in(repo, branch, false, true, Some(&msg), overrides.no_verify)?;
+            Ok(true)
+        }
+        MergeStrategy::FfOnly => {
+            // Same up-to-date guard as Merge: `--ff-only` also succeeds
+            // as a no-op.
+            if git::is_ancestor(branch, trunk)? {
+                return Ok(false);
+            }
+            check_fast_forward(branch, trunk)?;
+            git::merge_ff_only_in(repo, branch)?;
+            Ok(true)
+        }
+    }
+}
+
+/// Reject patterns that could escape the repo root.
+///
+/// Without this guard, a malicious `.agent-worktree.toml` could exfiltrate
+/// host files into the worktree via `/abs/path` or `..` traversal — the
+/// downstream `strip_prefix` would silently skip mismatches.
+fn validate_copy_pattern(pattern: &str) -> Result<()> {
+    if pattern.starts_with('/') {
+        return Err(Error::Other(format!(
+            "copy_files pattern '{pattern}' cannot start with '/' (absolute path)"
+        )));
+    }
+    if pattern.split(['/', '\\']).any(|seg| seg == "..") {
+        return Err(Error::Other(format!(
+            "copy_files pattern '{pattern}' cannot contain '..'"
+        )));
+    }
+    Ok(())
+}
+
+/// Copy files matching `patterns` from the main repo; returns one message
+/// per file (or walk error) that could not be copied.
+///
+/// `.git` is never copied. With `respect_gitignore`, broad patterns (`*`,
+/// `config/*`) skip gitignored paths, so `["*"]` doesn't drag build output
+/// along; anything naming files (`.env`, `.env.*`, `*.secret`) is copied
+/// even when gitignored — that is usually why it is listed.
+fn copy_files(
+    from: &Path,
+    to: &Path,
+    patterns: &[String],
+    respect_gitignore: bool,
+) -> Result<Vec<String>> {
+    use ignore::WalkBuilder;
+
+    if patterns.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    for pattern in patterns {
+        validate_copy_pattern(pattern)?;
+    }
+
+    let (broad, names): (Vec<&String>, Vec<&String>) = patterns
+        .iter()
+        .partition(|p| respect_gitignore && is_broad(p));
+
+    // The worktree already exists at this point, so failures are collected
+    // rather than aborting.
+    let mut failures = Vec::new();
+    let mut copied = HashSet::new();
+
+    // follow_links=false: a symlink in the repo could otherwise pull files
+    // from outside the repo into the worktree.
+    if !names.is_empty() {
+        let walker = WalkBuilder::new(from)
+            .overrides(overrides(from, &names)?)
+            .standard_filters(false)
+            .follow_links(false)
+            .filter_entry(|e| e.file_name() != ".git")
+            .build();
+        copy_walk(walker, from, to, |_| true, &mut copied, &mut failures);
+    }
+    if !broad.is_empty() {
+        // Inside the walker an override match wins over .gitignore, so the
+        // patterns are matched here, on what the gitignore walk yields.
+        let matcher = overrides(from, &broad)?;
+        let walker = WalkBuilder::new(from)
+            .standard_filters(false)
+            .git_ignore(true)
+            .git_exclude(true)
+            .git_global(true)
+            .require_git(false)
+            .follow_links(false)
+            .filter_entry(|e| e.file_name() != ".git")
+            .build();
+        copy_walk(
+            walker,
+            from,
+            to,
+            |path| matcher.matched(path, false).is_whitelist(),
+            &mut copied,
+            &mut failures,
+        );
+    }
+
+    Ok(failures)
+}
+
+/// File-name part is wildcards only (`*`, `src/**/*`): matches whatever
+/// is there rather than files someone meant to list
+fn is_broad(pattern: &str) -> bool {
+    let name = pattern.rsplit('/').next().unwrap_or(pattern);
+    !name.is_empty() && name.chars().all(|c| matches!(c, '*' | '?'))
+}
+
+/// Gitignore-style matcher: "*.md" matches all .md files, "/*.md" only root
+fn overrides(root: &Path, patterns: &[&String]) -> Result<ignore::overrides::Override> {
+    let mut builder = ignore::overrides::OverrideBuilder::new(root);
+    for pattern in patterns {
+        builder
+            .add(pattern)
+            .map_err(|e| Error::Other(format!("invalid pattern '{}': {}", pattern, e)))?;
+    }
+    builder.build().map_err(|e| Error::Other(e.to_string()))
+}
+
+/// Copy each walked file `keep` accepts and not already in `copied`.
+fn copy_walk(
+    walker: ignore::Walk,
+    from: &Path,
+    to: &Path,
+    keep: impl Fn(&Path) -> bool,
+    copied: &mut HashSet<PathBuf>,
+    failures: &mut Vec<String>,
+) {
+    for entry in walker {
+        let result = match entry {
+            Ok(entry) if entry.path().is_file() && keep(entry.path()) => {
+                if !copied.insert(entry.path().to_path_buf()) {
+                    continue;
+                }
+                copy_one(entry.path(), from, to)
+            }
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            failures.push(e);
+        }
+    }
+}
+
+/// Error text leads with the repo-relative path, for the caller's warning.
+fn copy_one(path: &Path, from: &Path, to: &Path) -> std::result::Result<(), String> {
+    let rel = path
+        .strip_prefix(from)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    let dest = to.join(rel);
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", rel.display()))?;
+    }
+    std::fs::copy(path, &dest).map_err(|e| format!("{}: {e}", rel.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_merge_message_with_commits() {
+        let log = "abc1234 Add user authentication\ndef5678 Fix login edge case\n";
+        let msg = build_merge_message("feature-auth", log);
+        assert!(msg.starts_with("Merge branch 'feature-auth'\n"));
+        assert!(msg.contains("abc1234 Add user authentication"));
+        assert!(msg.contains("def5678 Fix login edge case"));
+    }
+
+    #[test]
+    fn build_merge_message_single_commit() {
+        let log = "abc1234 Initial implementation\n";
+        let msg = build_merge_message("fix-bug", log);
+        assert_eq!(msg, "Initial implementation");
+    }
+
+    #[test]
+    fn build_merge_message_empty_log() {
+        let msg = build_merge_message("my-branch", "");
+        assert_eq!(msg, "Merge branch 'my-branch'");
+    }
+
+    #[test]
+    fn copy_files_collects_failures_and_copies_the_rest() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join(".env"), "A=1\n").unwrap();
+        std::fs::create_dir_all(src.path().join("sub")).unwrap();
+        std::fs::write(src.path().join("sub/.env"), "B=2\n").unwrap();
+        // A file where the directory should go: that copy must fail
+        std::fs::write(dst.path().join("sub"), "").unwrap();
+
+        let failures = copy_files(src.path(), dst.path(), &[".env".to_string()], true).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("sub"), "{failures:?}");
+        assert_eq!(
+            std::fs::read_to_string(dst.path().join(".env")).unwrap(),
+            "A=1\n"
+        );
+    }
+
+    /// Repo-like source: tracked file, .git, a gitignored dir and .env
+    fn gitignored_source() -> tempfile::TempDir {
+        let src = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join(".gitignore"), "target/\n.env\n").unwrap();
+        std::fs::write(src.path().join("README"), "hi\n").unwrap();
+        std::fs::write(src.path().join(".env"), "A=1\n").unwrap();
+        std::fs::create_dir_all(src.path().join("target")).unwrap();
+        std::fs::write(src.path().join("target/out.bin"), "").unwrap();
+        std::fs::create_dir_all(src.path().join(".git")).unwrap();
+        std::fs::write(src.path().join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        src
+    }
+
+    #[test]
+    fn copy_files_broad_patterns_respect_gitignore_but_names_do_not() {
+        let src = gitignored_source();
+        std::fs::write(src.path().join(".env.local"), "B=2\n").unwrap();
+        std::fs::write(src.path().join(".gitignore"), "target/\n.env*\n").unwrap();
+        let dst = tempfile::tempdir().unwrap();
+
+        let patterns = ["*".to_string(), ".env".to_string(), ".env.*".to_string()];
+        let failures = copy_files(src.path(), dst.path(), &patterns, true).unwrap();
+        assert!(failures.is_empty(), "{failures:?}");
+        assert!(dst.path().join("README").is_file());
+        assert!(dst.path().join(".gitignore").is_file());
+        assert!(dst.path().join(".env").is_file(), "named file is copied");
+        assert!(dst.path().join(".env.local").is_file());
+        assert!(!dst.path().join("target").exists());
+        assert!(!dst.path().join(".git").exists());
+    }
+
+    #[test]
+    fn copy_files_ignoring_gitignore_still_skips_git_dir() {
+        let src = gitignored_source();
+        let dst = tempfile::tempdir().unwrap();
+
+        let failures = copy_files(src.path(), dst.path(), &["*".to_string()], false).unwrap();
+        assert!(failures.is_empty(), "{failures:?}");
+        assert!(dst.path().join(".env").is_file());
+        assert!(dst.path().join("target/out.bin").is_file());
+        assert!(!dst.path().join(".git").exists());
+    }
+
+    #[test]
+    fn is_broad_only_for_wildcard_file_names() {
+        assert!(is_broad("*"));
+        assert!(is_broad("**/*"));
+        assert!(is_broad("config/*"));
+        assert!(!is_broad(".env"));
+        assert!(!is_broad(".env.*"));
+        assert!(!is_broad("*.secret"));
+    }
+
+    #[test]
+    fn validate_copy_pattern_accepts_relative_glob() {
+        assert!(validate_copy_pattern(".env").is_ok());
+        assert!(validate_copy_pattern(".env.*").is_ok());
+        assert!(validate_copy_pattern("config/*.toml").is_ok());
+        assert!(validate_copy_pattern("**/.secret").is_ok());
+    }
+
+    #[test]
+    fn validate_copy_pattern_rejects_absolute_path() {
+        let err = validate_copy_pattern("/etc/passwd").unwrap_err();
+        assert!(err.to_string().contains("absolute"));
+    }
+
+    #[test]
+    fn validate_copy_pattern_rejects_parent_traversal() {
+        let err = validate_copy_pattern("../secrets").unwrap_err();
+        assert!(err.to_string().contains(".."));
+
+        let err = validate_copy_pattern("config/../../etc/passwd").unwrap_err();
+        assert!(err.to_string().contains(".."));
+    }
+
+    #[test]
+    fn validate_copy_pattern_rejects_backslash_traversal() {
+        // Windows-style path separator should still be rejected.
+        let err = validate_copy_pattern("..\\secrets").unwrap_err();
+        assert!(err.to_string().contains(".."));
+    }
+}
@@ -118,3 +118,120 @@ fn test_cd_returns_correct_path() {
         assert_eq!(created_path, cd_path);
     }
 }
+
+/// An unwritable path file must not turn a successful `wt cd` into a
+/// failure; the target is reported on stderr instead.
+#[test]
+fn test_cd_path_file_write_failure_reports_target() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "cd-unwritable"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+
+    let bad = dir.path().join("no-such-dir").join("path");
+    let output = Command::new(wt_binary())
+        .args(["cd", "cd-unwritable", "--path-file", bad.to_str().unwrap()])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt cd failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "wt cd failed: {stderr}");
+    assert!(stderr.contains("failed to write path file"), "{stderr}");
+    assert!(stderr.contains("cd-unwritable"), "{stderr}");
+}
+
+#[test]
+fn test_cd_dash_returns_to_previous_location() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let path_file = create_path_file(dir.path());
+    let wt = |cwd: &std::path::Path, args: &[&str]| {
+        Command::new(wt_binary())
+            .args(args)
+            .args(["--path-file", path_file.to_str().unwrap()])
+            .current_dir(cwd)
+            .env("HOME", &home)
+            .output()
+            .expect("wt failed")
+    };
+
+    // No history yet
+    let output = wt(&repo, &["cd", "-"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no previous location"), "stderr: {stderr}");
+
+    assert!(wt(&repo, &["new", "cd-dash"]).status.success());
+    let wt_path = std::path::PathBuf::from(read_path_file(&path_file).trim());
+
+    // main -> worktree, then `-` goes back to main
+    assert!(wt(&repo, &["cd", "cd-dash"]).status.success());
+    assert!(wt(&wt_path, &["cd", "-"]).status.success());
+    let back = std::path::PathBuf::from(read_path_file(&path_file).trim());
+    assert_eq!(back.canonicalize().unwrap(), repo.canonicalize().unwrap());
+
+    // and `-` again toggles into the worktree
+    assert!(wt(&repo, &["cd", "-"]).status.success());
+    let again = std::path::PathBuf::from(read_path_file(&path_file).trim());
+    assert_eq!(
+        again.canonicalize().unwrap(),
+        wt_path.canonicalize().unwrap()
+    );
+}
+
+#[test]
+fn test_cd_rejects_path_outside_workspace() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let outside = dir.path().to_str().unwrap();
+
+    for target in [outside, "../.."] {
+        let path_file = create_path_file(dir.path());
+        let output = Command::new(wt_binary())
+            .args(["cd", target, "--path-file", path_file.to_str().unwrap()])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt cd failed");
+
+        assert!(!output.status.success(), "cd {target} should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("outside the workspace"), "stderr: {stderr}");
+        assert!(!path_file.exists());
+    }
+}
+
+#[test]
+fn test_cd_finds_worktree_added_outside_workspace() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let manual = dir.path().join("manual-wt");
+    let output = Command::new("git")
+        .args(["worktree", "add", "-b", "manual"])
+        .arg(&manual)
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args(["cd", "manual", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt cd failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "wt cd failed: {stderr}");
+    assert_eq!(
+        std::path::PathBuf::from(read_path_file(&path_file).trim())
+            .canonicalize()
+            .unwrap(),
+        manual.canonicalize().unwrap()
+    );
+}
@@ -64,7 +64,7 @@ fn test_clean_after_merge() {
         .ok();
 
     let output = Command::new(wt_binary())
-        .arg("clean")
+        .args(["clean", "--yes"])
         .current_dir(&repo)
         .env("HOME", &home)
         .output()
@@ -97,7 +97,7 @@ fn test_clean_remvs_merged_worktree() {
         .ok();
 
     let output = Command::new(wt_binary())
-        .arg("clean")
+        .args(["clean", "--yes"])
         .current_dir(&repo)
         .env("HOME", &home)
         .output()
@@ -178,11 +178,14 @@ fn test_clean_skips_dirty_worktree() {
         .lines()
         .find(|l| l.contains("dirty-clean"))
         .expect("worktree should appear in ls -l");
-    // ls -l output contains the path; pull whatever looks like a path token
+    // ls -l output contains the path (home-relative paths are shortened to ~/)
     let wt_path = wt_line
         .split_whitespace()
-        .find(|tok| tok.starts_with('/'))
-        .expect("ls -l should contain absolute path");
+        .find_map(|tok| match tok.strip_prefix("~/") {
+            Some(rest) => Some(home.join(rest).display().to_string()),
+            None => tok.starts_with('/').then(|| tok.to_string()),
+        })
+        .expect("ls -l should contain a path");
     std::fs::write(format!("{wt_path}/scratch.tmp"), "in-flight\n").unwrap();
 
     // Dry-run should report the dirty skip, not "Would clean"
@@ -203,3 +206,202 @@ fn test_clean_skips_dirty_worktree() {
         "dry-run must not promise to clean a dirty worktree: {stderr}"
     );
 }
+
+#[test]
+fn test_clean_merged_force_removes_dirty_merged_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = dir.path().join(".wt-path");
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "scratchy",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let wt_path = std::path::PathBuf::from(read_path_file(&path_file).trim());
+    std::fs::write(wt_path.join("scratch.tmp"), "leftover\n").unwrap();
+
+    let clean = |args: &[&str]| {
+        Command::new(wt_binary())
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt clean failed")
+    };
+
+    // Plain clean keeps the dirty worktree
+    let output = clean(&["clean"]);
+    assert!(output.status.success());
+    assert!(wt_path.exists());
+
+    let output = clean(&["clean", "--merged", "--force", "--dry-run"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(
+        stderr.contains(
+            "Would clean (merged into main, discarding 1 uncommitted change(s)): scratchy"
+        ),
+        "stderr: {stderr}"
+    );
+    assert!(wt_path.exists());
+
+    let output = clean(&["clean", "--merged", "--force", "--yes"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(stderr.contains("merged into main"), "stderr: {stderr}");
+    assert!(!wt_path.exists(), "dirty merged worktree should be removed");
+
+    let output = Command::new("git")
+        .args(["branch", "--list", "scratchy"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&output.stdout).trim().is_empty());
+}
+
+#[test]
+fn test_clean_skips_branch_checked_out_in_main_repo() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "shared-main"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+
+    // Main repo also has the branch checked out — git would refuse to
+    // delete it, so clean must not report it as cleaned.
+    let output = Command::new("git")
+        .args(["checkout", "--ignore-other-worktrees", "shared-main"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(wt_binary())
+        .args(["clean", "--yes"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt clean failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "clean failed: {stderr}");
+    assert!(
+        stderr.contains("Skipping shared-main: also checked out"),
+        "stderr should explain the skip: {stderr}"
+    );
+    assert!(
+        !stderr.contains("worktree(s) cleaned"),
+        "refused deletion must not be counted: {stderr}"
+    );
+
+    let ls_output = Command::new(wt_binary())
+        .arg("ls")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt ls failed");
+    assert!(String::from_utf8_lossy(&ls_output.stdout).contains("shared-main"));
+}
+
+#[test]
+fn test_clean_counts_only_actual_removals() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    for branch in ["clean-ok", "clean-shared"] {
+        let output = Command::new(wt_binary())
+            .args(["new", branch])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed");
+        assert!(output.status.success());
+    }
+
+    // Second, unmanaged checkout of the same branch blocks its deletion.
+    let other = dir.path().join("other-checkout");
+    let output = Command::new("git")
+        .args(["worktree", "add", "--force"])
+        .arg(&other)
+        .arg("clean-shared")
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(wt_binary())
+        .args(["clean", "--yes"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt clean failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "clean failed: {stderr}");
+    assert!(
+        stderr.contains("1 worktree(s) cleaned."),
+        "only the unshared worktree should count: {stderr}"
+    );
+    assert!(stderr.contains("Skipping clean-shared"), "{stderr}");
+}
+
+#[test]
+fn test_clean_requires_yes_when_not_interactive() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "idle-confirm"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+
+    let wt_list = || {
+        let output = Command::new(wt_binary())
+            .arg("ls")
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt ls failed");
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    // stdin is not a terminal: refuse instead of removing unasked
+    let output = Command::new(wt_binary())
+        .arg("clean")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt clean failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "clean should refuse: {stderr}");
+    assert!(
+        stderr.contains("About to remove 1 worktree(s)") && stderr.contains("idle-confirm"),
+        "stderr should list what would go: {stderr}"
+    );
+    assert!(stderr.contains("--yes"), "stderr: {stderr}");
+    assert!(wt_list().contains("idle-confirm"));
+
+    let output = Command::new(wt_binary())
+        .args(["clean", "--yes"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt clean failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "clean failed: {stderr}");
+    assert!(
+        stderr.contains("1 worktree(s) cleaned."),
+        "stderr: {stderr}"
+    );
+    assert!(!wt_list().contains("idle-confirm"));
+}
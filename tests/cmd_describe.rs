@@ -0,0 +1,134 @@
+// ===========================================================================
+// Integration Tests - Worktree Descriptions (new --desc / describe)
+// ===========================================================================
+
+mod common;
+
+use std::process::Command;
+
+use common::*;
+
+fn wt_new_with_desc(
+    dir: &std::path::Path,
+    repo: &std::path::Path,
+    home: &std::path::Path,
+    branch: &str,
+    desc: &str,
+) -> String {
+    let path_file = create_path_file(dir);
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            branch,
+            "--desc",
+            desc,
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(repo)
+        .env("HOME", home)
+        .output()
+        .expect("wt new failed");
+    assert!(
+        output.status.success(),
+        "wt new failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    read_path_file(&path_file).trim().to_string()
+}
+
+#[test]
+fn test_new_desc_shown_in_ls_and_status() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = wt_new_with_desc(dir.path(), &repo, &home, "desc-one", "fix login race");
+
+    let output = Command::new(wt_binary())
+        .arg("ls")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt ls failed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("DESC"), "{stdout}");
+    assert!(stdout.contains("fix login race"), "{stdout}");
+
+    let output = Command::new(wt_binary())
+        .arg("status")
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt status failed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Description:  fix login race"), "{stdout}");
+}
+
+#[test]
+fn test_describe_updates_and_clears() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = wt_new_with_desc(dir.path(), &repo, &home, "desc-two", "first idea");
+
+    let output = Command::new(wt_binary())
+        .args(["describe", "desc-two", "second idea"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt describe failed");
+    assert!(
+        output.status.success(),
+        "wt describe failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let status = |cwd: &str| {
+        let output = Command::new(wt_binary())
+            .arg("status")
+            .current_dir(cwd)
+            .env("HOME", &home)
+            .output()
+            .expect("wt status failed");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let stdout = status(&wt_path);
+    assert!(stdout.contains("Description:  second idea"), "{stdout}");
+    assert!(!stdout.contains("first idea"), "{stdout}");
+
+    // '.' resolves to the current worktree; empty text clears
+    let output = Command::new(wt_binary())
+        .args(["describe", ".", ""])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt describe failed");
+    assert!(output.status.success());
+    assert!(!status(&wt_path).contains("Description:"));
+}
+
+#[test]
+fn test_describe_nonexistent_worktree() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["describe", "no-such-branch", "text"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt describe failed");
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_describe_rejects_path_outside_workspace() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["describe", "../..", "text"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt describe failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("outside the workspace"), "stderr: {stderr}");
+}
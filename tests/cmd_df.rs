@@ -0,0 +1,63 @@
+// ===========================================================================
+// Integration Tests - Df Command
+// ===========================================================================
+
+mod common;
+
+use std::process::Command;
+
+use common::*;
+
+#[test]
+fn test_df_reports_workspace_usage() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "df-feature"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+
+    let output = Command::new(wt_binary())
+        .arg("df")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt df failed");
+
+    assert!(
+        output.status.success(),
+        "wt df failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Workspaces:"), "{stdout}");
+    assert!(stdout.contains("Total:"), "{stdout}");
+    assert!(stdout.contains("This project:"), "{stdout}");
+    assert!(stdout.contains("Free:"), "{stdout}");
+}
+
+/// An unreachable `min_free_gb` makes `wt new` warn but still create.
+#[cfg(unix)]
+#[test]
+fn test_new_warns_when_free_space_below_min() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    std::fs::write(
+        repo.join(".agent-worktree.toml"),
+        "[general]\nmin_free_gb = 999999999\n",
+    )
+    .unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "df-low"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "wt new failed: {stderr}");
+    assert!(stderr.contains("min_free_gb = 999999999"), "{stderr}");
+}
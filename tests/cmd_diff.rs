@@ -0,0 +1,89 @@
+// ===========================================================================
+// Integration Tests - Diff Command
+// ===========================================================================
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use common::*;
+
+/// Worktree with one committed file and one uncommitted edit
+fn setup_changed_worktree(dir: &Path, repo: &Path, home: &Path) -> PathBuf {
+    let wt_path = new_worktree(dir, repo, home, "diff-me");
+    commit_file(&wt_path, "committed.txt", "one\ntwo\n", "Add file");
+    std::fs::write(wt_path.join("README.md"), "# Edited\n").unwrap();
+    wt_path
+}
+
+#[test]
+fn test_diff_shows_committed_and_uncommitted() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    setup_changed_worktree(dir.path(), &repo, &home);
+
+    let output = wt(&repo, &home, &["diff", "diff-me"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let committed = stdout.find("+++ b/committed.txt").expect("committed diff");
+    let uncommitted = stdout.find("+# Edited").expect("uncommitted diff");
+    assert!(committed < uncommitted, "stdout: {stdout}");
+}
+
+#[test]
+fn test_diff_stat_current_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = setup_changed_worktree(dir.path(), &repo, &home);
+
+    let output = wt(&wt_path, &home, &["diff", "--stat"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("Committed:   +2 -0"), "stdout: {stdout}");
+    assert!(stdout.contains("Uncommitted: +1 -1"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_diff_unknown_worktree_fails() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = wt(&repo, &home, &["diff", "nope"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("nope"));
+}
+
+#[test]
+fn test_diff_rejects_path_outside_workspace() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = wt(&repo, &home, &["diff", "--stat", "../.."]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("outside the workspace"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_diff_finds_worktree_added_outside_workspace() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let manual = dir.path().join("manual-wt");
+    let output = Command::new("git")
+        .args(["worktree", "add", "-b", "manual"])
+        .arg(&manual)
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    std::fs::write(manual.join("README.md"), "# Edited\n").unwrap();
+
+    let output = wt(&repo, &home, &["diff", "--stat", "manual"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Uncommitted: +1 -1"), "stdout: {stdout}");
+}
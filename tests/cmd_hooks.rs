@@ -116,3 +116,210 @@ post_create = ["false"]
 
     drop(dir);
 }
+
+/// `--parallel` runs every post_create hook even when an earlier one fails,
+/// and reports each failure.
+#[test]
+fn test_post_create_parallel_runs_all_hooks() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let config = r#"
+[hooks]
+post_create = ["false", 'echo ran > "$WT_MAIN_REPO/second.txt"', "exit 2"]
+"#;
+    std::fs::write(repo.join(".agent-worktree.toml"), config).unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "para", "--base", "main", "--parallel"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("hooks failed: 'false', 'exit 2'"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        repo.join("second.txt").exists(),
+        "the hook after a failing one still runs"
+    );
+
+    drop(dir);
+}
+
+/// `log_hooks` tees hook output into `{branch}.hooks.log`, named in the error.
+#[test]
+fn test_log_hooks_keeps_failed_hook_output() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let config = r#"
+[hooks]
+post_create = ["echo to-out; echo to-err >&2; false"]
+log_hooks = true
+"#;
+    std::fs::write(repo.join(".agent-worktree.toml"), config).unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "logged", "--base", "main"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Still streamed to the terminal
+    assert!(stdout.contains("to-out"), "stdout: {stdout}");
+    assert!(stderr.contains("to-err"), "stderr: {stderr}");
+
+    let log = stderr
+        .split("(output in ")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .unwrap_or_else(|| panic!("no log path in stderr: {stderr}"));
+    assert!(log.ends_with("logged.hooks.log"), "{log}");
+    let content = std::fs::read_to_string(log).unwrap();
+    assert!(content.starts_with("$ echo to-out"), "{content}");
+    assert!(content.contains("to-out\n") && content.contains("to-err\n"));
+
+    drop(dir);
+}
+
+/// Create a worktree with one commit under a committed project config, so
+/// the main repo stays clean for `wt merge`.
+fn setup_merge_with_hooks(
+    config: &str,
+) -> (
+    tempfile::TempDir,
+    std::path::PathBuf,
+    std::path::PathBuf,
+    String,
+) {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    std::fs::write(repo.join(".agent-worktree.toml"), config).unwrap();
+    Command::new("git")
+        .args(["add", ".agent-worktree.toml"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    Command::new("git")
+        .args(["commit", "-m", "Add wt config"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "feature-post",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(
+        output.status.success(),
+        "wt new failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let wt_path = read_path_file(&path_file).trim().to_string();
+
+    std::fs::write(std::path::Path::new(&wt_path).join("feature.txt"), "x").unwrap();
+    Command::new("git")
+        .args(["add", "."])
+        .current_dir(&wt_path)
+        .output()
+        .unwrap();
+    Command::new("git")
+        .args(["commit", "-m", "Add feature"])
+        .current_dir(&wt_path)
+        .output()
+        .unwrap();
+
+    (dir, repo, home, wt_path)
+}
+
+/// A failing post_merge hook only warns: the merge already landed.
+#[test]
+fn test_post_merge_hook_failure_does_not_fail_merge() {
+    let (dir, repo, home, wt_path) = setup_merge_with_hooks(
+        r#"
+[hooks]
+post_merge = ["false"]
+"#,
+    );
+
+    let output = Command::new(wt_binary())
+        .arg("merge")
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "merge should succeed: {stderr}");
+    assert!(stderr.contains("Warning: post_merge"), "stderr: {stderr}");
+    assert!(stderr.contains("Merge complete"), "stderr: {stderr}");
+    assert!(
+        repo.join("feature.txt").exists(),
+        "merge should have landed"
+    );
+
+    drop(dir);
+}
+
+/// `post_merge_strict = true` restores fail-on-error behavior.
+#[test]
+fn test_post_merge_hook_failure_strict_fails_command() {
+    let (dir, repo, home, wt_path) = setup_merge_with_hooks(
+        r#"
+[hooks]
+post_merge = ["false"]
+post_merge_strict = true
+"#,
+    );
+
+    let output = Command::new(wt_binary())
+        .arg("merge")
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+
+    assert!(!output.status.success());
+    // The merge itself still happened before the hook ran.
+    assert!(repo.join("feature.txt").exists());
+
+    drop(dir);
+}
+
+/// pre_merge failures still block the merge.
+#[test]
+fn test_pre_merge_hook_failure_blocks_merge() {
+    let (dir, repo, home, wt_path) = setup_merge_with_hooks(
+        r#"
+[hooks]
+pre_merge = ["false"]
+"#,
+    );
+
+    let output = Command::new(wt_binary())
+        .arg("merge")
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+
+    assert!(!output.status.success());
+    assert!(!repo.join("feature.txt").exists());
+
+    drop(dir);
+}
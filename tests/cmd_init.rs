@@ -201,3 +201,39 @@ fn test_init_with_sync_strategy() {
         "Expected stderr to mention sync strategy, got: {stderr}"
     );
 }
+
+#[test]
+fn test_init_minimal_writes_only_given_options() {
+    let dir = tempdir().unwrap();
+    setup_git_repo(dir.path());
+
+    let output = Command::new(wt_binary())
+        .args(["init", "--minimal", "--trunk", "develop"])
+        .current_dir(dir.path())
+        .output()
+        .expect("wt init failed");
+    assert!(output.status.success());
+
+    let content = std::fs::read_to_string(dir.path().join(".agent-worktree.toml")).unwrap();
+    assert!(content.contains("develop"));
+    assert!(!content.contains('#'), "{content}");
+}
+
+#[test]
+fn test_init_default_lists_commented_options() {
+    let dir = tempdir().unwrap();
+    setup_git_repo(dir.path());
+
+    let output = Command::new(wt_binary())
+        .arg("init")
+        .current_dir(dir.path())
+        .output()
+        .expect("wt init failed");
+    assert!(output.status.success());
+
+    let content = std::fs::read_to_string(dir.path().join(".agent-worktree.toml")).unwrap();
+    assert!(content.contains("trunk = \"main\""), "{content}");
+    for key in ["merge_strategy", "copy_files", "post_create", "pre_merge"] {
+        assert!(content.contains(&format!("# {key} = ")), "{key}: {content}");
+    }
+}
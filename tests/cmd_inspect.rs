@@ -0,0 +1,172 @@
+// ===========================================================================
+// Integration Tests - Inspect Command
+// ===========================================================================
+
+mod common;
+
+use std::process::Command;
+
+use common::*;
+
+fn worktree_count(repo: &std::path::Path) -> usize {
+    let out = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .current_dir(repo)
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter(|l| l.starts_with("worktree "))
+        .count()
+}
+
+#[test]
+fn test_inspect_runs_command_and_removes_worktree() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args([
+            "inspect",
+            "main",
+            "--",
+            "sh",
+            "-c",
+            r#"pwd > "$WT_MAIN_REPO/inspect_pwd.txt" && test -f README.md"#,
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt inspect failed");
+
+    assert!(
+        output.status.success(),
+        "wt inspect failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let ran_in = std::fs::read_to_string(repo.join("inspect_pwd.txt")).unwrap();
+    let ran_in = std::path::Path::new(ran_in.trim());
+    assert!(ran_in.to_string_lossy().contains("inspect"), "{ran_in:?}");
+    assert!(!ran_in.exists(), "temp worktree should be removed");
+    assert_eq!(worktree_count(&repo), 1);
+}
+
+#[test]
+fn test_inspect_removes_worktree_when_command_fails() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args([
+            "inspect",
+            "main",
+            "--",
+            "sh",
+            "-c",
+            r#"pwd > "$WT_MAIN_REPO/inspect_pwd.txt"; exit 3"#,
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt inspect failed");
+
+    assert!(!output.status.success());
+    let ran_in = std::fs::read_to_string(repo.join("inspect_pwd.txt")).unwrap();
+    assert!(!std::path::Path::new(ran_in.trim()).exists());
+    assert_eq!(worktree_count(&repo), 1);
+}
+
+#[test]
+fn test_inspect_leaves_branch_free() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    // Detached: inspecting the branch checked out in the main repo works.
+    let output = Command::new(wt_binary())
+        .args(["inspect", "main", "--", "true"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt inspect failed");
+
+    assert!(
+        output.status.success(),
+        "wt inspect failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_inspect_passes_arguments_verbatim() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args([
+            "inspect",
+            "main",
+            "--",
+            "printf",
+            "[%s]",
+            "a b",
+            "c; echo d",
+            "$(echo e)",
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt inspect failed");
+
+    assert!(
+        output.status.success(),
+        "wt inspect failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "[a b][c; echo d][$(echo e)]"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_inspect_removes_worktree_on_ctrl_c() {
+    use std::os::unix::process::CommandExt;
+
+    let (dir, repo, home) = setup_worktree_test_env();
+    let marker = dir.path().join("inspect_path.txt");
+
+    let mut child = Command::new(wt_binary())
+        .args(["inspect", "main", "--", "sh", "-c"])
+        .arg(format!(
+            r#"pwd > "{}.tmp" && mv "{0}.tmp" "{0}" && sleep 30"#,
+            marker.display()
+        ))
+        .current_dir(&repo)
+        .env("HOME", &home)
+        // Own group, standing in for the terminal's foreground group
+        .process_group(0)
+        .spawn()
+        .expect("wt inspect failed to spawn");
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(20);
+    while !marker.exists() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "command never started"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    let ran_in = std::fs::read_to_string(&marker).unwrap();
+    let ran_in = std::path::Path::new(ran_in.trim());
+    assert!(ran_in.exists());
+
+    // Ctrl-C: SIGINT to every process in the group
+    let status = Command::new("kill")
+        .args(["-INT", "--", &format!("-{}", child.id())])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = child.wait().unwrap();
+    assert!(!status.success());
+    assert!(!ran_in.exists(), "temp worktree should be removed");
+    assert_eq!(worktree_count(&repo), 1);
+}
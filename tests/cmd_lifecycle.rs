@@ -0,0 +1,186 @@
+// ===========================================================================
+// Integration Tests - Full Worktree Lifecycle (new -> ls -> merge -> clean)
+// ===========================================================================
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+use common::*;
+
+// ===========================================================================
+// Harness
+// ===========================================================================
+
+/// 完全隔离的测试环境：临时 HOME + 显式 AGENT_WORKTREE_DIR，
+/// 不依赖也不污染真实的 ~/.agent-worktree
+struct Harness {
+    dir: TempDir,
+    repo: PathBuf,
+    home: PathBuf,
+    base: PathBuf,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        setup_git_repo(&repo);
+
+        let home = dir.path().join("home");
+        std::fs::create_dir_all(&home).unwrap();
+
+        // Base dir lives outside HOME so a fallback to ~/.agent-worktree
+        // would show up as worktrees in the wrong place.
+        let base = dir.path().join("wt-base");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(
+            base.join("config.toml"),
+            "[worktree]\ndefault_base = \"main\"\n",
+        )
+        .unwrap();
+
+        Self {
+            dir,
+            repo,
+            home,
+            base,
+        }
+    }
+
+    fn wt(&self, cwd: &Path, args: &[&str]) -> Output {
+        Command::new(wt_binary())
+            .args(args)
+            .current_dir(cwd)
+            .env("HOME", &self.home)
+            .env("AGENT_WORKTREE_DIR", &self.base)
+            .env("AGENT_WORKTREE_NO_UPDATE", "1")
+            .output()
+            .unwrap_or_else(|e| panic!("failed to run wt {args:?}: {e}"))
+    }
+
+    /// Runs wt and panics with its stderr unless it succeeded.
+    fn wt_ok(&self, cwd: &Path, args: &[&str]) -> Output {
+        let output = self.wt(cwd, args);
+        assert!(
+            output.status.success(),
+            "wt {args:?} failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    }
+
+    /// `wt new` via the path file; returns the created worktree path.
+    fn new_worktree(&self, branch: &str) -> PathBuf {
+        let path_file = self.dir.path().join(format!(".wt-path-{branch}"));
+        self.wt_ok(
+            &self.repo,
+            &["new", branch, "--path-file", path_file.to_str().unwrap()],
+        );
+        let wt_path = PathBuf::from(read_path_file(&path_file).trim());
+        assert!(
+            wt_path.starts_with(self.base.join("workspaces")),
+            "worktree {} is outside the base dir {}",
+            wt_path.display(),
+            self.base.display()
+        );
+        assert!(
+            wt_path.is_dir(),
+            "worktree dir missing: {}",
+            wt_path.display()
+        );
+        wt_path
+    }
+
+    fn git(&self, cwd: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    fn local_branches(&self) -> Vec<String> {
+        self.git(&self.repo, &["branch", "--format=%(refname:short)"])
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[test]
+fn test_lifecycle_new_ls_merge_clean() {
+    let h = Harness::new();
+
+    // new
+    let feature = h.new_worktree("feature-a");
+    let idle = h.new_worktree("idle-b");
+    assert!(!h.home.join(".agent-worktree").exists());
+
+    // ls
+    let output = h.wt_ok(&h.repo, &["ls"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("feature-a"), "ls output: {stdout}");
+    assert!(stdout.contains("idle-b"), "ls output: {stdout}");
+
+    // merge (squash, delete worktree afterwards)
+    std::fs::write(feature.join("feature.txt"), "feature\n").unwrap();
+    h.git(&feature, &["add", "feature.txt"]);
+    h.git(&feature, &["commit", "-m", "Add feature"]);
+
+    let path_file = h.dir.path().join(".wt-path-merge");
+    h.wt_ok(
+        &feature,
+        &["merge", "-d", "--path-file", path_file.to_str().unwrap()],
+    );
+    assert!(!feature.exists(), "merged worktree should be removed");
+    assert_eq!(
+        PathBuf::from(read_path_file(&path_file).trim())
+            .canonicalize()
+            .unwrap(),
+        h.repo.canonicalize().unwrap()
+    );
+    assert_eq!(
+        std::fs::read_to_string(h.repo.join("feature.txt")).unwrap(),
+        "feature\n"
+    );
+    assert!(!h.local_branches().contains(&"feature-a".to_string()));
+
+    // clean: idle-b has no diff from trunk
+    h.wt_ok(&h.repo, &["clean", "--yes"]);
+    assert!(!idle.exists(), "clean should remove the idle worktree");
+    assert!(!h.local_branches().contains(&"idle-b".to_string()));
+
+    let output = h.wt_ok(&h.repo, &["ls"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stdout.contains("feature-a") && !stdout.contains("idle-b"));
+    assert!(stderr.contains("No worktrees"), "ls stderr: {stderr}");
+}
+
+#[test]
+fn test_lifecycle_clean_keeps_worktree_with_commits() {
+    let h = Harness::new();
+
+    let wt_path = h.new_worktree("busy");
+    std::fs::write(wt_path.join("wip.txt"), "wip\n").unwrap();
+    h.git(&wt_path, &["add", "wip.txt"]);
+    h.git(&wt_path, &["commit", "-m", "WIP"]);
+
+    h.wt_ok(&h.repo, &["clean", "--yes"]);
+    assert!(wt_path.is_dir(), "worktree with commits must survive clean");
+    assert!(h.local_branches().contains(&"busy".to_string()));
+}
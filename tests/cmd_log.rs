@@ -0,0 +1,50 @@
+// ===========================================================================
+// Integration Tests - Log Command
+// ===========================================================================
+
+mod common;
+
+use common::*;
+
+#[test]
+fn test_log_lists_commits_over_target() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree(dir.path(), &repo, &home, "log-me");
+    commit_file(&wt_path, "a.txt", "First change", "First change");
+    commit_file(&wt_path, "b.txt", "Second change", "Second change");
+
+    let output = wt(&repo, &home, &["log", "log-me"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert_eq!(stdout.lines().count(), 2, "stdout: {stdout}");
+    assert!(stdout.lines().next().unwrap().ends_with("Second change"));
+    assert!(!stdout.contains("Initial commit"));
+}
+
+#[test]
+fn test_log_graph_current_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree(dir.path(), &repo, &home, "log-graph");
+    commit_file(&wt_path, "a.txt", "Graph change", "Graph change");
+
+    let output = wt(&wt_path, &home, &["log", "--graph"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("* "), "stdout: {stdout}");
+    assert!(
+        stdout.contains("log-graph"),
+        "decorations missing: {stdout}"
+    );
+    assert!(stdout.contains("Graph change"));
+}
+
+#[test]
+fn test_log_no_commits() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    new_worktree(dir.path(), &repo, &home, "log-empty");
+
+    let output = wt(&repo, &home, &["log", "log-empty"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No commits"));
+}
@@ -101,3 +101,363 @@ fn test_ls_with_multiple_worktrees() {
 
     assert!(combined.contains("multi-ls") || combined.contains("BRANCH"));
 }
+
+#[test]
+fn test_ls_shows_age_and_last_accessed() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    for name in &["age-visited", "age-unvisited"] {
+        let output = Command::new(wt_binary())
+            .args(["new", name])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed");
+        assert!(output.status.success());
+    }
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "cd",
+            "age-visited",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt cd failed");
+    assert!(output.status.success());
+
+    let output = Command::new(wt_binary())
+        .arg("ls")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt ls failed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("AGE") && stdout.contains("ACCESSED"),
+        "{stdout}"
+    );
+    let row = |name: &str| {
+        stdout
+            .lines()
+            .find(|l| l.contains(name))
+            .unwrap_or_else(|| panic!("missing {name}: {stdout}"))
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    let visited = row("age-visited");
+    assert_eq!(visited[visited.len() - 2..], ["now", "now"], "{stdout}");
+    let unvisited = row("age-unvisited");
+    assert_eq!(unvisited[unvisited.len() - 2..], ["now", "-"], "{stdout}");
+}
+
+#[test]
+fn test_ls_sort_by_commits_and_name() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let mut paths = Vec::new();
+    for name in ["sort-a", "sort-b"] {
+        let output = Command::new(wt_binary())
+            .args(["new", name, "--json"])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed");
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        paths.push(std::path::PathBuf::from(json["path"].as_str().unwrap()));
+    }
+    // Two commits on sort-a, none on sort-b
+    for i in 0..2 {
+        std::fs::write(paths[0].join(format!("f{i}.txt")), "x\n").unwrap();
+        for args in [vec!["add", "."], vec!["commit", "-m", "work"]] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(&paths[0])
+                .output()
+                .unwrap();
+        }
+    }
+
+    let order = |args: &[&str]| -> Vec<String> {
+        let output = Command::new(wt_binary())
+            .arg("ls")
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt ls failed");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .skip(2)
+            .filter_map(|l| l.split_whitespace().next().map(str::to_string))
+            .collect()
+    };
+
+    assert_eq!(order(&["--sort", "commits"]), ["sort-a", "sort-b"]);
+    assert_eq!(
+        order(&["--sort", "commits", "--reverse"]),
+        ["sort-b", "sort-a"]
+    );
+    assert_eq!(order(&["--sort", "name", "-r"]), ["sort-b", "sort-a"]);
+}
+
+#[test]
+fn test_ls_filters_dirty_ahead_clean() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let wt = |args: &[&str]| {
+        Command::new(wt_binary())
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt failed")
+    };
+    let mut paths = std::collections::HashMap::new();
+    for name in ["flt-ahead", "flt-dirty", "flt-idle"] {
+        let output = wt(&["new", name, "--json"]);
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        paths.insert(
+            name,
+            std::path::PathBuf::from(json["path"].as_str().unwrap()),
+        );
+    }
+    let ahead = &paths["flt-ahead"];
+    std::fs::write(ahead.join("done.txt"), "x\n").unwrap();
+    for args in [vec!["add", "."], vec!["commit", "-m", "work"]] {
+        Command::new("git")
+            .args(&args)
+            .current_dir(ahead)
+            .output()
+            .unwrap();
+    }
+    std::fs::write(paths["flt-dirty"].join("wip.txt"), "x\n").unwrap();
+
+    let listed = |filter: &str| -> Vec<String> {
+        let output = wt(&["ls", filter, "--sort", "name"]);
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .skip(2)
+            .filter_map(|l| l.split_whitespace().next().map(str::to_string))
+            .collect()
+    };
+    assert_eq!(listed("--ahead"), ["flt-ahead"]);
+    assert_eq!(listed("--dirty"), ["flt-dirty"]);
+    assert_eq!(listed("--clean"), ["flt-idle"]);
+
+    // Agrees with what `wt clean` would remove
+    let output = wt(&["clean", "--dry-run"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("flt-idle"), "clean: {stderr}");
+    assert!(!stderr.contains("Would clean (no diff from main): flt-dirty"));
+    assert!(!stderr.contains("flt-ahead"), "clean: {stderr}");
+
+    let output = wt(&["ls", "--clean", "--dirty"]);
+    assert!(!output.status.success(), "--clean conflicts with --dirty");
+}
+
+#[test]
+fn test_ls_porcelain_is_tab_separated() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "porc-a", "--json"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let wt_path = json["path"].as_str().unwrap().to_string();
+    std::fs::write(std::path::Path::new(&wt_path).join("dirty.txt"), "x\n").unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["ls", "--porcelain"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt ls failed");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "porcelain output: {stdout}");
+    let fields: Vec<&str> = lines[0].split('\t').collect();
+    assert_eq!(fields, ["porc-a", "1", "0", "0", "0", wt_path.as_str()]);
+}
+
+#[test]
+fn test_ls_all_includes_unmanaged_worktrees() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "managed-a"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+
+    let external = dir.path().join("raw-wt");
+    let output = Command::new("git")
+        .args(["worktree", "add", "-q", "-b", "raw-b"])
+        .arg(&external)
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let ls = |args: &[&str]| {
+        let output = Command::new(wt_binary())
+            .arg("ls")
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt ls failed");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let stdout = ls(&["--porcelain"]);
+    assert!(!stdout.contains("raw-b"), "ls output: {stdout}");
+
+    let stdout = ls(&["--all", "--porcelain", "--sort", "name"]);
+    let kinds: Vec<(&str, &str)> = stdout
+        .lines()
+        .map(|l| {
+            let fields: Vec<&str> = l.split('\t').collect();
+            (fields[0], fields[6])
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            ("main", "main"),
+            ("managed-a", "managed"),
+            ("raw-b", "external")
+        ]
+    );
+
+    let stdout = ls(&["--all"]);
+    assert!(stdout.contains("KIND"), "ls output: {stdout}");
+    assert!(stdout.contains("external"), "ls output: {stdout}");
+}
+
+#[test]
+fn test_ls_stale_lists_old_unvisited_worktrees() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let mut metas = Vec::new();
+    for name in ["stale-old", "stale-fresh"] {
+        let output = Command::new(wt_binary())
+            .args(["new", name, "--json"])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed");
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        metas.push(std::path::PathBuf::from(json["path"].as_str().unwrap()).with_extension("toml"));
+    }
+
+    // Backdate stale-old's creation by ten days
+    let mut meta: toml::Table =
+        toml::from_str(&std::fs::read_to_string(&metas[0]).unwrap()).unwrap();
+    let created = chrono::Utc::now() - chrono::Duration::days(10);
+    meta.insert(
+        "created_at".into(),
+        toml::Value::String(created.to_rfc3339()),
+    );
+    std::fs::write(&metas[0], toml::to_string(&meta).unwrap()).unwrap();
+
+    let ls = |stale: &str| {
+        let output = Command::new(wt_binary())
+            .args(["ls", "--porcelain", "--stale", stale])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt ls failed");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.split('\t').next().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ls("7d"), ["stale-old"]);
+    assert!(ls("2w").is_empty());
+
+    let output = Command::new(wt_binary())
+        .args(["ls", "--stale", "soon"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid duration"));
+}
+
+#[test]
+fn test_ls_remote_shows_upstream_column() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    for branch in ["tracked-a", "local-b"] {
+        let output = Command::new(wt_binary())
+            .args(["new", branch])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed");
+        assert!(output.status.success());
+    }
+    // A local branch stands in for a remote one: same %(upstream:short)
+    for args in [
+        &["branch", "upstream-x"][..],
+        &["branch", "-u", "upstream-x", "tracked-a"],
+    ] {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+
+    let ls = |args: &[&str]| {
+        let output = Command::new(wt_binary())
+            .arg("ls")
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt ls failed");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let stdout = ls(&[]);
+    assert!(!stdout.contains("UPSTREAM"), "ls output: {stdout}");
+
+    for flag in ["--remote", "--long"] {
+        let stdout = ls(&[flag]);
+        assert!(stdout.contains("UPSTREAM"), "ls {flag} output: {stdout}");
+        let line = |branch: &str| {
+            stdout
+                .lines()
+                .find(|l| l.contains(branch))
+                .unwrap_or_else(|| panic!("no {branch} row: {stdout}"))
+                .to_string()
+        };
+        assert!(line("tracked-a").contains("upstream-x"), "{stdout}");
+        assert!(!line("local-b").contains("upstream-x"), "{stdout}");
+    }
+}
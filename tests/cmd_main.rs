@@ -10,11 +10,14 @@ use tempfile::tempdir;
 use common::*;
 
 #[test]
-fn test_cd_no_args_returns_repo_root() {
+fn test_cd_no_args_in_main_repo_skips_cd() {
+    // Already at the main repo root: no path file, so the wrapper doesn't
+    // cd (which would clobber the shell's own `cd -`).
     let dir = tempdir().unwrap();
     setup_git_repo(dir.path());
 
     let path_file = create_path_file(dir.path());
+    std::fs::write(&path_file, "").unwrap();
     let output = Command::new(wt_binary())
         .args(["cd", "--path-file", path_file.to_str().unwrap()])
         .current_dir(dir.path())
@@ -22,11 +25,83 @@ fn test_cd_no_args_returns_repo_root() {
         .expect("Failed to execute wt cd");
 
     assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Already in main repo"), "stderr: {stderr}");
+    assert!(!path_file.exists(), "path file should be removed");
+}
 
-    let path = read_path_file(&path_file);
-    let path = path.trim();
-    assert!(!path.is_empty());
-    assert!(std::path::Path::new(path).exists());
+#[test]
+fn test_cd_no_args_from_worktree_returns_repo_root() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "back-home",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let wt_path = std::path::PathBuf::from(read_path_file(&path_file).trim());
+
+    let output = Command::new(wt_binary())
+        .args(["cd", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("Failed to execute wt cd");
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::path::PathBuf::from(read_path_file(&path_file).trim()),
+        repo.canonicalize().unwrap()
+    );
+}
+
+#[test]
+fn test_cd_no_args_in_bare_setup_fails() {
+    // Bare clone in container/.git with linked worktrees beside it: the
+    // container is not a checkout, so there is nowhere to return to.
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    setup_git_repo(&src);
+    let container = dir.path().join("container");
+    let git = |args: &[&str], cwd: &std::path::Path| {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    };
+    std::fs::create_dir_all(&container).unwrap();
+    git(
+        &["clone", "--bare", src.to_str().unwrap(), ".git"],
+        &container,
+    );
+    git(
+        &["worktree", "add", "../wt1", "main"],
+        &container.join(".git"),
+    );
+    let wt1 = container.join("wt1");
+
+    let home = dir.path().join("home");
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args(["cd", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(&wt1)
+        .env("HOME", &home)
+        .output()
+        .expect("Failed to execute wt cd");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("bare"), "stderr: {stderr}");
 }
 
 #[test]
@@ -69,3 +144,55 @@ fn test_cd_no_args_from_subdirectory() {
     let path = read_path_file(&path_file);
     assert!(!path.trim().is_empty());
 }
+
+/// `wt cd` from outside any repo, with the repo selected only via env.
+fn cd_with_git_env(cwd: &std::path::Path, env: &[(&str, &std::path::Path)]) -> String {
+    let path_file = create_path_file(cwd);
+    let mut cmd = Command::new(wt_binary());
+    cmd.args(["cd", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(cwd);
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
+    let output = cmd.output().expect("wt cd failed");
+    assert!(
+        output.status.success(),
+        "wt cd failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    read_path_file(&path_file).trim().to_string()
+}
+
+#[test]
+fn test_cd_no_args_honors_git_dir() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    setup_git_repo(&repo);
+    let elsewhere = dir.path().join("elsewhere");
+    std::fs::create_dir_all(&elsewhere).unwrap();
+
+    let path = cd_with_git_env(&elsewhere, &[("GIT_DIR", &repo.join(".git"))]);
+    assert_eq!(std::path::PathBuf::from(path), repo.canonicalize().unwrap());
+}
+
+#[test]
+fn test_cd_no_args_honors_git_dir_outside_work_tree() {
+    // GIT_DIR names a store that is not <root>/.git; GIT_WORK_TREE says
+    // where the checkout lives.
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let store = dir.path().join("store.git");
+    std::fs::create_dir_all(&repo).unwrap();
+    Command::new("git")
+        .args(["init", "--separate-git-dir"])
+        .arg(&store)
+        .arg(&repo)
+        .output()
+        .unwrap();
+    let elsewhere = dir.path().join("elsewhere");
+    std::fs::create_dir_all(&elsewhere).unwrap();
+
+    let path = cd_with_git_env(&elsewhere, &[("GIT_DIR", &store), ("GIT_WORK_TREE", &repo)]);
+    assert_eq!(std::path::PathBuf::from(path), repo.canonicalize().unwrap());
+}
@@ -4,7 +4,7 @@
 
 mod common;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::tempdir;
 
@@ -232,19 +232,33 @@ fn test_merge_conflict_rejected() {
         .output()
         .expect("wt merge failed");
 
-    // Merge should fail (non-zero exit) due to conflict precheck
-    assert!(!output.status.success());
+    // Conflicts get their own exit code (4), distinct from hard failures (1)
+    assert_eq!(output.status.code(), Some(4));
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
         stderr.contains("conflict") || stderr.contains("Sync first"),
         "Expected conflict rejection message, got: {stderr}"
     );
+    let listing = stderr
+        .find("Conflicts:\n  README.md")
+        .expect("conflict list");
+    assert!(
+        listing < stderr.find("Sync first").unwrap(),
+        "conflict list should precede the resolve hint: {stderr}"
+    );
 
-    // No WT_MERGE_BRANCH state file should exist
-    let state_path = repo.join(".git").join("WT_MERGE_BRANCH");
+    // Merge is atomic and stateless: no WT_MERGE_* file (legacy
+    // WT_MERGE_BRANCH or any per-branch variant) may land in the shared
+    // .git dir, where concurrent merges from other worktrees would clobber it.
+    let leftovers: Vec<_> = std::fs::read_dir(repo.join(".git"))
+        .unwrap()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("WT_MERGE"))
+        .collect();
     assert!(
-        !state_path.exists(),
-        "No merge state file should exist after precheck rejection"
+        leftovers.is_empty(),
+        "No merge state file should exist after precheck rejection: {leftovers:?}"
     );
 }
 
@@ -283,30 +297,28 @@ fn test_merge_into_nonexistent_branch_fails() {
     );
 }
 
-#[test]
-fn test_merge_into_branch_held_by_another_worktree_fails() {
-    // `wt merge --into X` must refuse upfront if X is already checked out by
-    // another worktree — git would error mid-merge with a confusing
-    // low-level message and potentially leave HEAD detached.
-    let (dir, repo, home) = setup_worktree_test_env();
-
-    // Create source worktree
-    let path_file = create_path_file(dir.path());
-    let output = Command::new(wt_binary())
-        .args([
-            "new",
-            "merge-into-busy-src",
-            "--path-file",
-            path_file.to_str().unwrap(),
-        ])
-        .current_dir(&repo)
-        .env("HOME", &home)
-        .output()
-        .expect("wt new src failed");
-    assert!(output.status.success());
-    let src_wt = PathBuf::from(read_path_file(&path_file).trim());
+/// Source worktree with one commit plus a second worktree on `target`;
+/// returns (source path, target path).
+fn setup_merge_into_worktree(
+    dir: &std::path::Path,
+    repo: &std::path::Path,
+    home: &std::path::Path,
+    target: &str,
+) -> (PathBuf, PathBuf) {
+    let mut paths = Vec::new();
+    for branch in ["merge-into-busy-src", target] {
+        let path_file = dir.join(format!(".wt-path-{branch}"));
+        let output = Command::new(wt_binary())
+            .args(["new", branch, "--path-file", path_file.to_str().unwrap()])
+            .current_dir(repo)
+            .env("HOME", home)
+            .output()
+            .expect("wt new failed");
+        assert!(output.status.success());
+        paths.push(PathBuf::from(read_path_file(&path_file).trim()));
+    }
+    let src_wt = paths.remove(0);
 
-    // Add a commit so merge has something to do
     std::fs::write(src_wt.join("feat.txt"), "feat").unwrap();
     Command::new("git")
         .args(["add", "."])
@@ -319,24 +331,16 @@ fn test_merge_into_branch_held_by_another_worktree_fails() {
         .output()
         .unwrap();
 
-    // Create busy worktree on a separate branch we want to merge into
-    let path_file2 = create_path_file(dir.path());
-    let target_path_file = path_file2.with_extension("2");
-    std::fs::write(&target_path_file, "").unwrap();
-    let output = Command::new(wt_binary())
-        .args([
-            "new",
-            "busy-target",
-            "--path-file",
-            target_path_file.to_str().unwrap(),
-        ])
-        .current_dir(&repo)
-        .env("HOME", &home)
-        .output()
-        .expect("wt new busy failed");
-    assert!(output.status.success());
+    (src_wt, paths.remove(0))
+}
+
+#[test]
+fn test_merge_into_branch_held_by_another_worktree_merges_there() {
+    // git refuses to check out a branch another worktree owns, so the merge
+    // must run inside that worktree and leave the main repo's HEAD alone.
+    let (dir, repo, home) = setup_worktree_test_env();
+    let (src_wt, target_wt) = setup_merge_into_worktree(dir.path(), &repo, &home, "busy-target");
 
-    // Try to merge src into busy-target — should fail upfront
     let output = Command::new(wt_binary())
         .args(["merge", "--into", "busy-target"])
         .current_dir(&src_wt)
@@ -344,15 +348,43 @@ fn test_merge_into_branch_held_by_another_worktree_fails() {
         .output()
         .expect("wt merge --into busy failed");
 
-    assert!(
-        !output.status.success(),
-        "merge into busy branch should be rejected"
-    );
     let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "merge should succeed: {stderr}");
     assert!(
-        stderr.contains("checked out in another worktree") || stderr.contains("worktree"),
-        "stderr should explain busy worktree: {stderr}"
+        stderr.contains("Merge complete: merge-into-busy-src into busy-target"),
+        "stderr: {stderr}"
     );
+    assert_eq!(
+        std::fs::read_to_string(target_wt.join("feat.txt")).unwrap(),
+        "feat"
+    );
+    assert!(!repo.join("feat.txt").exists(), "main repo must not change");
+
+    let head = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&head.stdout).trim(), "main");
+}
+
+#[test]
+fn test_merge_into_branch_held_by_missing_worktree_fails() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let (src_wt, target_wt) = setup_merge_into_worktree(dir.path(), &repo, &home, "gone-target");
+    std::fs::remove_dir_all(&target_wt).unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "--into", "gone-target"])
+        .current_dir(&src_wt)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge --into gone failed");
+
+    assert!(!output.status.success(), "merge should be rejected");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no longer exists"), "stderr: {stderr}");
+    assert!(stderr.contains("git worktree prune"), "stderr: {stderr}");
 }
 
 #[test]
@@ -398,3 +430,719 @@ fn test_merge_already_up_to_date_with_merge_strategy() {
         "worktree should NOT be deleted when nothing was merged"
     );
 }
+
+/// Create a worktree with one committed file; returns its path.
+fn new_worktree_with_commit(
+    dir: &std::path::Path,
+    repo: &std::path::Path,
+    home: &std::path::Path,
+    branch: &str,
+) -> PathBuf {
+    let wt_path = new_worktree(dir, repo, home, branch);
+    commit_file(&wt_path, &format!("{branch}.txt"), branch, "Add feature");
+    wt_path
+}
+
+#[test]
+fn test_merge_then_new_creates_next_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "chain-one");
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "merge",
+            "-d",
+            "--then-new",
+            "chain-two",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge --then-new failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "merge --then-new failed: {stderr}");
+    assert!(!wt_path.exists(), "merged worktree should be deleted");
+
+    // Path file points at the new worktree, not the main repo
+    let next = PathBuf::from(read_path_file(&path_file).trim());
+    assert!(next.ends_with("chain-two"), "path file: {}", next.display());
+    // New worktree starts from the freshly merged target
+    assert!(next.join("chain-one.txt").exists());
+}
+
+#[test]
+fn test_merge_then_new_runs_when_nothing_to_merge() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree(dir.path(), &repo, &home, "chain-idle");
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "merge",
+            "-d",
+            "--then-new",
+            "chain-after-idle",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge --then-new failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "merge --then-new failed: {stderr}");
+    assert!(stderr.contains("Nothing to merge"), "{stderr}");
+    // Nothing merged: -d keeps the worktree
+    assert!(wt_path.exists());
+
+    let next = PathBuf::from(read_path_file(&path_file).trim());
+    assert!(
+        next.ends_with("chain-after-idle"),
+        "path file: {}",
+        next.display()
+    );
+    assert!(next.is_dir());
+}
+
+#[test]
+fn test_merge_then_new_random_branch() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "chain-rand");
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "merge",
+            "--then-new",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge --then-new failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "merge --then-new failed: {stderr}");
+    // Without -d the merged worktree stays
+    assert!(wt_path.exists());
+
+    let next = PathBuf::from(read_path_file(&path_file).trim());
+    assert_ne!(next, wt_path);
+    assert!(next.exists(), "new worktree missing: {}", next.display());
+    assert!(next.join("chain-rand.txt").exists());
+}
+
+#[test]
+fn test_merge_dry_run_prints_plan_without_merging() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "dry-plan");
+
+    let head_before = Command::new("git")
+        .args(["rev-parse", "main"])
+        .current_dir(&repo)
+        .output()
+        .unwrap()
+        .stdout;
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "--dry-run", "-s", "merge"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge --dry-run failed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "dry run failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Target:       main"), "{stdout}");
+    assert!(stdout.contains("Strategy:     Merge"), "{stdout}");
+    assert!(stdout.contains("Commits:      1"), "{stdout}");
+    // Single commit -> its message is reused verbatim
+    assert!(stdout.contains("  Add feature"), "{stdout}");
+
+    // Nothing moved
+    let head_after = Command::new("git")
+        .args(["rev-parse", "main"])
+        .current_dir(&repo)
+        .output()
+        .unwrap()
+        .stdout;
+    assert_eq!(head_before, head_after);
+    assert!(!repo.join("dry-plan.txt").exists());
+}
+
+#[test]
+fn test_merge_autostash_restores_worktree_changes() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "stash-wt");
+    std::fs::write(wt_path.join("wip.txt"), "work in progress").unwrap();
+
+    let merge = |extra: &[&str]| {
+        Command::new(wt_binary())
+            .arg("merge")
+            .args(extra)
+            .current_dir(&wt_path)
+            .env("HOME", &home)
+            .output()
+            .expect("wt merge failed")
+    };
+
+    let output = merge(&[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--autostash"));
+
+    let output = merge(&["--autostash"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "autostash merge failed: {stderr}");
+    assert!(repo.join("stash-wt.txt").exists(), "merge should land");
+    assert!(!repo.join("wip.txt").exists(), "wip must not be merged");
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join("wip.txt")).unwrap(),
+        "work in progress"
+    );
+}
+
+#[test]
+fn test_merge_autostash_restores_main_repo_changes() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "stash-main");
+    std::fs::write(repo.join("scratch.txt"), "main wip").unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "--autostash"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "autostash merge failed: {stderr}");
+    assert!(repo.join("stash-main.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(repo.join("scratch.txt")).unwrap(),
+        "main wip"
+    );
+
+    let stashes = Command::new("git")
+        .args(["stash", "list"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&stashes.stdout).trim().is_empty());
+}
+
+#[test]
+fn test_merge_autostash_keeps_main_repo_changes_on_original_branch() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "stash-side");
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    git(&["checkout", "-b", "side"]);
+    std::fs::write(repo.join("scratch.txt"), "side wip").unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "--autostash"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "autostash merge failed: {stderr}");
+    assert_eq!(git(&["branch", "--show-current"]), "side");
+    assert_eq!(
+        std::fs::read_to_string(repo.join("scratch.txt")).unwrap(),
+        "side wip"
+    );
+    assert!(git(&["stash", "list"]).is_empty());
+    assert!(!git(&["ls-tree", "--name-only", "main"]).contains("scratch.txt"));
+    assert!(git(&["ls-tree", "--name-only", "main"]).contains("stash-side.txt"));
+}
+
+#[test]
+fn test_merge_autostash_from_main_repo_stashes_cwd() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    // Branch with no managed worktree: there is no wt_dir/<branch> to stash in.
+    git(&["checkout", "-b", "plain"]);
+    std::fs::write(repo.join("plain.txt"), "plain\n").unwrap();
+    git(&["add", "plain.txt"]);
+    git(&["commit", "-m", "Add plain"]);
+    std::fs::write(repo.join("wip.txt"), "wip").unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "--autostash"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "autostash merge failed: {stderr}");
+    assert_eq!(git(&["branch", "--show-current"]), "plain");
+    assert_eq!(
+        std::fs::read_to_string(repo.join("wip.txt")).unwrap(),
+        "wip"
+    );
+    assert!(git(&["ls-tree", "--name-only", "main"]).contains("plain.txt"));
+    assert!(git(&["stash", "list"]).is_empty());
+}
+
+#[test]
+fn test_merge_autostash_with_delete_refuses_dirty_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "stash-del");
+    std::fs::write(wt_path.join("wip.txt"), "wip").unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "--autostash", "-d"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+
+    assert!(!output.status.success());
+    assert!(wt_path.join("wip.txt").exists());
+    assert!(!repo.join("stash-del.txt").exists());
+}
+
+#[test]
+fn test_merge_squash_author_and_date_override() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "pinned");
+
+    let output = Command::new(wt_binary())
+        .args([
+            "merge",
+            "-s",
+            "squash",
+            "--author",
+            "Agent <agent@example.com>",
+            "--date",
+            "2024-01-15T10:30:00Z",
+        ])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "merge failed: {stderr}");
+
+    let log = Command::new("git")
+        .args(["log", "-1", "--format=%an <%ae>|%aI", "main"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    let log = String::from_utf8_lossy(&log.stdout);
+    assert!(
+        log.starts_with("Agent <agent@example.com>|2024-01-15T10:30:00"),
+        "{log}"
+    );
+}
+
+#[test]
+fn test_merge_author_rejected_for_merge_strategy() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "pinned-merge");
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "-s", "merge", "--author", "A <a@example.com>"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("squash"));
+}
+
+#[test]
+fn test_merge_no_verify_skips_git_hooks() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "slow-hooks");
+
+    // Rejecting git hooks, shared by every worktree via the common dir.
+    let hooks = repo.join(".git/hooks");
+    std::fs::create_dir_all(&hooks).unwrap();
+    for hook in ["pre-commit", "pre-merge-commit"] {
+        let path = hooks.join(hook);
+        std::fs::write(&path, "#!/bin/sh\nexit 1\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+    }
+
+    let merge = |extra: &[&str]| {
+        Command::new(wt_binary())
+            .args(["merge", "-s", "squash"])
+            .args(extra)
+            .current_dir(&wt_path)
+            .env("HOME", &home)
+            .output()
+            .expect("wt merge failed")
+    };
+
+    let output = merge(&[]);
+    assert!(!output.status.success(), "pre-commit hook should block");
+    assert!(!repo.join("slow-hooks.txt").exists());
+
+    let output = merge(&["--no-verify"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "--no-verify merge failed: {stderr}"
+    );
+    assert!(repo.join("slow-hooks.txt").exists());
+}
+
+#[test]
+fn test_merge_routes_branch_to_trunk_rule_target() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    std::fs::write(
+        repo.join(".agent-worktree.toml"),
+        "[[trunk_rules]]\npattern = \"hotfix-*\"\ntrunk = \"release\"\n",
+    )
+    .unwrap();
+    for args in [
+        &["add", ".agent-worktree.toml"][..],
+        &["commit", "-m", "Add trunk rules"],
+        &["branch", "release"],
+    ] {
+        Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+    }
+
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "hotfix-crash");
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "-s", "squash"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "merge failed: {stderr}");
+    assert!(stderr.contains("into release"), "stderr: {stderr}");
+
+    let in_branch = |branch: &str| {
+        Command::new("git")
+            .args(["cat-file", "-e", &format!("{branch}:hotfix-crash.txt")])
+            .current_dir(&repo)
+            .status()
+            .unwrap()
+            .success()
+    };
+    assert!(in_branch("release"));
+    assert!(!in_branch("main"));
+}
+
+#[test]
+fn test_merge_ff_only_fast_forwards_linear_branch() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "ff-linear");
+    let head = |cwd: &std::path::Path| {
+        let out = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+    let branch_head = head(&wt_path);
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "-s", "ff-only"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+    assert!(
+        output.status.success(),
+        "ff-only merge failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // No merge commit: trunk now points at the branch tip.
+    assert_eq!(head(&repo), branch_head);
+}
+
+#[test]
+fn test_merge_ff_only_rejects_diverged_trunk() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "ff-diverged");
+
+    std::fs::write(repo.join("trunk.txt"), "trunk\n").unwrap();
+    Command::new("git")
+        .args(["add", "."])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    Command::new("git")
+        .args(["commit", "-m", "Trunk moves on"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["merge", "-s", "ff-only"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt merge failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    assert!(stderr.contains("Cannot fast-forward"), "stderr: {stderr}");
+    assert!(!repo.join("ff-diverged.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_merge_abort_recovers_from_killed_merge() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (dir, repo, home) = setup_worktree_test_env();
+    let first = new_worktree_with_commit(dir.path(), &repo, &home, "killed");
+    let second = new_worktree_with_commit(dir.path(), &repo, &home, "killed-too");
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    let wt = |cwd: &Path, args: &[&str]| {
+        Command::new(wt_binary())
+            .args(args)
+            .current_dir(cwd)
+            .env("HOME", &home)
+            .output()
+            .expect("wt failed")
+    };
+    // Main repo off trunk, so wt merge has to check trunk out there
+    git(&["checkout", "-q", "-b", "parked"]);
+
+    // pre-commit kills `wt` (the parent of `git commit`) mid squash commit
+    let hook = repo.join(".git/hooks/pre-commit");
+    std::fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    std::fs::write(
+        &hook,
+        "#!/bin/sh\nkill -9 $(ps -o ppid= -p $PPID)\nexit 1\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let output = wt(&first, &["merge", "-s", "squash"]);
+    assert!(output.status.code().is_none(), "wt should have been killed");
+    assert_eq!(git(&["branch", "--show-current"]), "main");
+    assert!(!git(&["diff", "--cached", "--name-only"]).is_empty());
+
+    // Clean up by hand (leaving the first marker behind), park on another
+    // branch, and get the second merge killed too.
+    git(&["reset", "-q", "--merge"]);
+    git(&["checkout", "-q", "-b", "parked-too"]);
+    let output = wt(&second, &["merge", "-s", "squash"]);
+    assert!(output.status.code().is_none(), "wt should have been killed");
+
+    // Markers live outside the shared .git dir, one per merged branch
+    let in_git_dir: Vec<_> = std::fs::read_dir(repo.join(".git"))
+        .unwrap()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("WT_MERGE"))
+        .collect();
+    assert!(in_git_dir.is_empty(), "{in_git_dir:?}");
+
+    // Each worktree's abort goes back to what its own merge recorded
+    let output = wt(&second, &["merge", "--abort"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "abort failed: {stderr}");
+    assert_eq!(git(&["branch", "--show-current"]), "parked-too");
+    assert!(git(&["status", "--porcelain"]).is_empty());
+
+    let output = wt(&first, &["merge", "--abort"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "abort failed: {stderr}");
+    assert_eq!(git(&["branch", "--show-current"]), "parked");
+    assert!(git(&["status", "--porcelain"]).is_empty());
+
+    // Nothing left to abort
+    for cwd in [&first, &second] {
+        let output = wt(cwd, &["merge", "--abort"]);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("No interrupted wt merge"));
+    }
+}
+
+#[test]
+fn test_merge_delete_remote_removes_pushed_branch() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let remote = dir.path().join("remote.git");
+    let git = |cwd: &std::path::Path, args: &[&str]| {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?}: {output:?}");
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    git(
+        dir.path(),
+        &["init", "-q", "--bare", remote.to_str().unwrap()],
+    );
+    git(
+        &repo,
+        &["remote", "add", "origin", remote.to_str().unwrap()],
+    );
+
+    let pushed = new_worktree_with_commit(dir.path(), &repo, &home, "pushed");
+    git(&pushed, &["push", "-q", "origin", "pushed"]);
+    let local = new_worktree_with_commit(dir.path(), &repo, &home, "local");
+
+    let merge = |wt: &std::path::Path| {
+        let output = Command::new(wt_binary())
+            .args(["merge", "-d", "--delete-remote"])
+            .current_dir(wt)
+            .env("HOME", &home)
+            .output()
+            .expect("wt merge failed");
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        assert!(output.status.success(), "merge failed: {stderr}");
+        stderr
+    };
+
+    merge(&pushed);
+    let branches = git(&remote, &["branch", "--list"]);
+    assert!(!branches.contains("pushed"), "remote branches: {branches}");
+
+    // Never pushed: the merge still succeeds, with a warning
+    let stderr = merge(&local);
+    assert!(stderr.contains("origin has no branch 'local'"), "{stderr}");
+    assert!(!local.exists());
+}
+
+#[test]
+fn test_merge_delete_remote_requires_delete() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    let output = Command::new(wt_binary())
+        .args(["merge", "--delete-remote"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--delete"));
+}
+
+#[test]
+fn test_merge_and_sync_refuse_detached_head() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree_with_commit(dir.path(), &repo, &home, "detached");
+    let output = Command::new("git")
+        .args(["checkout", "-q", "--detach"])
+        .current_dir(&wt_path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    for cmd in ["merge", "sync"] {
+        let output = Command::new(wt_binary())
+            .arg(cmd)
+            .current_dir(&wt_path)
+            .env("HOME", &home)
+            .output()
+            .expect("wt failed");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success(), "wt {cmd} should fail");
+        assert!(stderr.contains("HEAD is detached"), "wt {cmd}: {stderr}");
+    }
+}
+
+#[test]
+fn test_merge_edit_uses_edited_message() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let subject = |repo: &std::path::Path| {
+        let output = Command::new("git")
+            .args(["log", "-1", "--format=%s", "main"])
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    let merge = |wt: &std::path::Path, editor: &str| {
+        let output = Command::new(wt_binary())
+            .args(["merge", "--edit"])
+            .current_dir(wt)
+            .env("HOME", &home)
+            .env("GIT_EDITOR", editor)
+            .output()
+            .expect("wt merge failed");
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        assert!(output.status.success(), "merge failed: {stderr}");
+        stderr
+    };
+
+    let wt = new_worktree_with_commit(dir.path(), &repo, &home, "edited");
+    merge(&wt, "sed -i.bak -e '1s/^/Edited: /'");
+    assert_eq!(subject(&repo), "Edited: Add feature");
+
+    // A failing editor keeps the generated message
+    let wt = new_worktree_with_commit(dir.path(), &repo, &home, "kept");
+    let stderr = merge(&wt, "false");
+    assert!(
+        stderr.contains("using the generated commit message"),
+        "{stderr}"
+    );
+    assert_eq!(subject(&repo), "Add feature");
+
+    // The scratch file is per branch and outside the shared .git dir
+    let seen = dir.path().join("editor-saw");
+    let wt = new_worktree_with_commit(dir.path(), &repo, &home, "where");
+    merge(&wt, &format!("echo >{}", seen.display()));
+    let file = PathBuf::from(std::fs::read_to_string(&seen).unwrap().trim());
+    assert!(!file.starts_with(repo.join(".git")), "{}", file.display());
+    assert_eq!(file.file_name().unwrap(), "where.merge-msg");
+    assert!(!file.exists(), "scratch file left behind");
+}
+
+#[test]
+fn test_merge_edit_rejects_non_squash() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt = new_worktree_with_commit(dir.path(), &repo, &home, "no-edit");
+    let output = Command::new(wt_binary())
+        .args(["merge", "--edit", "-s", "merge"])
+        .current_dir(&wt)
+        .env("HOME", &home)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--edit only applies"));
+}
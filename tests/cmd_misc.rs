@@ -45,3 +45,105 @@ fn test_version_output() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("wt") || stdout.contains("0."));
 }
+
+#[cfg(unix)]
+#[test]
+fn test_git_binary_config_and_wt_git_env() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    // Wrapper that records it ran, then defers to the real git
+    let marker = dir.path().join("wrapper-used");
+    let wrapper = dir.path().join("git-wrapper");
+    std::fs::write(
+        &wrapper,
+        format!("#!/bin/sh\ntouch '{}'\nexec git \"$@\"\n", marker.display()),
+    )
+    .unwrap();
+    std::fs::set_permissions(&wrapper, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::fs::write(
+        home.join(".agent-worktree/config.toml"),
+        format!("[general]\ngit_binary = \"{}\"\n", wrapper.display()),
+    )
+    .unwrap();
+
+    let output = Command::new(wt_binary())
+        .arg("ls")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt ls failed");
+    assert!(
+        output.status.success(),
+        "wt ls failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(marker.exists(), "configured git_binary was not used");
+
+    // WT_GIT overrides the config
+    let output = Command::new(wt_binary())
+        .arg("ls")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .env("WT_GIT", dir.path().join("no-such-git"))
+        .output()
+        .expect("wt ls failed");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_quiet_suppresses_progress_but_not_errors() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args(["-q", "new", "quiet-wt", "--path-file"])
+        .arg(&path_file)
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("Created worktree"), "stderr: {stderr}");
+    // The path file is the shell protocol, not progress output
+    assert!(read_path_file(&path_file).trim().ends_with("quiet-wt"));
+
+    let output = Command::new(wt_binary())
+        .args(["rm", "no-such-wt", "--quiet"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt rm failed");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("error:"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_verbose_prints_git_command_lines() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args(["-v", "new", "verbose-wt", "--path-file"])
+        .arg(&path_file)
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("+ git rev-parse"), "stderr: {stderr}");
+    assert!(stderr.contains("+ git worktree add"), "stderr: {stderr}");
+
+    let output = Command::new(wt_binary())
+        .arg("ls")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt ls failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("+ git"), "stderr: {stderr}");
+}
@@ -106,3 +106,231 @@ fn test_mv_renames_worktree() {
             || stderr.contains("error")
     );
 }
+
+#[test]
+fn test_mv_dot_keeps_snap_metadata() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "mv-snap", "--json", "-s", "claude --resume"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let old_path = std::path::PathBuf::from(json["path"].as_str().unwrap());
+    let old_meta = old_path.with_extension("toml");
+    let description = "carried across the rename";
+    Command::new(wt_binary())
+        .args(["describe", "mv-snap", description])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .unwrap();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "mv",
+            ".",
+            "mv-snap-renamed",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&old_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt mv failed");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let new_path = old_path.with_file_name("mv-snap-renamed");
+    assert_eq!(
+        read_path_file(&path_file).trim(),
+        new_path.to_str().unwrap()
+    );
+    assert!(!old_meta.exists(), "old metadata should be gone");
+
+    let meta: toml::Value =
+        toml::from_str(&std::fs::read_to_string(new_path.with_extension("toml")).unwrap()).unwrap();
+    assert_eq!(
+        meta["snap_state"]["command"].as_str(),
+        Some("claude --resume")
+    );
+    assert_eq!(meta["description"].as_str(), Some(description));
+    assert_eq!(meta["base_branch"].as_str(), Some("main"));
+}
+
+#[test]
+fn test_mv_refuses_worktree_mid_merge() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "mv-busy", "--json"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let wt_path = std::path::PathBuf::from(json["path"].as_str().unwrap());
+
+    // Conflicting edits to README.md, then leave the merge unresolved
+    let git = |cwd: &std::path::Path, args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap()
+    };
+    std::fs::write(wt_path.join("README.md"), "worktree\n").unwrap();
+    git(&wt_path, &["commit", "-am", "Worktree edit"]);
+    std::fs::write(repo.join("README.md"), "main\n").unwrap();
+    git(&repo, &["commit", "-am", "Main edit"]);
+    assert!(!git(&wt_path, &["merge", "main"]).status.success());
+
+    let mv = |extra: &[&str]| {
+        Command::new(wt_binary())
+            .args(["mv", "mv-busy", "mv-renamed"])
+            .args(extra)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt mv failed")
+    };
+    let output = mv(&[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("in progress"), "stderr: {stderr}");
+    assert!(wt_path.exists(), "worktree must not move");
+
+    assert!(mv(&["--force"]).status.success());
+    assert!(wt_path.with_file_name("mv-renamed").exists());
+}
+
+#[test]
+fn test_rename_uses_current_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "rename-src", "--json"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let old_path = std::path::PathBuf::from(json["path"].as_str().unwrap());
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "rename",
+            "rename-dst",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&old_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt rename failed");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let new_path = old_path.with_file_name("rename-dst");
+    assert!(!old_path.exists());
+    assert!(new_path.is_dir());
+    assert!(new_path.with_extension("toml").exists());
+    assert_eq!(
+        read_path_file(&path_file).trim(),
+        new_path.to_str().unwrap()
+    );
+}
+
+#[test]
+fn test_rename_outside_worktree_fails() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["rename", "whatever"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt rename failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Not inside a managed worktree"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn test_mv_failed_move_keeps_branch_and_meta() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let path_file = create_path_file(dir.path());
+
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "locked-src",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let wt_path = std::path::PathBuf::from(read_path_file(&path_file).trim());
+    let meta = wt_path.with_extension("toml");
+    assert!(meta.exists(), "meta missing: {}", meta.display());
+
+    // A locked worktree makes `git worktree move` fail
+    let output = Command::new("git")
+        .args(["worktree", "lock"])
+        .arg(&wt_path)
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(wt_binary())
+        .args(["mv", "locked-src", "locked-dst"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt mv failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("git worktree unlock"), "stderr: {stderr}");
+
+    // Branch renamed back, worktree and meta untouched
+    let branches = Command::new("git")
+        .args(["branch", "--format=%(refname:short)"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    let branches = String::from_utf8_lossy(&branches.stdout);
+    assert!(branches.lines().any(|b| b == "locked-src"), "{branches}");
+    assert!(!branches.lines().any(|b| b == "locked-dst"), "{branches}");
+    assert!(wt_path.is_dir());
+    assert!(meta.exists());
+    assert!(!wt_path.with_file_name("locked-dst.toml").exists());
+}
@@ -4,6 +4,7 @@
 
 mod common;
 
+use std::path::PathBuf;
 use std::process::Command;
 use tempfile::tempdir;
 
@@ -250,3 +251,732 @@ fn test_nested_snap_is_rejected() {
         "stderr should explain nested rejection: {stderr}"
     );
 }
+
+#[test]
+fn test_new_json_output() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "json-feature",
+            "--json",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new --json failed");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(json["branch"], "json-feature");
+    assert_eq!(json["base"], "main");
+    assert_eq!(json["trunk"], "main");
+    assert!(json["snap_command"].is_null());
+
+    // Path file is still written alongside the JSON
+    let wt_path = read_path_file(&path_file);
+    assert_eq!(json["path"], wt_path.trim());
+    assert!(wt_path.contains("json-feature"));
+}
+
+#[test]
+fn test_new_json_output_with_snap() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "json-snap", "--json", "-s", "claude --resume"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new --json -s failed");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(json["branch"], "json-snap");
+    assert_eq!(json["snap_command"], "claude --resume");
+}
+
+#[test]
+fn test_new_snap_resolves_agent_preset() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    std::fs::write(
+        repo.join(".agent-worktree.toml"),
+        "[agent]\nfast = \"claude --model haiku\"\n",
+    )
+    .unwrap();
+
+    let new = |branch: &str, snap: &str| {
+        let output = Command::new(wt_binary())
+            .args(["new", branch, "--json", "-s", snap])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new --json -s failed");
+        assert!(
+            output.status.success(),
+            "Command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+        (json, String::from_utf8_lossy(&output.stderr).to_string())
+    };
+
+    let (json, _) = new("preset-fast", "@fast");
+    assert_eq!(json["snap_command"], "claude --model haiku");
+
+    // Unknown preset falls back to the literal string, with a warning
+    let (json, stderr) = new("preset-missing", "@slow");
+    assert_eq!(json["snap_command"], "@slow");
+    assert!(stderr.contains("no [agent] preset"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_new_applies_branch_prefix() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    std::fs::write(
+        repo.join(".agent-worktree.toml"),
+        "[general]\nbranch_prefix = \"agent/\"\n",
+    )
+    .unwrap();
+
+    let new = |args: &[&str]| {
+        let output = Command::new(wt_binary())
+            .args(["new", "--json"])
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new --json failed");
+        assert!(
+            output.status.success(),
+            "Command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+        (
+            json["branch"].as_str().unwrap().to_string(),
+            PathBuf::from(json["path"].as_str().unwrap()),
+        )
+    };
+
+    let (branch, path) = new(&["fix-login"]);
+    assert_eq!(branch, "agent/fix-login");
+    assert!(path.ends_with("agent/fix-login") && path.is_dir());
+
+    // Already prefixed: not doubled
+    let (branch, _) = new(&["agent/explicit"]);
+    assert_eq!(branch, "agent/explicit");
+
+    let (branch, path) = new(&[]);
+    assert!(branch.starts_with("agent/"), "branch: {branch}");
+    assert!(path.ends_with(&branch) && path.is_dir());
+}
+
+#[test]
+fn test_new_finds_project_config_from_nested_subdir() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    let nested = repo.join("pkg").join("src");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(
+        repo.join("pkg").join(".agent-worktree.toml"),
+        "[general]\ntrunk = \"develop\"\n",
+    )
+    .unwrap();
+    Command::new("git")
+        .args(["branch", "develop"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "nested-cfg", "--json"])
+        .current_dir(&nested)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new --json failed");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(json["trunk"], "develop");
+}
+
+#[test]
+fn test_new_honors_home_and_workspaces_env_overrides() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let base = dir.path().join("wt-home");
+    let workspaces = dir.path().join("wt-spaces");
+
+    let output = Command::new(wt_binary())
+        .args(["new", "env-dirs", "--json"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .env_remove("AGENT_WORKTREE_DIR")
+        .env("AGENT_WORKTREE_HOME", &base)
+        .env("WT_WORKSPACES_DIR", &workspaces)
+        .output()
+        .expect("wt new --json failed");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let path = PathBuf::from(json["path"].as_str().unwrap());
+    assert!(
+        path.starts_with(workspaces.canonicalize().unwrap()),
+        "path: {}",
+        path.display()
+    );
+    assert!(path.is_dir());
+    assert!(!home.join(".agent-worktree").join("workspaces").exists());
+}
+
+#[test]
+fn test_new_no_copy_and_extra_copy_patterns() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    std::fs::write(
+        repo.join(".agent-worktree.toml"),
+        "[general]\ncopy_files = [\".env\"]\n",
+    )
+    .unwrap();
+    std::fs::write(repo.join(".env"), "SECRET=1\n").unwrap();
+    std::fs::write(repo.join("notes.local"), "local\n").unwrap();
+
+    let new = |args: &[&str]| {
+        let output = Command::new(wt_binary())
+            .args(["new", "--json"])
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new --json failed");
+        assert!(
+            output.status.success(),
+            "Command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+        PathBuf::from(json["path"].as_str().unwrap())
+    };
+
+    let path = new(&["copy-default"]);
+    assert!(path.join(".env").exists());
+    assert!(!path.join("notes.local").exists());
+
+    let path = new(&["copy-extra", "--copy", "*.local"]);
+    assert!(path.join(".env").exists());
+    assert!(path.join("notes.local").exists());
+
+    let path = new(&["copy-none", "--no-copy"]);
+    assert!(!path.join(".env").exists());
+}
+
+#[test]
+fn test_new_respects_max_worktrees() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    std::fs::write(
+        repo.join(".agent-worktree.toml"),
+        "[general]\nmax_worktrees = 1\n",
+    )
+    .unwrap();
+
+    let new = |branch: &str, force: bool| {
+        let mut cmd = Command::new(wt_binary());
+        cmd.args(["new", branch]);
+        if force {
+            cmd.arg("--force");
+        }
+        cmd.current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed")
+    };
+
+    // Below the limit
+    let output = new("limit-one", false);
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // At the limit
+    let output = new("limit-two", false);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Worktree limit reached (1/1"), "{stderr}");
+    assert!(stderr.contains("wt clean"), "{stderr}");
+
+    // --force overrides
+    let output = new("limit-two", true);
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Above the limit
+    let output = new("limit-three", false);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("(2/1"));
+}
+
+#[test]
+fn test_new_max_worktrees_holds_under_concurrency() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    std::fs::write(
+        repo.join(".agent-worktree.toml"),
+        "[general]\nmax_worktrees = 2\n",
+    )
+    .unwrap();
+
+    let children: Vec<_> = (0..5)
+        .map(|i| {
+            Command::new(wt_binary())
+                .args(["new", &format!("race-{i}")])
+                .current_dir(&repo)
+                .env("HOME", &home)
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+                .expect("wt new failed to spawn")
+        })
+        .collect();
+    let created = children
+        .into_iter()
+        .map(|mut child| child.wait().unwrap())
+        .filter(|status| status.success())
+        .count();
+    assert_eq!(created, 2, "the limit must hold for racing wt new runs");
+}
+
+#[test]
+fn test_new_issue_key_name_used_verbatim() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "PROJ-123",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let wt_path = read_path_file(&path_file);
+    assert!(wt_path.trim().ends_with("/PROJ-123"), "path: {wt_path}");
+
+    let head = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(wt_path.trim())
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&head.stdout).trim(), "PROJ-123");
+}
+
+/// A second `wt new PROJ-123` must fail, not quietly become `PROJ-123-2`.
+#[test]
+fn test_new_issue_key_collision_errors_without_suffix() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let new = || {
+        Command::new(wt_binary())
+            .args(["new", "PROJ-123"])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed")
+    };
+    assert!(new().status.success());
+
+    let output = new();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("already exists"), "{stderr}");
+
+    let branches = Command::new("git")
+        .args(["branch", "--list", "PROJ-123*"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    let branches = String::from_utf8_lossy(&branches.stdout);
+    assert_eq!(branches.lines().count(), 1, "{branches}");
+}
+
+#[test]
+fn test_new_rejects_invalid_branch_name() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "bad..name"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not a valid branch name"), "{stderr}");
+}
+
+#[test]
+fn test_new_base_same_as_branch_errors() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    // Branch exists as base, and is also the requested name
+    let output = Command::new(wt_binary())
+        .args(["new", "main", "--base", "main"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("from itself"), "{stderr}");
+}
+
+#[test]
+fn test_new_nonexistent_base_errors() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "foo", "--base", "foo"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Branch 'foo' does not exist"), "{stderr}");
+
+    let branches = Command::new("git")
+        .args(["branch", "--list", "foo"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&branches.stdout).trim().is_empty());
+}
+
+#[test]
+fn test_new_warns_when_name_exists_on_remote() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let remote = dir.path().join("remote.git");
+    let git = |args: &[&str]| {
+        let out = Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+    };
+    git(&["init", "--bare", remote.to_str().unwrap()]);
+    git(&["remote", "add", "origin", remote.to_str().unwrap()]);
+    git(&["push", "origin", "main", "main:remote-only"]);
+    git(&["fetch", "origin"]);
+
+    let output = Command::new(wt_binary())
+        .args(["new", "remote-only"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "Command failed: {stderr}");
+    assert!(
+        stderr.contains("'remote-only' already exists on origin"),
+        "stderr: {stderr}"
+    );
+
+    // A purely local name stays quiet
+    let output = Command::new(wt_binary())
+        .args(["new", "local-only"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("already exists on"));
+}
+
+#[test]
+fn test_new_pull_starts_from_fetched_remote_base() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let remote = dir.path().join("remote.git");
+    let other = dir.path().join("other");
+    let git = |cwd: &std::path::Path, args: &[&str]| {
+        let out = Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+    git(
+        dir.path(),
+        &["init", "-q", "--bare", remote.to_str().unwrap()],
+    );
+    git(
+        &repo,
+        &["remote", "add", "origin", remote.to_str().unwrap()],
+    );
+    git(&repo, &["push", "-q", "origin", "main"]);
+    // Someone else moves origin/main; the local main is now behind
+    git(
+        dir.path(),
+        &[
+            "clone",
+            "-q",
+            "-b",
+            "main",
+            remote.to_str().unwrap(),
+            other.to_str().unwrap(),
+        ],
+    );
+    std::fs::write(other.join("upstream.txt"), "new\n").unwrap();
+    git(&other, &["add", "upstream.txt"]);
+    git(
+        &other,
+        &[
+            "-c",
+            "user.name=T",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-qm",
+            "Upstream",
+        ],
+    );
+    git(&other, &["push", "-q", "origin", "main"]);
+
+    let wt = |args: &[&str]| {
+        Command::new(wt_binary())
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed")
+    };
+
+    let output = wt(&["new", "fresh", "--pull", "--json"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    // Merge target is still the local branch
+    assert_eq!(json["base"], "main");
+    let path = PathBuf::from(json["path"].as_str().unwrap());
+    assert!(path.join("upstream.txt").exists());
+    let upstream = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "fresh@{upstream}"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(
+        !upstream.status.success(),
+        "fresh should not track origin/main"
+    );
+
+    let output = wt(&["new", "stale"]);
+    assert!(output.status.success());
+    assert!(!repo.join("upstream.txt").exists());
+
+    git(&repo, &["branch", "local-base"]);
+    let output = wt(&["new", "x", "--base", "local-base", "--pull"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("origin has no branch 'local-base'"));
+}
+
+#[test]
+fn test_new_base_commit_expressions() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+    let git = |args: &[&str]| {
+        let out = Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+    let first = git(&["rev-parse", "HEAD"]);
+    git(&["commit", "-q", "--allow-empty", "-m", "Second"]);
+
+    let wt = |args: &[&str]| {
+        let output = Command::new(wt_binary())
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed");
+        assert!(
+            output.status.success(),
+            "wt {args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+    let head_of = |json: &serde_json::Value| {
+        let out = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(json["path"].as_str().unwrap())
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+
+    for (branch, base) in [("from-tilde", "HEAD~1"), ("from-reflog", "HEAD@{1}")] {
+        let json = wt(&["new", branch, "--base", base, "--json"]);
+        assert_eq!(head_of(&json), first, "{base}");
+        // Only the starting point: merges still go to the current branch
+        assert_eq!(json["base"], "main");
+    }
+
+    let output = Command::new(wt_binary())
+        .args(["new", "nope", "--base", "HEAD~9"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'HEAD~9' does not exist"));
+}
+
+#[test]
+fn test_new_count_creates_several_worktrees() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let path_file = create_path_file(dir.path());
+
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "--count",
+            "3",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new --count failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "Command failed: {stderr}");
+
+    let paths: Vec<PathBuf> = stderr
+        .lines()
+        .filter_map(|l| l.strip_prefix("Path: "))
+        .map(PathBuf::from)
+        .collect();
+    assert_eq!(paths.len(), 3, "stderr: {stderr}");
+    assert!(paths.iter().all(|p| p.is_dir()));
+    let mut names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap()).collect();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), 3, "names must be unique: {names:?}");
+
+    // cd target is the last one created
+    assert_eq!(PathBuf::from(read_path_file(&path_file).trim()), paths[2]);
+}
+
+#[test]
+fn test_new_count_rejects_branch_name() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "feat", "--count", "2"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new --count failed");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--count"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_parallel_new_creates_distinct_worktrees() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let run_new = |args: &'static [&'static str]| {
+        let (repo, home) = (repo.clone(), home.clone());
+        std::thread::spawn(move || {
+            Command::new(wt_binary())
+                .args(args)
+                .current_dir(&repo)
+                .env("HOME", &home)
+                .output()
+                .expect("wt new failed")
+        })
+    };
+
+    // Generated names: every run succeeds, each with its own branch
+    let outputs: Vec<_> = (0..6)
+        .map(|_| run_new(&["new", "--json"]))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|h| h.join().unwrap())
+        .collect();
+    let mut branches = Vec::new();
+    for output in &outputs {
+        assert!(
+            output.status.success(),
+            "parallel wt new failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        branches.push(json["branch"].as_str().unwrap().to_string());
+    }
+    branches.sort();
+    branches.dedup();
+    assert_eq!(branches.len(), 6, "duplicate branches: {branches:?}");
+
+    // Same explicit name: exactly one wins, the rest get a clean error
+    let outputs: Vec<_> = (0..4)
+        .map(|_| run_new(&["new", "contended"]))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|h| h.join().unwrap())
+        .collect();
+    assert_eq!(outputs.iter().filter(|o| o.status.success()).count(), 1);
+    for output in outputs.iter().filter(|o| !o.status.success()) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("already exists"), "stderr: {stderr}");
+    }
+}
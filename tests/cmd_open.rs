@@ -0,0 +1,79 @@
+// ===========================================================================
+// Integration Tests - Open Command
+// ===========================================================================
+
+mod common;
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use common::*;
+
+#[test]
+fn test_open_runs_editor_in_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args(["new", "open-me", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let wt_path = PathBuf::from(read_path_file(&path_file).trim());
+
+    // `touch opened .` run with CWD = worktree leaves a marker there
+    let output = Command::new(wt_binary())
+        .args(["open", "open-me"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .env_remove("VISUAL")
+        .env("EDITOR", "touch opened")
+        .output()
+        .expect("wt open failed");
+
+    assert!(
+        output.status.success(),
+        "wt open failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(wt_path.join("opened").exists());
+}
+
+#[test]
+fn test_open_without_editor_lists_options() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["open", "."])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .env_remove("VISUAL")
+        .env_remove("EDITOR")
+        .output()
+        .expect("wt open failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No editor configured"), "{stderr}");
+    assert!(
+        stderr.contains("$VISUAL") && stderr.contains("$EDITOR"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_open_nonexistent_worktree() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["open", "no-such-branch"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .env("EDITOR", "true")
+        .output()
+        .expect("wt open failed");
+
+    assert!(!output.status.success());
+}
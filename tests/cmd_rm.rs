@@ -156,3 +156,88 @@ fn test_rm_dot_with_wrapper_works() {
     );
     assert!(!wt_path.exists(), "worktree should be removed");
 }
+
+#[test]
+fn test_rm_finds_worktree_added_outside_workspace() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let manual = dir.path().join("manual-wt");
+    let output = Command::new("git")
+        .args(["worktree", "add", "-b", "manual"])
+        .arg(&manual)
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(wt_binary())
+        .args(["rm", "manual"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt rm failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "wt rm failed: {stderr}");
+    assert!(!manual.exists());
+}
+
+#[test]
+fn test_rm_without_branch_removes_current_worktree_after_confirm() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args(["new", "rm-cwd", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let wt_path = PathBuf::from(read_path_file(&path_file).trim());
+    let subdir = wt_path.join("nested");
+    std::fs::create_dir_all(&subdir).unwrap();
+
+    // Non-TTY without --yes: refuses rather than removing unasked
+    let output = Command::new(wt_binary())
+        .args(["rm", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(&subdir)
+        .env("HOME", &home)
+        .output()
+        .expect("wt rm failed");
+    assert!(!output.status.success());
+    assert!(wt_path.exists());
+
+    let output = Command::new(wt_binary())
+        .args(["rm", "-y", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(&subdir)
+        .env("HOME", &home)
+        .output()
+        .expect("wt rm failed");
+    assert!(
+        output.status.success(),
+        "wt rm failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!wt_path.exists());
+    assert_eq!(
+        PathBuf::from(read_path_file(&path_file).trim())
+            .canonicalize()
+            .unwrap(),
+        repo.canonicalize().unwrap()
+    );
+}
+
+#[test]
+fn test_rm_without_branch_outside_worktree_fails() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .arg("rm")
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt rm failed");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Not inside a worktree"), "stderr: {stderr}");
+}
@@ -45,6 +45,53 @@ fn test_new_with_snap_outputs_two_lines() {
         .output();
 }
 
+#[test]
+fn test_new_bare_snap_uses_default_snap_command() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let path_file = create_path_file(dir.path());
+    let new_snap = |branch: &str| {
+        Command::new(wt_binary())
+            .args([
+                "new",
+                branch,
+                "--path-file",
+                path_file.to_str().unwrap(),
+                "-s",
+            ])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt new failed")
+    };
+
+    // Neither a command nor a default: refuse before creating anything
+    let output = new_snap("no-default");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("default_snap_command"));
+    assert!(!path_file.exists());
+
+    std::fs::write(
+        repo.join(".agent-worktree.toml"),
+        "[general]\ndefault_snap_command = \"@fast\"\n\n[agent]\nfast = \"claude --model haiku\"\n",
+    )
+    .unwrap();
+    let output = new_snap("with-default");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let content = read_path_file(&path_file);
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("with-default"));
+    assert_eq!(lines[1], "claude --model haiku");
+
+    let meta = std::path::PathBuf::from(lines[0]).with_extension("toml");
+    let meta: toml::Value = toml::from_str(&std::fs::read_to_string(meta).unwrap()).unwrap();
+    assert_eq!(meta["snap_command"].as_str(), Some("claude --model haiku"));
+}
+
 #[test]
 fn test_new_with_snap_creates_metadata() {
     let dir = tempdir().unwrap();
@@ -78,11 +125,11 @@ fn test_new_with_snap_creates_metadata() {
     let meta_path = workspace_dir.path().join("snap-meta-test.toml");
     assert!(meta_path.exists());
 
-    // snap command no longer persisted in meta — it flows through path_file only.
+    // Kept past the session so `wt snap <branch>` can rerun it
     let content = std::fs::read_to_string(&meta_path).unwrap();
     assert!(content.contains("base_branch"));
     assert!(content.contains("created_at"));
-    assert!(!content.contains("snap_command"));
+    assert!(content.contains("snap_command = \"agent\""));
 
     let _ = Command::new(wt_binary())
         .args(["rm", "snap-meta-test", "-f"])
@@ -107,3 +154,320 @@ fn test_snap_continue_not_in_worktree() {
         "snap-continue should fail outside worktree"
     );
 }
+
+#[test]
+fn test_new_snap_without_shell_integration_creates_nothing() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["new", "direct-snap", "-s", "claude"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("wt setup"));
+
+    // No worktree, branch or snap session left behind to abort
+    let branches = Command::new("git")
+        .args(["branch", "--list", "direct-snap"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&branches.stdout).trim().is_empty());
+    let ls = Command::new(wt_binary())
+        .args(["ls"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .unwrap();
+    assert!(!String::from_utf8_lossy(&ls.stdout).contains("direct-snap"));
+
+    // --json hands the agent loop to the caller instead
+    let output = Command::new(wt_binary())
+        .args(["new", "direct-snap", "-s", "claude", "--json"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_snap_continue_no_changes_removes_worktree_and_branch() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "snap-idle",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let wt_path = std::path::PathBuf::from(read_path_file(&path_file).trim());
+
+    // Runs from inside the worktree it deletes, as the shell wrapper does.
+    let output = Command::new(wt_binary())
+        .args(["snap-continue", "--path-file", path_file.to_str().unwrap()])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .output()
+        .expect("wt snap-continue failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "snap-continue failed: {stderr}");
+    assert!(!wt_path.exists());
+    assert!(!wt_path.with_extension("toml").exists(), "meta left behind");
+
+    let branches = Command::new("git")
+        .args(["branch", "--list", "snap-idle"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&branches.stdout).trim().is_empty());
+}
+
+fn new_snap_worktree(
+    dir: &std::path::Path,
+    repo: &std::path::Path,
+    home: &std::path::Path,
+    branch: &str,
+) -> std::path::PathBuf {
+    let path_file = create_path_file(dir);
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            branch,
+            "-s",
+            "claude",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(repo)
+        .env("HOME", home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let content = read_path_file(&path_file);
+    std::path::PathBuf::from(content.lines().next().unwrap())
+}
+
+#[test]
+fn test_snap_abort_clears_state_and_preserves_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_snap_worktree(dir.path(), &repo, &home, "snap-orphan");
+    let meta = wt_path.with_extension("toml");
+    assert!(std::fs::read_to_string(&meta)
+        .unwrap()
+        .contains("[snap_state]"));
+
+    // From the main repo: no need to be inside the worktree
+    let output = Command::new(wt_binary())
+        .args(["snap-abort", "snap-orphan"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt snap-abort failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "snap-abort failed: {stderr}");
+    assert!(wt_path.exists(), "worktree should be preserved");
+    assert!(!std::fs::read_to_string(&meta)
+        .unwrap()
+        .contains("snap_state"));
+
+    // Second abort: nothing left to abort
+    let output = Command::new(wt_binary())
+        .args(["snap-abort", "snap-orphan"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt snap-abort failed");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no active snap session"));
+}
+
+#[test]
+fn test_snap_abort_rm_removes_worktree_with_yes() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_snap_worktree(dir.path(), &repo, &home, "snap-gone");
+
+    let output = Command::new(wt_binary())
+        .args(["snap-abort", "snap-gone", "--rm", "--yes"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt snap-abort failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "snap-abort --rm failed: {stderr}");
+    assert!(!wt_path.exists());
+    assert!(!wt_path.with_extension("toml").exists());
+}
+
+#[test]
+fn test_snap_abort_rm_without_tty_refuses() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_snap_worktree(dir.path(), &repo, &home, "snap-ask");
+
+    let output = Command::new(wt_binary())
+        .args(["snap-abort", "snap-ask", "--rm"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("wt snap-abort failed");
+
+    assert!(!output.status.success());
+    assert!(wt_path.exists());
+}
+
+#[test]
+fn test_snap_reenters_existing_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let output = Command::new(wt_binary())
+        .args(["new", "snap-again", "--json"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let wt_path = std::path::PathBuf::from(json["path"].as_str().unwrap());
+    let meta = wt_path.with_extension("toml");
+
+    let snap = |path_file: Option<&std::path::Path>| {
+        let mut cmd = Command::new(wt_binary());
+        cmd.args(["snap", "snap-again"]);
+        if let Some(file) = path_file {
+            cmd.args(["--path-file", file.to_str().unwrap()]);
+        }
+        cmd.args(["--", "claude", "--continue"])
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt snap failed")
+    };
+
+    // No shell integration: refuse without recording a session
+    let output = snap(None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("wt setup"));
+    assert!(!std::fs::read_to_string(&meta)
+        .unwrap()
+        .contains("snap_state"));
+
+    let path_file = create_path_file(dir.path());
+    let output = snap(Some(&path_file));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "wt snap failed: {stderr}");
+    let content = read_path_file(&path_file);
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines, [wt_path.to_str().unwrap(), "claude --continue"]);
+    assert!(std::fs::read_to_string(&meta)
+        .unwrap()
+        .contains("[snap_state]"));
+
+    // Session already active
+    let output = snap(Some(&path_file));
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already has an active snap session"));
+}
+
+#[test]
+fn test_snap_unknown_worktree_fails() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "snap",
+            "no-such-wt",
+            "--path-file",
+            path_file.to_str().unwrap(),
+            "--",
+            "claude",
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt snap failed");
+    assert!(!output.status.success());
+    assert!(!path_file.exists());
+}
+
+#[test]
+fn test_snap_without_command_reruns_last_one() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_snap_worktree(dir.path(), &repo, &home, "snap-rerun");
+
+    let wt = |args: &[&str]| {
+        Command::new(wt_binary())
+            .args(args)
+            .current_dir(&repo)
+            .env("HOME", &home)
+            .output()
+            .expect("wt failed")
+    };
+    let snap = |extra: &[&str]| {
+        let path_file = create_path_file(dir.path());
+        let mut args = vec![
+            "snap",
+            "snap-rerun",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ];
+        args.extend_from_slice(extra);
+        let output = wt(&args);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "wt snap failed: {stderr}");
+        let content = read_path_file(&path_file);
+        assert_eq!(content.lines().next(), wt_path.to_str());
+        wt(&["snap-abort", "snap-rerun"]);
+        content.lines().nth(1).unwrap().to_string()
+    };
+
+    // Session from `wt new -s claude` ended; reuse its command
+    assert!(wt(&["snap-abort", "snap-rerun"]).status.success());
+    assert_eq!(snap(&[]), "claude");
+
+    // A new command replaces the stored one
+    assert_eq!(snap(&["--", "codex", "--full-auto"]), "codex --full-auto");
+    assert_eq!(snap(&[]), "codex --full-auto");
+}
+
+#[test]
+fn test_snap_without_command_and_no_history_fails() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let output = Command::new(wt_binary())
+        .args(["new", "snap-fresh"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "snap",
+            "snap-fresh",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt snap failed");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No previous snap command"));
+}
@@ -178,3 +178,32 @@ fn test_status_with_base_branch() {
         "Expected base branch name, got: {stdout}"
     );
 }
+
+#[test]
+fn test_status_in_worktree_added_outside_workspace() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let manual = dir.path().join("manual-wt");
+    let output = Command::new("git")
+        .args(["worktree", "add", "-b", "manual"])
+        .arg(&manual)
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(wt_binary())
+        .arg("status")
+        .current_dir(&manual)
+        .env("HOME", &home)
+        .output()
+        .expect("wt status failed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Branch:       manual"), "stdout: {stdout}");
+    assert!(stdout.contains("manual-wt"), "stdout: {stdout}");
+}
@@ -306,3 +306,292 @@ fn test_sync_from_specific_branch() {
     assert!(output.status.success(), "sync --from failed: {stderr}");
     assert!(stderr.contains("source-branch"));
 }
+
+#[test]
+fn test_sync_named_worktree_from_main_repo() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree(dir.path(), &repo, &home, "sync-named");
+
+    commit_file(&wt_path, "feature.txt", "feature", "Feature work");
+    commit_file(&repo, "trunk.txt", "trunk", "Trunk update");
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "sync-named"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "sync failed: {stderr}");
+    assert!(wt_path.join("trunk.txt").exists(), "trunk commit missing");
+    assert!(wt_path.join("feature.txt").exists());
+
+    // Main repo is untouched: still on main, no feature file
+    let head = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&head.stdout).trim(), "main");
+    assert!(!repo.join("feature.txt").exists());
+}
+
+#[test]
+fn test_sync_named_worktree_from_another_worktree_merge() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let target = new_worktree(dir.path(), &repo, &home, "sync-target");
+    let other = new_worktree(dir.path(), &repo, &home, "sync-other");
+
+    commit_file(&repo, "trunk.txt", "trunk", "Trunk update");
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "sync-target", "-s", "merge"])
+        .current_dir(&other)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "sync failed: {stderr}");
+    assert!(target.join("trunk.txt").exists());
+    assert!(!other.join("trunk.txt").exists());
+}
+
+#[test]
+fn test_sync_named_worktree_conflict_reports_worktree() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree(dir.path(), &repo, &home, "sync-clash");
+
+    commit_file(&wt_path, "README.md", "worktree side\n", "Worktree edit");
+    commit_file(&repo, "README.md", "trunk side\n", "Trunk edit");
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "sync-clash"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'sync-clash' needs attention"), "{stderr}");
+    assert!(stderr.contains(wt_path.to_str().unwrap()), "{stderr}");
+}
+
+#[test]
+fn test_sync_unknown_worktree_fails() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "no-such-worktree"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not found"));
+}
+
+#[test]
+fn test_sync_rejects_path_outside_workspace() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "../.."])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("outside the workspace"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_sync_named_worktree_added_outside_workspace() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let manual = dir.path().join("manual-wt");
+    let output = Command::new("git")
+        .args(["worktree", "add", "-b", "manual"])
+        .arg(&manual)
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    commit_file(&repo, "trunk.txt", "trunk", "Trunk update");
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "manual"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "sync failed: {stderr}");
+    assert!(manual.join("trunk.txt").exists());
+}
+
+#[test]
+fn test_sync_interactive_rebases_with_sequence_editor() {
+    let (dir, repo, home) = setup_worktree_test_env();
+
+    let path_file = create_path_file(dir.path());
+    let output = Command::new(wt_binary())
+        .args([
+            "new",
+            "sync-inter",
+            "--path-file",
+            path_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt new failed");
+    assert!(output.status.success());
+    let wt_path = PathBuf::from(read_path_file(&path_file).trim());
+
+    for (cwd, file) in [(&wt_path, "feature.txt"), (&repo, "main-update.txt")] {
+        std::fs::write(cwd.join(file), file).unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", file])
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+    }
+
+    // `true` accepts the todo list unchanged, standing in for the editor.
+    let output = Command::new(wt_binary())
+        .args(["sync", "--interactive"])
+        .current_dir(&wt_path)
+        .env("HOME", &home)
+        .env("GIT_SEQUENCE_EDITOR", "true")
+        .output()
+        .expect("wt sync failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "sync -i failed: {stderr}");
+    assert!(wt_path.join("main-update.txt").exists());
+    assert!(wt_path.join("feature.txt").exists());
+}
+
+#[test]
+fn test_sync_interactive_rejects_merge_strategy() {
+    let (_dir, repo, home) = setup_worktree_test_env();
+
+    Command::new("git")
+        .args(["checkout", "-b", "sync-inter-merge"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "-i", "-s", "merge"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("--interactive requires"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn test_sync_fetch_warns_when_remote_unreachable() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree(dir.path(), &repo, &home, "sync-offline");
+    commit_file(&repo, "trunk.txt", "trunk", "Trunk update");
+
+    let missing = dir.path().join("no-such-remote.git");
+    Command::new("git")
+        .args(["remote", "add", "origin", missing.to_str().unwrap()])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "sync-offline", "--fetch"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "fetch failure must not be fatal: {stderr}"
+    );
+    assert!(
+        stderr.contains("Warning: fetch failed, syncing against local main"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        wt_path.join("trunk.txt").exists(),
+        "synced against local main"
+    );
+}
+
+#[test]
+fn test_sync_fetch_uses_remote_target() {
+    let (dir, repo, home) = setup_worktree_test_env();
+    let wt_path = new_worktree(dir.path(), &repo, &home, "sync-fresh");
+
+    // origin/main gets a commit the local main never sees
+    let remote = dir.path().join("remote.git");
+    let other = dir.path().join("other");
+    let git = |cwd: &std::path::Path, args: &[&str]| {
+        let out = Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+    };
+    git(
+        dir.path(),
+        &["init", "-q", "--bare", remote.to_str().unwrap()],
+    );
+    git(
+        &repo,
+        &["remote", "add", "origin", remote.to_str().unwrap()],
+    );
+    git(&repo, &["push", "-q", "origin", "main"]);
+    git(
+        dir.path(),
+        &[
+            "clone",
+            "-q",
+            "-b",
+            "main",
+            remote.to_str().unwrap(),
+            other.to_str().unwrap(),
+        ],
+    );
+    git(&other, &["config", "user.name", "Test"]);
+    git(&other, &["config", "user.email", "test@test.com"]);
+    commit_file(&other, "upstream.txt", "new", "Upstream");
+    git(&other, &["push", "-q", "origin", "main"]);
+
+    let output = Command::new(wt_binary())
+        .args(["sync", "sync-fresh", "--fetch"])
+        .current_dir(&repo)
+        .env("HOME", &home)
+        .output()
+        .expect("wt sync failed");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "sync failed: {stderr}");
+    assert!(!stderr.contains("Warning"), "stderr: {stderr}");
+    assert!(wt_path.join("upstream.txt").exists());
+}
@@ -5,7 +5,7 @@
 #![allow(dead_code)]
 
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Output};
 
 pub fn wt_binary() -> PathBuf {
     let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -13,6 +13,47 @@ pub fn wt_binary() -> PathBuf {
     path
 }
 
+/// Run wt in `cwd` with an isolated HOME. Pager off, so diff/log output
+/// comes back on stdout.
+pub fn wt(cwd: &Path, home: &Path, args: &[&str]) -> Output {
+    Command::new(wt_binary())
+        .args(args)
+        .current_dir(cwd)
+        .env("HOME", home)
+        .env("GIT_PAGER", "cat")
+        .output()
+        .expect("failed to run wt")
+}
+
+/// `wt new <branch>` via the path file; returns the worktree path.
+pub fn new_worktree(dir: &Path, repo: &Path, home: &Path, branch: &str) -> PathBuf {
+    let path_file = create_path_file(dir);
+    let output = wt(
+        repo,
+        home,
+        &["new", branch, "--path-file", path_file.to_str().unwrap()],
+    );
+    assert!(
+        output.status.success(),
+        "wt new failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    PathBuf::from(read_path_file(&path_file).trim())
+}
+
+/// Write `name` and commit everything in `dir`.
+pub fn commit_file(dir: &Path, name: &str, content: &str, message: &str) {
+    std::fs::write(dir.join(name), content).unwrap();
+    for args in [&["add", "."][..], &["commit", "-m", message]] {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+}
+
 pub fn create_path_file(dir: &Path) -> PathBuf {
     dir.join(".wt-path")
 }
@@ -0,0 +1,82 @@
+// ===========================================================================
+// Integration Tests - Library Workspace Facade (no CLI binary involved)
+// ===========================================================================
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use agent_worktree::config::{Config, HooksConfig, MergeStrategy, SyncStrategy};
+use agent_worktree::Workspace;
+
+use common::*;
+
+/// One test per file: `Workspace::open` works from the process cwd.
+#[test]
+fn test_workspace_create_list_merge_remove() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    setup_git_repo(&repo);
+    std::env::set_current_dir(&repo).unwrap();
+
+    let base_dir = dir.path().join("base");
+    let config = Config {
+        workspaces_dir: base_dir.join("workspaces"),
+        base_dir,
+        merge_strategy: MergeStrategy::Squash,
+        sync_strategy: SyncStrategy::Rebase,
+        copy_files: vec![],
+        copy_respect_gitignore: true,
+        hooks: HooksConfig::default(),
+        trunk: Some("main".into()),
+        max_worktrees: None,
+        min_free_gb: None,
+        editor: None,
+        trunk_rules: vec![],
+        agent_presets: BTreeMap::new(),
+        branch_prefix: None,
+        default_snap_command: None,
+        remote: "origin".into(),
+    };
+    let ws = Workspace::open(config).unwrap();
+
+    let path = ws.create("lib-feat", None).unwrap();
+    assert!(path.is_dir());
+    let listed = ws.list().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].branch.as_deref(), Some("lib-feat"));
+
+    // Nothing committed yet: nothing to merge
+    assert!(!ws.merge("lib-feat", MergeStrategy::Squash).unwrap());
+
+    std::fs::write(path.join("lib.txt"), "lib\n").unwrap();
+    for args in [&["add", "."][..], &["commit", "-m", "Add lib"]] {
+        let out = Command::new("git")
+            .args(args)
+            .current_dir(&path)
+            .output()
+            .unwrap();
+        assert!(out.status.success());
+    }
+    // Dirty main repo: refused, without CLI-only advice
+    std::fs::write(repo.join("dirty.txt"), "dirty\n").unwrap();
+    let err = ws
+        .merge("lib-feat", MergeStrategy::Squash)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("uncommitted changes"), "{err}");
+    assert!(!err.contains("--autostash"), "{err}");
+    std::fs::remove_file(repo.join("dirty.txt")).unwrap();
+
+    assert!(ws.merge("lib-feat", MergeStrategy::Squash).unwrap());
+    assert_eq!(
+        std::fs::read_to_string(repo.join("lib.txt")).unwrap(),
+        "lib\n"
+    );
+
+    ws.remove("lib-feat", true).unwrap();
+    assert!(!path.exists());
+    assert!(ws.list().unwrap().is_empty());
+}